@@ -0,0 +1,78 @@
+use asefile::AsepriteFile;
+use bevy_ase::process::{pack_atlas, process_file, ProcessSettings, RawImage};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::path::Path;
+
+fn frames(count: usize, width: u32, height: u32) -> Vec<RawImage> {
+    (0..count)
+        .map(|i| RawImage {
+            width,
+            height,
+            data: vec![i as u8; width as usize * height as usize * 4],
+        })
+        .collect()
+}
+
+// Baseline: copies every pixel into the atlas individually.
+fn pack_atlas_per_pixel(images: &[&RawImage]) -> Vec<u8> {
+    let (width, height) = (images[0].width as usize, images[0].height as usize);
+    let columns = (images.len() as f64).sqrt().ceil() as usize;
+    let rows = (images.len() + columns - 1) / columns;
+    let stride = columns * width * 4;
+    let mut data = vec![0; stride * rows * height];
+    for (index, image) in images.iter().enumerate() {
+        let (x0, y0) = ((index % columns) * width, (index / columns) * height);
+        for y in 0..height {
+            for x in 0..width {
+                let src = (y * width + x) * 4;
+                let dst = (y0 + y) * stride + (x0 + x) * 4;
+                data[dst..dst + 4].copy_from_slice(&image.data[src..src + 4]);
+            }
+        }
+    }
+    data
+}
+
+fn bench_pack_atlas(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pack_atlas");
+    for (count, size) in [(16, 64), (64, 128), (600, 256)] {
+        let frames = frames(count, size, size);
+        let images: Vec<&RawImage> = frames.iter().collect();
+        let id = format!("{}x{}px", count, size);
+        group.bench_with_input(BenchmarkId::new("per_pixel", &id), &images, |b, images| {
+            b.iter(|| pack_atlas_per_pixel(black_box(images)))
+        });
+        group.bench_with_input(BenchmarkId::new("rows", &id), &images, |b, images| {
+            b.iter(|| pack_atlas(black_box(images)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_process_file(c: &mut Criterion) {
+    let mut group = c.benchmark_group("process_file");
+    for path in [
+        "tests/data/tileset.aseprite",
+        "assets/sprites/hello.aseprite",
+    ] {
+        let file = AsepriteFile::read_file(Path::new(path)).unwrap();
+        let serial = ProcessSettings {
+            parallel_frames_threshold: usize::MAX,
+            ..Default::default()
+        };
+        let parallel = ProcessSettings {
+            parallel_frames_threshold: 0,
+            ..Default::default()
+        };
+        group.bench_with_input(BenchmarkId::new("serial", path), &file, |b, file| {
+            b.iter(|| process_file(black_box(file), &serial))
+        });
+        group.bench_with_input(BenchmarkId::new("parallel", path), &file, |b, file| {
+            b.iter(|| process_file(black_box(file), &parallel))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_pack_atlas, bench_process_file);
+criterion_main!(benches);
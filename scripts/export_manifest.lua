@@ -0,0 +1,90 @@
+-- Writes a bevy_ase manifest listing the open sprites which are inside the game's asset folder.
+--
+-- Install by copying this file into Aseprite's scripts folder (File > Scripts > Open Scripts Folder),
+-- then run it from File > Scripts. Tags are exported unless their user data text contains
+-- `noexport`. A tag's repeat count, when set, is written to its tag settings.
+-- Load the written `.ase.json` file with bevy_ase's AseManifestPlugin.
+
+local function escape(text)
+  return text:gsub('\\', '\\\\'):gsub('"', '\\"')
+end
+
+local function normalize(path)
+  return path:gsub('\\', '/')
+end
+
+-- Returns the path relative to the asset folder, or nil if the file is outside of it.
+local function relative_path(asset_folder, filename)
+  local folder = normalize(asset_folder)
+  if folder:sub(-1) ~= '/' then
+    folder = folder .. '/'
+  end
+  local path = normalize(filename)
+  if path:sub(1, #folder) ~= folder then
+    return nil
+  end
+  return path:sub(#folder + 1)
+end
+
+local function tag_entries(sprite)
+  local tags = {}
+  local settings = {}
+  for _, tag in ipairs(sprite.tags) do
+    local user_data = tag.data or ''
+    if not user_data:find('noexport', 1, true) then
+      table.insert(tags, '"' .. escape(tag.name) .. '"')
+      if tag.repeats ~= nil and tag.repeats > 0 then
+        table.insert(settings, '"' .. escape(tag.name) .. '": { "repeat": ' .. tag.repeats .. ' }')
+      end
+    end
+  end
+  return tags, settings
+end
+
+local function manifest_json(asset_folder)
+  local files = {}
+  for _, sprite in ipairs(app.sprites) do
+    local path = relative_path(asset_folder, sprite.filename)
+    if path ~= nil then
+      local tags, settings = tag_entries(sprite)
+      table.insert(files, table.concat({
+        '    {',
+        '      "path": "' .. escape(path) .. '",',
+        '      "tags": [' .. table.concat(tags, ', ') .. '],',
+        '      "tag_settings": {' .. table.concat(settings, ', ') .. '}',
+        '    }',
+      }, '\n'))
+    end
+  end
+  return '{\n  "files": [\n' .. table.concat(files, ',\n') .. '\n  ]\n}\n'
+end
+
+local dialog = Dialog('Export bevy_ase manifest')
+dialog:file{
+  id = 'asset_folder',
+  label = 'Any file in the asset folder',
+  open = true,
+}
+dialog:file{
+  id = 'output',
+  label = 'Manifest',
+  save = true,
+  filename = 'game.ase.json',
+  filetypes = { 'json' },
+}
+dialog:button{ id = 'ok', text = 'Export' }
+dialog:button{ id = 'cancel', text = 'Cancel' }
+dialog:show()
+
+local data = dialog.data
+if not data.ok then
+  return
+end
+local asset_folder = app.fs.filePath(data.asset_folder)
+local output = io.open(data.output, 'w')
+if output == nil then
+  app.alert('Could not write ' .. data.output)
+  return
+end
+output:write(manifest_json(asset_folder))
+output:close()
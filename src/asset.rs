@@ -1,11 +1,22 @@
 pub(crate) mod animation;
 pub(crate) mod ase;
 pub(crate) mod asset_index;
+pub(crate) mod collision;
+pub(crate) mod events;
+pub mod frame_table;
 pub mod slice;
+pub mod snapshot;
+pub(crate) mod text;
 pub(crate) mod tileset;
 
-pub use animation::{Animation, Frame, Sprite};
+pub use animation::{Animation, AseAnimationHandle, Frame, Sprite};
 pub use ase::AseAsset;
 pub use asefile::UserData;
-pub use asset_index::{AseAssetMap, AseFileMap};
-pub use tileset::{TileSize, Tileset};
+pub use asset_index::{AseAssetMap, AseFileMap, NameMatching};
+pub use collision::FrameCollision;
+pub use events::{AnimationEvent, AnimationEvents};
+pub use text::UiTextPlaceholder;
+pub use tileset::{
+    CompositeSource, CompositeTileset, CompositeTilesetError, TileRect, TileSize, TileWriteError,
+    Tileset,
+};
@@ -1,9 +1,9 @@
-use asefile::{AsepriteFile, Tag};
-use bevy::{
-    prelude::*,
-    reflect::TypeUuid,
-    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
-    sprite::TextureAtlas,
+use super::events::{AnimationEvent, AnimationEvents};
+use crate::processing;
+use bevy::{prelude::*, reflect::TypeUuid, sprite::TextureAtlas};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
 };
 
 /// A sprite-based animation.
@@ -12,11 +12,143 @@ use bevy::{
 pub struct Animation {
     frames: Vec<Frame>,
     atlas: Handle<TextureAtlas>,
+    lod_atlases: Vec<Handle<TextureAtlas>>,
+    palette_atlases: Vec<Handle<TextureAtlas>>,
+    repeat: Option<u32>,
+    speed: f32,
+    frame_speeds: Vec<f32>,
+    events: Option<Handle<AnimationEvents>>,
+    color: Option<Color>,
+    flip_x: bool,
+    flip_y: bool,
 }
 impl Animation {
     /// Creates a new Animation with a [Frame] vec and a [TextureAtlas] handle.
     pub fn new(frames: Vec<Frame>, atlas: Handle<TextureAtlas>) -> Self {
-        Animation { frames, atlas }
+        Animation {
+            frames,
+            atlas,
+            lod_atlases: Vec::new(),
+            palette_atlases: Vec::new(),
+            repeat: None,
+            speed: 1.0,
+            frame_speeds: Vec::new(),
+            events: None,
+            color: None,
+            flip_x: false,
+            flip_y: false,
+        }
+    }
+
+    /// Sets the tag's repeat count. See [Animation::repeat].
+    pub fn with_repeat(mut self, repeat: Option<u32>) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Returns how many times the animation's tag repeats, as set in Aseprite.
+    ///
+    /// `Some(0)` repeats forever and `Some(1)` plays once.
+    /// Returns `None` if the repeat count is unknown.
+    pub fn repeat(&self) -> Option<u32> {
+        self.repeat
+    }
+
+    /// Sets the playback speed multiplier. See [Animation::speed].
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Returns the playback speed multiplier of the whole animation.
+    ///
+    /// Imported animations read it from their tag's user data text, e.g. `speed:1.5`.
+    /// Defaults to 1.
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Sets the playback speed multiplier of the whole animation.
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed;
+    }
+
+    /// Sets a speed multiplier per frame, indexed like [Animation::frames].
+    /// Frames without a multiplier play at speed 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_ase::asset::Animation;
+    ///
+    /// // Linger on the wind-up frame of an attack, then strike quickly.
+    /// fn tune_attack(mut animations: ResMut<Assets<Animation>>, attack: Handle<Animation>) {
+    ///     if let Some(animation) = animations.get_mut(&attack) {
+    ///         animation.set_frame_speeds(vec![0.5, 2.0, 2.0]);
+    ///     }
+    /// }
+    /// ```
+    pub fn set_frame_speeds(&mut self, frame_speeds: Vec<f32>) {
+        self.frame_speeds = frame_speeds;
+    }
+
+    /// Returns the combined speed multiplier for a frame:
+    /// the animation's speed times the frame's own multiplier.
+    ///
+    /// The player divides frame durations by this value. A speed of zero pauses on the frame.
+    pub fn frame_speed(&self, frame: usize) -> f32 {
+        self.speed * self.frame_speeds.get(frame).copied().unwrap_or(1.0)
+    }
+
+    /// Sets the animation's events. See [Animation::events].
+    pub fn with_events(mut self, events: Handle<AnimationEvents>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Returns a handle to the animation's [AnimationEvents], if it has any events.
+    ///
+    /// The [player](crate::player) sends an [AnimationEventFired](crate::player::AnimationEventFired)
+    /// event for each event on a frame it shows.
+    pub fn events(&self) -> Option<&Handle<AnimationEvents>> {
+        self.events.as_ref()
+    }
+
+    /// Sets the animation's color. See [Animation::color].
+    pub fn with_color(mut self, color: Option<Color>) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Returns the color of the animation's tag, as set in Aseprite.
+    ///
+    /// Spawn a sprite with an [AseTint](crate::tint::AseTint) to tint it with this color.
+    pub fn color(&self) -> Option<Color> {
+        self.color
+    }
+
+    /// Sets whether the animation's frames are mirrored. See [Animation::flip_x].
+    pub fn with_flip(mut self, flip_x: bool, flip_y: bool) -> Self {
+        self.flip_x = flip_x;
+        self.flip_y = flip_y;
+        self
+    }
+
+    /// Returns true if the animation's frames are shown mirrored horizontally.
+    ///
+    /// Imported animations are mirrored variants of another tag when that tag's user data
+    /// text has a `mirror:{axes}={tag_name}` entry, e.g. `mirror:x=walk_left`. They reuse the
+    /// tag's frames, so left and right facings only need to be drawn once.
+    /// The [player](crate::player) flips its sprite while playing a mirrored animation.
+    pub fn flip_x(&self) -> bool {
+        self.flip_x
+    }
+
+    /// Returns true if the animation's frames are shown mirrored vertically.
+    /// See [Animation::flip_x].
+    pub fn flip_y(&self) -> bool {
+        self.flip_y
     }
 
     /// Returns a reference to the animation's [Frame] vec.
@@ -24,10 +156,280 @@ impl Animation {
         &self.frames
     }
 
+    /// Returns the total duration of the animation's frames in milliseconds, as authored.
+    ///
+    /// Speed multipliers are not applied.
+    pub fn duration_ms(&self) -> u32 {
+        self.frames.iter().map(|frame| frame.duration_ms).sum()
+    }
+
+    /// Returns the index of the frame shown at a normalized time of the animation.
+    ///
+    /// `time` runs from 0 at the start of the first frame to 1 at the end of the last frame,
+    /// and is clamped to that range. Frames take up time in proportion to their authored
+    /// durations. Returns `None` if the animation has no frames.
+    ///
+    /// This suits scrubbing through an animation in editor and preview UIs.
+    /// See [AnimationPreview](crate::preview::AnimationPreview).
+    pub fn frame_at(&self, time: f32) -> Option<usize> {
+        let last = self.frames.len().checked_sub(1)?;
+        let elapsed = time.clamp(0.0, 1.0) * self.duration_ms() as f32;
+        let mut end = 0.0;
+        for (index, frame) in self.frames.iter().enumerate() {
+            end += frame.duration_ms as f32;
+            if elapsed < end {
+                return Some(index);
+            }
+        }
+        Some(last)
+    }
+
+    /// Returns true if a point hits a visible pixel of the given frame.
+    ///
+    /// The point is relative to the center of a sprite showing the frame, with y pointing up,
+    /// as for a sprite with the default [Anchor](bevy::sprite::Anchor). Pixels with non-zero alpha
+    /// are visible; for single-channel atlases, pixels with a non-zero value are.
+    /// Returns false if the frame, its atlas or the atlas image is not available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_ase::asset::Animation;
+    /// use bevy_ase::player::AnimationPlayer;
+    ///
+    /// // Checks a world-space cursor position against each animated sprite.
+    /// fn clicked_sprites(
+    ///     cursor: Vec2,
+    ///     sprites: Query<(Entity, &AnimationPlayer, &GlobalTransform)>,
+    ///     animations: Res<Assets<Animation>>,
+    ///     atlases: Res<Assets<TextureAtlas>>,
+    ///     images: Res<Assets<Image>>,
+    /// ) -> Vec<Entity> {
+    ///     sprites
+    ///         .iter()
+    ///         .filter(|(_, player, transform)| {
+    ///             let local = transform.affine().inverse().transform_point3(cursor.extend(0.0));
+    ///             animations.get(player.animation()).map_or(false, |animation| {
+    ///                 animation.hit_test(player.frame(), local.truncate(), &atlases, &images)
+    ///             })
+    ///         })
+    ///         .map(|(entity, _, _)| entity)
+    ///         .collect()
+    /// }
+    /// ```
+    pub fn hit_test(
+        &self,
+        frame: usize,
+        local_point: Vec2,
+        atlases: &Assets<TextureAtlas>,
+        images: &Assets<Image>,
+    ) -> bool {
+        let Some(frame) = self.frames.get(frame) else {
+            return false;
+        };
+        let Some(atlas) = atlases.get(&self.atlas) else {
+            return false;
+        };
+        let (Some(rect), Some(image)) = (
+            atlas.textures.get(frame.sprite.atlas_index as usize),
+            images.get(&atlas.texture),
+        ) else {
+            return false;
+        };
+        // Pixel position within the frame, from its top-left corner with y pointing down.
+        let size = rect.size();
+        let pixel = Vec2::new(local_point.x + size.x / 2.0, size.y / 2.0 - local_point.y);
+        if pixel.x < 0.0 || pixel.y < 0.0 || pixel.x >= size.x || pixel.y >= size.y {
+            return false;
+        }
+        let Some(pixel_bytes) = processing::pixel_bytes(image) else {
+            return false;
+        };
+        let (x, y) = (
+            (rect.min.x + pixel.x) as usize,
+            (rect.min.y + pixel.y) as usize,
+        );
+        let stride = image.texture_descriptor.size.width as usize * pixel_bytes;
+        // Alpha is the last channel of RGBA pixels. Single-channel pixels are their own alpha.
+        let alpha = y * stride + x * pixel_bytes + pixel_bytes - 1;
+        image.data.get(alpha).map_or(false, |alpha| *alpha > 0)
+    }
+
     /// Returns a cloned handle to the animation's [TextureAtlas].
+    ///
+    /// Imported animations use the file's atlas, or their tag's own atlas with
+    /// [ProcessSettings::atlas_per_tag](crate::process::ProcessSettings::atlas_per_tag).
     pub fn atlas(&self) -> Handle<TextureAtlas> {
         self.atlas.clone()
     }
+
+    /// Sets the downscaled copies of the animation's atlas, from the largest to the smallest.
+    /// See [Animation::lod_atlas].
+    pub fn with_lod_atlases(mut self, lod_atlases: Vec<Handle<TextureAtlas>>) -> Self {
+        self.lod_atlases = lod_atlases;
+        self
+    }
+
+    /// Returns the number of downscaled atlases, not counting the full-size atlas.
+    pub fn lod_levels(&self) -> u32 {
+        self.lod_atlases.len() as u32
+    }
+
+    /// Returns a cloned handle to the animation's atlas at the given level of detail.
+    ///
+    /// Level 0 is the full-size atlas, level 1 is at half size, level 2 at a quarter, and so on.
+    /// Levels beyond [Animation::lod_levels] return the smallest atlas. Downscaled atlases
+    /// share the full-size atlas' layout, so frames keep their atlas index at every level.
+    /// Imported animations have them with [ProcessSettings::lod_levels](crate::process::ProcessSettings::lod_levels).
+    ///
+    /// The [player](crate::player) shows the level set with
+    /// [AnimationPlayer::set_lod_level](crate::player::AnimationPlayer::set_lod_level).
+    pub fn lod_atlas(&self, level: u32) -> Handle<TextureAtlas> {
+        match level {
+            0 => self.atlas.clone(),
+            _ => self
+                .lod_atlases
+                .get(level as usize - 1)
+                .or(self.lod_atlases.last())
+                .unwrap_or(&self.atlas)
+                .clone(),
+        }
+    }
+
+    /// Sets the copies of the animation's atlas recolored with variant palettes, in order.
+    /// See [Animation::palette_atlas].
+    pub fn with_palette_atlases(mut self, palette_atlases: Vec<Handle<TextureAtlas>>) -> Self {
+        self.palette_atlases = palette_atlases;
+        self
+    }
+
+    /// Returns a cloned handle to the animation's atlas recolored with the given variant palette.
+    ///
+    /// Palettes are numbered from 1, see
+    /// [AseAssetMap::atlas_for_palette](super::AseAssetMap::atlas_for_palette). Palette 0,
+    /// and palettes which were not baked, return the original atlas.
+    ///
+    /// The [player](crate::player) shows the palette set with
+    /// [AnimationPlayer::set_palette](crate::player::AnimationPlayer::set_palette).
+    pub fn palette_atlas(&self, number: u32) -> Handle<TextureAtlas> {
+        let palette_atlas = match number {
+            0 => None,
+            _ => self.palette_atlases.get(number as usize - 1),
+        };
+        palette_atlas.unwrap_or(&self.atlas).clone()
+    }
+
+    /// Returns the approximate memory used by the animation, in bytes.
+    ///
+    /// Counts the textures of its atlas, downscaled atlases and recolored atlases, plus its frame data.
+    /// Imported animations of a file usually share one atlas, so summing this over a file's animations
+    /// counts the atlas repeatedly; [memory_report](crate::memory_report) reports each file once.
+    pub fn approx_size_bytes(
+        &self,
+        atlases: &Assets<TextureAtlas>,
+        images: &Assets<Image>,
+    ) -> usize {
+        let atlas_bytes: usize = std::iter::once(&self.atlas)
+            .chain(&self.lod_atlases)
+            .chain(&self.palette_atlases)
+            .filter_map(|handle| atlases.get(handle))
+            .filter_map(|atlas| images.get(&atlas.texture))
+            .map(|image| image.data.len())
+            .sum();
+        atlas_bytes
+            + std::mem::size_of::<Self>()
+            + self.frames.len() * std::mem::size_of::<Frame>()
+            + self.frame_speeds.len() * std::mem::size_of::<f32>()
+    }
+}
+
+/// A [Handle] to an imported [Animation], along with the file and tag it came from.
+///
+/// Returned by [AseFileMap::animation](super::AseFileMap::animation). Its [Display](fmt::Display)
+/// output names the animation, e.g. `sprites/hero.aseprite#Animation/walk (8 frames)`,
+/// so references can be logged meaningfully instead of as opaque handle ids.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::asset::AseFileMap;
+/// use bevy_ase::player::AnimationPlayer;
+///
+/// fn spawn_hero(mut commands: Commands, file_map: Res<AseFileMap>) {
+///     let Some(walk) = file_map.animation("sprites/hero.aseprite", "walk") else {
+///         return;
+///     };
+///     info!("Spawning hero with {}", walk);
+///     commands.spawn((SpriteSheetBundle::default(), AnimationPlayer::new(walk.into())));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AseAnimationHandle {
+    handle: Handle<Animation>,
+    path: PathBuf,
+    tag_name: String,
+    frame_count: u32,
+}
+
+impl AseAnimationHandle {
+    pub(crate) fn new(
+        handle: Handle<Animation>,
+        path: PathBuf,
+        tag_name: String,
+        frame_count: u32,
+    ) -> Self {
+        Self {
+            handle,
+            path,
+            tag_name,
+            frame_count,
+        }
+    }
+
+    /// Returns the animation's handle.
+    pub fn handle(&self) -> &Handle<Animation> {
+        &self.handle
+    }
+
+    /// Returns the animation's handle, dropping the metadata.
+    pub fn into_handle(self) -> Handle<Animation> {
+        self.handle
+    }
+
+    /// Returns the path of the Aseprite file the animation was imported from.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the name of the animation's tag. Aliases are resolved to the tag's own name.
+    pub fn tag_name(&self) -> &str {
+        &self.tag_name
+    }
+
+    /// Returns the number of frames in the animation.
+    pub fn frame_count(&self) -> u32 {
+        self.frame_count
+    }
+}
+
+impl From<AseAnimationHandle> for Handle<Animation> {
+    fn from(animation: AseAnimationHandle) -> Self {
+        animation.handle
+    }
+}
+
+impl fmt::Display for AseAnimationHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}#Animation/{} ({} frames)",
+            self.path.display(),
+            self.tag_name,
+            self.frame_count
+        )
+    }
 }
 
 /// The sprite of an animation frame. Refers to an item in a sprite atlas.
@@ -41,27 +443,8 @@ pub(crate) struct SpriteData<T> {
     pub(crate) frame: u32,
     pub(crate) texture: T,
     pub(crate) duration: u32,
-}
-impl SpriteData<Image> {
-    pub(crate) fn new(ase: &AsepriteFile, frame: u32) -> Self {
-        let img = ase.frame(frame).image();
-        let size = Extent3d {
-            width: ase.width() as u32,
-            height: ase.height() as u32,
-            depth_or_array_layers: 1,
-        };
-        let texture = Image::new_fill(
-            size,
-            TextureDimension::D2,
-            img.as_raw(),
-            TextureFormat::Rgba8UnormSrgb,
-        );
-        Self {
-            frame,
-            texture,
-            duration: ase.frame(frame).duration(),
-        }
-    }
+    pub(crate) opaque_bounds: Option<Rect>,
+    pub(crate) changed_bounds: Option<Rect>,
 }
 
 /// A single frame in an [Animation].
@@ -71,28 +454,31 @@ pub struct Frame {
     pub sprite: Sprite,
     /// The duration of this frame in milliseconds.
     pub duration_ms: u32,
+    /// The tight bounds of the frame's pixels with non-zero alpha, in pixels from the
+    /// canvas' top-left corner with y pointing down. See [slice::to_sprite_space](crate::asset::slice::to_sprite_space)
+    /// to convert them into sprite space.
+    ///
+    /// `None` if the frame is fully transparent, or if its image was not decoded.
+    pub opaque_bounds: Option<Rect>,
+    /// The bounds of the pixels which differ from the previous frame of the file, in the same
+    /// space as `opaque_bounds`. Useful for partial texture updates and frame-diff effects.
+    ///
+    /// The file's first frame is compared with its last. Frames are compared with their
+    /// neighbours in the file, not in the animation's tag. `None` if no pixel changed,
+    /// and the whole canvas if frame images were not decoded.
+    pub changed_bounds: Option<Rect>,
 }
 
 #[derive(Debug)]
 pub(crate) struct AnimationData {
     pub(crate) tag_name: Option<String>,
     pub(crate) sprites: Vec<usize>,
-}
-impl AnimationData {
-    pub(crate) fn new(ase: &AsepriteFile, sprite_offset: usize) -> Self {
-        Self {
-            tag_name: None,
-            sprites: (0..ase.num_frames())
-                .map(|f| sprite_offset + f as usize)
-                .collect(),
-        }
-    }
-    pub(crate) fn from_tag(sprite_offset: usize, tag: &Tag) -> Self {
-        Self {
-            tag_name: Some(tag.name().to_owned()),
-            sprites: (tag.from_frame()..tag.to_frame() + 1)
-                .map(|f| sprite_offset + f as usize)
-                .collect(),
-        }
-    }
+    pub(crate) repeat: Option<u32>,
+    pub(crate) speed: f32,
+    pub(crate) events: Vec<AnimationEvent>,
+    pub(crate) color: Option<Color>,
+    pub(crate) flip_x: bool,
+    pub(crate) flip_y: bool,
+    // Index of the tag atlas holding the animation's frames, if atlases are packed per tag.
+    pub(crate) tag_atlas: Option<usize>,
 }
@@ -1,3 +1,4 @@
+use crate::process::{FileHeader, Palette};
 use asefile::AsepriteFile;
 use bevy::reflect::TypeUuid;
 use std::path::PathBuf;
@@ -25,6 +26,10 @@ use std::path::PathBuf;
 pub struct AseAsset {
     pub(crate) data: AseData,
     pub(crate) name: PathBuf,
+    // Data read from the file's raw bytes.
+    pub(crate) header: FileHeader,
+    // Variant palettes read from sidecar files.
+    pub(crate) palettes: Vec<Palette>,
 }
 impl AseAsset {
     /// Returns a reference to the asset's file data, if this asset has not yet been processed.
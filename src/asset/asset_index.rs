@@ -1,8 +1,30 @@
 //! Index for assets created by this library.
-use super::{animation::Animation, slice::Slice, tileset::Tileset};
+use super::{
+    animation::{Animation, AseAnimationHandle},
+    ase::AseAsset,
+    collision::FrameCollision,
+    events::AnimationEvents,
+    slice::{self, Slice},
+    text::UiTextPlaceholder,
+    tileset::Tileset,
+};
+use crate::handle_id::{self, label, FilePath};
+use crate::process::AtlasUsage;
+use bevy::asset::{AssetPath, HandleId};
 use bevy::prelude::*;
-use bevy::utils::HashMap;
+use bevy::sprite::Anchor;
+use bevy::utils::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Mutex, PoisonError,
+};
+
+// How many external aliases AseFileMap::animation follows before giving up.
+const MAX_EXTERNAL_ALIAS_HOPS: usize = 8;
+
+// Source of AseAssetMap generations, shared by all files.
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(1);
 
 /// Provides a map to [Handles](Handle) for an Ase file's assets.
 ///
@@ -42,52 +64,790 @@ use std::path::{Path, PathBuf};
 /// [Animation], [Slice], and Tileset assets are mapped to their string name. There may be
 /// more than one asset with the same name. If just one asset is expected,
 /// compose the result with `first()`.
+///
+/// # Aliases and case
+///
+/// Animation and slice lookups can be made more forgiving of naming inconsistencies.
+/// An alias maps an alternative name onto an existing tag or slice name, and
+/// [NameMatching::CaseInsensitive] ignores letter case when looking up names and aliases.
+/// An exact name match always takes priority over an alias or a case-insensitive match.
+///
+/// ```
+/// use bevy_ase::asset::{AseAssetMap, NameMatching};
+///
+/// fn configure(ase_asset_map: &mut AseAssetMap) {
+///     // Look up the "walk_cycle" animation with the name "Walk".
+///     ase_asset_map.add_animation_alias("Walk", "walk_cycle");
+///     // Also accept "WALK", "walk", and so on.
+///     ase_asset_map.set_name_matching(NameMatching::CaseInsensitive);
+/// }
+/// ```
 #[derive(Default, Debug)]
 pub struct AseAssetMap {
     pub(crate) animations: HashMap<String, Handle<Animation>>,
+    // File frame index and atlas index of each frame of each animation.
+    pub(crate) animation_frames: HashMap<String, Vec<(u32, u32)>>,
+    pub(crate) animation_events: HashMap<String, Handle<AnimationEvents>>,
     pub(crate) slices: HashMap<String, Handle<Slice>>,
+    pub(crate) text_placeholders: HashMap<String, Handle<UiTextPlaceholder>>,
     pub(crate) tilesets: HashMap<u32, Handle<Tileset>>,
     pub(crate) textures: HashMap<u32, Handle<Image>>,
+    pub(crate) layer_images: HashMap<(u32, String), Handle<Image>>,
+    pub(crate) frame_collisions: HashMap<u32, Handle<FrameCollision>>,
     pub(crate) atlas: Handle<TextureAtlas>,
+    pub(crate) lod_atlases: Vec<Handle<TextureAtlas>>,
+    pub(crate) palette_atlases: Vec<Handle<TextureAtlas>>,
+    pub(crate) outline_atlas: Option<Handle<TextureAtlas>>,
+    pub(crate) tag_atlases: HashMap<String, Handle<TextureAtlas>>,
+    pub(crate) atlas_usage: Option<AtlasUsage>,
+    pub(crate) shadow_atlas: Option<Handle<TextureAtlas>>,
+    pub(crate) thumbnail: Option<Handle<Image>>,
+    pub(crate) canvas_size: Vec2,
+    // None for square pixels.
+    pub(crate) pixel_scale: Option<Vec2>,
+    pub(crate) pixels_per_unit: Option<f32>,
+    pub(crate) layer_colors: HashMap<String, Color>,
+    pub(crate) layer_z_orders: HashMap<String, f32>,
+    pub(crate) partial: bool,
+    pub(crate) generation: u64,
+    pub(crate) animation_aliases: HashMap<String, String>,
+    pub(crate) slice_aliases: HashMap<String, String>,
+    pub(crate) external_aliases: HashMap<String, (PathBuf, String)>,
+    pub(crate) name_matching: NameMatching,
+    // Frames whose images have not been created yet, and frames requested since.
+    pub(crate) lazy_textures: HashSet<u32>,
+    pub(crate) lazy_requests: Mutex<Vec<u32>>,
 }
 impl AseAssetMap {
     /// Returns the animation with the given tag name.
+    ///
+    /// The name is resolved through this map's aliases and [NameMatching] setting.
     pub fn animation(&self, tag_name: &str) -> Option<&Handle<Animation>> {
-        self.animations.get(tag_name)
+        find_named(
+            &self.animations,
+            &self.animation_aliases,
+            self.name_matching,
+            tag_name,
+        )
+    }
+    /// Returns the animations whose tags are nested in a group, keyed by their name within it.
+    ///
+    /// Tags are grouped with `/` in their names: `attack/light` and `attack/heavy` are returned
+    /// by `animation_group("attack")` as `light` and `heavy`. Nested groups keep their
+    /// remaining path, so `attack/air/down` is returned as `air/down`. The group name is
+    /// compared according to this map's [NameMatching] setting; aliases are not considered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_ase::asset::{Animation, AseFileMap};
+    /// use std::collections::HashMap;
+    ///
+    /// fn attacks(file_map: Res<AseFileMap>) -> HashMap<String, Handle<Animation>> {
+    ///     file_map
+    ///         .get("sprites/hero.aseprite")
+    ///         .map(|asset_map| asset_map.animation_group("attack"))
+    ///         .unwrap_or_default()
+    /// }
+    /// ```
+    pub fn animation_group(&self, group: &str) -> HashMap<String, Handle<Animation>> {
+        let group = group.trim_end_matches('/');
+        self.animations
+            .iter()
+            .filter_map(|(tag_name, handle)| {
+                let prefix = tag_name.get(..group.len())?;
+                let name = tag_name.get(group.len()..)?.strip_prefix('/')?;
+                let matches = match self.name_matching {
+                    NameMatching::Exact => prefix == group,
+                    NameMatching::CaseInsensitive => prefix.to_lowercase() == group.to_lowercase(),
+                };
+                (matches && !name.is_empty()).then(|| (name.to_string(), handle.clone()))
+            })
+            .collect()
+    }
+    /// Returns the events of the animation with the given tag name, if it has any.
+    ///
+    /// The name is resolved through this map's aliases and [NameMatching] setting.
+    pub fn animation_events(&self, tag_name: &str) -> Option<&Handle<AnimationEvents>> {
+        find_named(
+            &self.animation_events,
+            &self.animation_aliases,
+            self.name_matching,
+            tag_name,
+        )
     }
     /// Returns the slice with the given name.
+    ///
+    /// The name is resolved through this map's aliases and [NameMatching] setting.
     pub fn slice(&self, slice_name: &str) -> Option<&Handle<Slice>> {
-        self.slices.get(slice_name)
+        find_named(
+            &self.slices,
+            &self.slice_aliases,
+            self.name_matching,
+            slice_name,
+        )
+    }
+    /// Returns the text placeholder made from the slice with the given name.
+    ///
+    /// See [ProcessSettings::text_slices](crate::process::ProcessSettings::text_slices).
+    /// The name is resolved through this map's slice aliases and [NameMatching] setting.
+    pub fn text_placeholder(&self, slice_name: &str) -> Option<&Handle<UiTextPlaceholder>> {
+        find_named(
+            &self.text_placeholders,
+            &self.slice_aliases,
+            self.name_matching,
+            slice_name,
+        )
     }
     /// Returns the tileset with the given id.
     pub fn tileset(&self, tileset_id: u32) -> Option<&Handle<Tileset>> {
         self.tilesets.get(&tileset_id)
     }
     /// Returns the texture for the given frame index.
+    ///
+    /// If the file was processed without keeping frame images
+    /// (see [ProcessSettings::keep_frame_images](crate::process::ProcessSettings::keep_frame_images)),
+    /// the returned handle is weak, and the first call requests the image to be created
+    /// from the file's atlas. The image is available in `Assets<Image>` from the next time
+    /// [generate_lazy_frame_images](crate::loader::generate_lazy_frame_images) runs.
     pub fn texture(&self, frame_index: u32) -> Option<&Handle<Image>> {
-        self.textures.get(&frame_index)
+        let handle = self.textures.get(&frame_index)?;
+        if self.lazy_textures.contains(&frame_index) {
+            self.lazy_requests
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(frame_index);
+        }
+        Some(handle)
+    }
+    /// Returns the image of a top-level layer or group in the given frame.
+    ///
+    /// Only set for files processed with
+    /// [LayerFlattening::TopLevelGroups](crate::process::LayerFlattening::TopLevelGroups).
+    pub fn layer_image(&self, frame_index: u32, layer_name: &str) -> Option<&Handle<Image>> {
+        self.layer_images.get(&(frame_index, layer_name.to_owned()))
+    }
+    /// Returns the collision shape for the given frame index, if collision shapes were generated.
+    ///
+    /// See [ProcessSettings::collision](crate::process::ProcessSettings::collision).
+    pub fn frame_collision(&self, frame_index: u32) -> Option<&Handle<FrameCollision>> {
+        self.frame_collisions.get(&frame_index)
     }
     /// Returns the texture atlas for the file.
     pub fn atlas(&self) -> &Handle<TextureAtlas> {
         &self.atlas
     }
+    /// Returns the downscaled texture atlas for the given level, if it was generated.
+    ///
+    /// Level 1 is at half size, level 2 at a quarter, and so on. Level 0 is the full-size atlas.
+    /// See [ProcessSettings::lod_levels](crate::process::ProcessSettings::lod_levels).
+    pub fn lod_atlas(&self, level: u32) -> Option<&Handle<TextureAtlas>> {
+        match level {
+            0 => Some(&self.atlas),
+            _ => self.lod_atlases.get(level as usize - 1),
+        }
+    }
+    /// Returns how much of the file's atlas is covered by visible pixels, if it was packed.
+    ///
+    /// Dev tools can list files with a low [fill ratio](AtlasUsage::fill_ratio),
+    /// which should be trimmed or split to save texture memory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_ase::asset::AseFileMap;
+    ///
+    /// fn report_wasted_space(file_map: Res<AseFileMap>) {
+    ///     let usage = file_map
+    ///         .get("sprites/hero.aseprite")
+    ///         .and_then(|asset_map| asset_map.atlas_usage());
+    ///     if let Some(usage) = usage.filter(|usage| usage.wasted_ratio() > 0.75) {
+    ///         warn!("sprites/hero.aseprite: {}", usage);
+    ///     }
+    /// }
+    /// ```
+    pub fn atlas_usage(&self) -> Option<&AtlasUsage> {
+        self.atlas_usage.as_ref()
+    }
+    /// Returns the texture atlas recolored with the given variant palette, if it was baked.
+    ///
+    /// Palettes are numbered from 1, like their sidecar files. Palette 0 is the file's own
+    /// palette, returning the original atlas. Recolored atlases share the original's layout.
+    /// See [AseAssetLoader::palette_variants](crate::loader::AseAssetLoader::palette_variants).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_ase::asset::AseFileMap;
+    ///
+    /// // Spawn goblins in the colors of goblin.palette1.gpl and goblin.palette2.gpl.
+    /// fn spawn_goblins(mut commands: Commands, file_map: Res<AseFileMap>) {
+    ///     let Some(goblin) = file_map.get("sprites/goblin.aseprite") else {
+    ///         return;
+    ///     };
+    ///     for palette in 0..3 {
+    ///         let Some(atlas) = goblin.atlas_for_palette(palette) else {
+    ///             continue;
+    ///         };
+    ///         commands.spawn(SpriteSheetBundle {
+    ///             texture_atlas: atlas.clone(),
+    ///             transform: Transform::from_xyz(palette as f32 * 32.0, 0.0, 0.0),
+    ///             ..default()
+    ///         });
+    ///     }
+    /// }
+    /// ```
+    pub fn atlas_for_palette(&self, number: u32) -> Option<&Handle<TextureAtlas>> {
+        match number {
+            0 => Some(&self.atlas),
+            _ => self.palette_atlases.get(number as usize - 1),
+        }
+    }
+    /// Returns the number of variant palettes the atlas was recolored with.
+    pub fn palette_count(&self) -> u32 {
+        self.palette_atlases.len() as u32
+    }
+    /// Returns the texture atlas of each frame's outline or silhouette, if it was generated.
+    ///
+    /// The atlas shares the file atlas' layout. Show it on a second sprite which follows the
+    /// animated one, e.g. with [spawn_shadow](crate::shadow::spawn_shadow), and toggle its
+    /// visibility for selection highlights or hit flashes.
+    /// See [ProcessSettings::outline](crate::process::ProcessSettings::outline).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_ase::asset::AseAssetMap;
+    /// use bevy_ase::shadow::spawn_shadow;
+    ///
+    /// // Draws a hidden highlight above the sprite, shown while it is selected.
+    /// fn spawn_highlight(commands: &mut Commands, sprite: Entity, asset_map: &AseAssetMap) {
+    ///     if let Some(outline_atlas) = asset_map.outline_atlas() {
+    ///         let highlight = spawn_shadow(commands, sprite, outline_atlas.clone(), 0.1);
+    ///         commands.entity(highlight).insert(Visibility::Hidden);
+    ///     }
+    /// }
+    /// ```
+    pub fn outline_atlas(&self) -> Option<&Handle<TextureAtlas>> {
+        self.outline_atlas.as_ref()
+    }
+    /// Returns the texture atlas of a tag's frames, if atlases were packed per tag.
+    ///
+    /// The name is resolved like in [AseAssetMap::animation]. The tag's animation already
+    /// refers to this atlas with [Animation::atlas]; mirrored animations use the atlas of
+    /// the tag they were generated from, under that tag's name.
+    /// See [ProcessSettings::atlas_per_tag](crate::process::ProcessSettings::atlas_per_tag).
+    pub fn tag_atlas(&self, tag_name: &str) -> Option<&Handle<TextureAtlas>> {
+        find_named(
+            &self.tag_atlases,
+            &self.animation_aliases,
+            self.name_matching,
+            tag_name,
+        )
+    }
+    /// Returns the texture atlas for the file's shadow layer, if it was split off.
+    ///
+    /// See [ProcessSettings::shadow_layer](crate::process::ProcessSettings::shadow_layer).
+    pub fn shadow_atlas(&self) -> Option<&Handle<TextureAtlas>> {
+        self.shadow_atlas.as_ref()
+    }
+    /// Returns the file's thumbnail, a small preview image, if it was generated.
+    ///
+    /// See [ProcessSettings::thumbnail](crate::process::ProcessSettings::thumbnail).
+    pub fn thumbnail(&self) -> Option<&Handle<Image>> {
+        self.thumbnail.as_ref()
+    }
+    /// Returns the size of the file's canvas in pixels.
+    pub fn canvas_size(&self) -> Vec2 {
+        self.canvas_size
+    }
+    /// Returns the scale which keeps the aspect ratio of the file's pixels.
+    ///
+    /// Aseprite supports non-square pixels, e.g. pixels twice as wide as they are high.
+    /// Generated images have square pixels, so apply this scale to the [Transform] of
+    /// sprites showing the file to avoid squashing them. Returns [Vec2::ONE] for square pixels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_ase::asset::AseAssetMap;
+    ///
+    /// fn sprite_transform(asset_map: &AseAssetMap) -> Transform {
+    ///     Transform::from_scale(asset_map.pixel_scale().extend(1.0))
+    /// }
+    /// ```
+    pub fn pixel_scale(&self) -> Vec2 {
+        self.pixel_scale.unwrap_or(Vec2::ONE)
+    }
+    /// Returns how many pixels make up one world unit, if the file or the import settings
+    /// set a value.
+    ///
+    /// See [ProcessSettings::pixels_per_unit](crate::process::ProcessSettings::pixels_per_unit).
+    pub fn pixels_per_unit(&self) -> Option<f32> {
+        self.pixels_per_unit
+    }
+    /// Returns the size of the file's canvas in world units.
+    ///
+    /// Without a [pixels per unit](AseAssetMap::pixels_per_unit) value,
+    /// one pixel is one unit.
+    pub fn world_size(&self) -> Vec2 {
+        self.canvas_size / self.pixels_per_unit.unwrap_or(1.0)
+    }
+    /// Returns the `custom_size` for sprites showing the file's frames, if the file has a
+    /// [pixels per unit](AseAssetMap::pixels_per_unit) value.
+    ///
+    /// Without one, sprites keep their pixel size and need no custom size.
+    pub fn custom_size(&self) -> Option<Vec2> {
+        self.pixels_per_unit.map(|_| self.world_size())
+    }
+    /// Returns the size of a UI node showing the file's frames.
+    ///
+    /// `ui_scale` is the number of UI pixels per world unit.
+    pub fn node_size(&self, ui_scale: f32) -> Size {
+        let size = self.world_size() * ui_scale;
+        Size::new(Val::Px(size.x), Val::Px(size.y))
+    }
+    /// Returns a [SpriteSheetBundle] showing the file's atlas, sized by its
+    /// [pixels per unit](AseAssetMap::pixels_per_unit) value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_ase::asset::AseFileMap;
+    ///
+    /// // Heroes drawn at 32px and slimes drawn at 16px both end up one unit high,
+    /// // if their files set `ppu:32` and `ppu:16`.
+    /// fn spawn_characters(mut commands: Commands, file_map: Res<AseFileMap>) {
+    ///     for path in ["sprites/hero.aseprite", "sprites/slime.aseprite"] {
+    ///         if let Some(asset_map) = file_map.get(path) {
+    ///             commands.spawn(asset_map.sprite_sheet_bundle());
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn sprite_sheet_bundle(&self) -> SpriteSheetBundle {
+        SpriteSheetBundle {
+            sprite: TextureAtlasSprite {
+                custom_size: self.custom_size(),
+                ..default()
+            },
+            texture_atlas: self.atlas.clone(),
+            ..default()
+        }
+    }
+    /// Converts a pixel position on the file's canvas into an offset from the origin
+    /// of a sprite showing the canvas with the given [Anchor].
+    ///
+    /// Pixel positions are measured from the canvas' top-left corner, with y pointing down.
+    /// Add 0.5 to target the center of a pixel. The returned offset has y pointing up,
+    /// as used by [Transform]. It does not include [AseAssetMap::pixel_scale], which
+    /// applies through the sprite's Transform.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::{prelude::*, sprite::Anchor};
+    /// use bevy_ase::asset::AseFileMap;
+    ///
+    /// // The gun's muzzle is drawn at pixel (27, 9).
+    /// fn muzzle_offset(file_map: Res<AseFileMap>) -> Option<Vec3> {
+    ///     let asset_map = file_map.get("sprites/hero.aseprite")?;
+    ///     let offset = asset_map.pixel_to_local(Vec2::new(27.5, 9.5), &Anchor::BottomCenter);
+    ///     Some(offset.extend(0.0))
+    /// }
+    /// ```
+    pub fn pixel_to_local(&self, pixel: Vec2, anchor: &Anchor) -> Vec2 {
+        slice::to_sprite_point(pixel, self.canvas_size) - anchor.as_vec() * self.canvas_size
+    }
+    /// Returns the color of the layer with the given name, as set in Aseprite.
+    ///
+    /// Layers without a color are not included.
+    pub fn layer_color(&self, layer_name: &str) -> Option<Color> {
+        self.layer_colors.get(layer_name).copied()
+    }
+    /// Returns the z-order of the layer with the given name, as set in its user data.
+    ///
+    /// Artists set a layer's z-order with a `z:{value}` entry in its user data text,
+    /// e.g. `z:5`. Layers without one are not included. See [AseZOrder](crate::z_order::AseZOrder)
+    /// to apply it to a sprite.
+    pub fn layer_z_order(&self, layer_name: &str) -> Option<f32> {
+        self.layer_z_orders.get(layer_name).copied()
+    }
+
+    /// Returns true if some of the file's contents could not be imported.
+    ///
+    /// Such files are imported without the unsupported contents, e.g. tilemap data
+    /// written by a newer Aseprite version. An
+    /// [AseCompatibilityWarning](crate::loader::AseCompatibilityWarning) event describes them.
+    pub fn is_partial(&self) -> bool {
+        self.partial
+    }
+
+    /// Returns the generation of the file's assets, which changes on every (re)import.
+    ///
+    /// Systems caching data derived from the file, e.g. colliders built from its slices,
+    /// can store the generation they were built from, and rebuild when it differs.
+    /// Generations are unique across all files and increase with each import, so a file
+    /// which is unloaded and imported again never returns to an earlier generation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_ase::asset::AseFileMap;
+    ///
+    /// #[derive(Component)]
+    /// struct Colliders {
+    ///     generation: u64,
+    /// }
+    ///
+    /// fn rebuild_colliders(file_map: Res<AseFileMap>, mut query: Query<&mut Colliders>) {
+    ///     let Some(asset_map) = file_map.get("sprites/level.aseprite") else {
+    ///         return;
+    ///     };
+    ///     for mut colliders in &mut query {
+    ///         if colliders.generation != asset_map.generation() {
+    ///             // Rebuild from the file's slices here.
+    ///             colliders.generation = asset_map.generation();
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+    /// Returns true if the animation with the given tag name exists.
+    ///
+    /// The name is resolved like in [AseAssetMap::animation].
+    pub fn contains_animation(&self, tag_name: &str) -> bool {
+        self.animation(tag_name).is_some()
+    }
+    /// Returns true if the slice with the given name exists.
+    ///
+    /// The name is resolved like in [AseAssetMap::slice].
+    pub fn contains_slice(&self, slice_name: &str) -> bool {
+        self.slice(slice_name).is_some()
+    }
+    /// Returns true if the tileset with the given id exists.
+    pub fn contains_tileset(&self, tileset_id: u32) -> bool {
+        self.tilesets.contains_key(&tileset_id)
+    }
+    /// Returns true if the texture for the given frame index exists.
+    ///
+    /// Unlike [AseAssetMap::texture], this does not request lazy frame images.
+    pub fn contains_texture(&self, frame_index: u32) -> bool {
+        self.textures.contains_key(&frame_index)
+    }
+    /// Returns the labels of all sub-assets generated for this file, sorted.
+    ///
+    /// Labels take the forms documented in [handle_id](crate::handle_id).
+    /// Aliases are not included.
+    pub fn labels(&self) -> Vec<String> {
+        let mut labels: Vec<String> = self
+            .animations
+            .keys()
+            .map(|tag_name| label::animation(tag_name))
+            .chain(
+                self.animation_events
+                    .keys()
+                    .map(|tag_name| label::animation_events(tag_name)),
+            )
+            .chain(self.slices.keys().map(|name| label::slice(name)))
+            .chain(
+                self.text_placeholders
+                    .keys()
+                    .map(|name| label::text_placeholder(name)),
+            )
+            .chain(self.textures.keys().map(|frame| label::frame_image(*frame)))
+            .chain(
+                self.layer_images
+                    .keys()
+                    .map(|(frame, layer_name)| label::layer_image(*frame, layer_name)),
+            )
+            .chain(
+                self.frame_collisions
+                    .keys()
+                    .map(|frame| label::frame_collision(*frame)),
+            )
+            .chain(
+                self.tilesets
+                    .keys()
+                    .flat_map(|id| [label::tileset(*id), label::tileset_image(*id)]),
+            )
+            .collect();
+        if self.atlas.id() != HandleId::default::<TextureAtlas>() {
+            labels.push(label::atlas());
+        }
+        labels.extend((1..=self.lod_atlases.len() as u32).map(label::lod_atlas));
+        labels.extend((1..=self.palette_count()).map(label::palette_atlas));
+        if self.outline_atlas.is_some() {
+            labels.push(label::outline_atlas());
+        }
+        labels.extend(
+            self.tag_atlases
+                .keys()
+                .map(|tag_name| label::tag_atlas(tag_name)),
+        );
+        if self.shadow_atlas.is_some() {
+            labels.push(label::shadow_atlas());
+        }
+        if self.thumbnail.is_some() {
+            labels.push(label::thumbnail());
+        }
+        labels.sort();
+        labels
+    }
+
+    // Lookup configuration API
+    /// Returns how animation and slice names are matched.
+    pub fn name_matching(&self) -> NameMatching {
+        self.name_matching
+    }
+    /// Sets how animation and slice names are matched.
+    pub fn set_name_matching(&mut self, name_matching: NameMatching) {
+        self.name_matching = name_matching;
+    }
+    /// Registers `alias` as an alternative name for the animation with the given tag name.
+    ///
+    /// Registering the same alias twice replaces the previous target.
+    pub fn add_animation_alias(&mut self, alias: impl Into<String>, tag_name: impl Into<String>) {
+        self.animation_aliases.insert(alias.into(), tag_name.into());
+    }
+    /// Registers `alias` as an alternative name for the slice with the given name.
+    ///
+    /// Registering the same alias twice replaces the previous target.
+    pub fn add_slice_alias(&mut self, alias: impl Into<String>, slice_name: impl Into<String>) {
+        self.slice_aliases.insert(alias.into(), slice_name.into());
+    }
+    /// Registers `alias` as a name for the animation with the given tag name in another file.
+    ///
+    /// [AseFileMap::animation] follows external aliases into the other file's map, so
+    /// characters can share animations without duplicating their frames. Imported tags
+    /// register one with an `alias:{path}#{tag_name}` entry in their user data text;
+    /// see [ExternalAlias](crate::process::ExternalAlias).
+    ///
+    /// Registering the same alias twice replaces the previous target.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_ase::asset::AseFileMap;
+    ///
+    /// let mut file_map = AseFileMap::default();
+    /// file_map
+    ///     .get_mut("sprites/villager.aseprite")
+    ///     .add_external_alias("walk", "sprites/hero.aseprite", "walk");
+    /// // Once the hero is imported, this returns the hero's walk animation.
+    /// let walk = file_map.animation("sprites/villager.aseprite", "walk");
+    /// ```
+    pub fn add_external_alias(
+        &mut self,
+        alias: impl Into<String>,
+        path: impl FilePath,
+        tag_name: impl Into<String>,
+    ) {
+        let target = (path.file_path().to_path_buf(), tag_name.into());
+        self.external_aliases.insert(alias.into(), target);
+    }
+    /// Returns the file path and tag name an external alias points to.
+    ///
+    /// The alias is resolved through this map's animation aliases and [NameMatching] setting.
+    pub fn external_alias(&self, alias: &str) -> Option<(&Path, &str)> {
+        let (path, tag_name) = find_named(
+            &self.external_aliases,
+            &self.animation_aliases,
+            self.name_matching,
+            alias,
+        )?;
+        Some((path.as_path(), tag_name.as_str()))
+    }
+    /// Removes an external alias. Returns the file path and tag name it pointed to, if any.
+    pub fn remove_external_alias(&mut self, alias: &str) -> Option<(PathBuf, String)> {
+        self.external_aliases.remove(alias)
+    }
+    /// Removes an animation alias. Returns the tag name it pointed to, if any.
+    pub fn remove_animation_alias(&mut self, alias: &str) -> Option<String> {
+        self.animation_aliases.remove(alias)
+    }
+    /// Removes a slice alias. Returns the slice name it pointed to, if any.
+    pub fn remove_slice_alias(&mut self, alias: &str) -> Option<String> {
+        self.slice_aliases.remove(alias)
+    }
 
     // Insert API
-    pub(crate) fn insert_animation(&mut self, tag_name: String, handle: Handle<Animation>) {
+    pub(crate) fn insert_animation(
+        &mut self,
+        tag_name: String,
+        handle: Handle<Animation>,
+        frames: Vec<(u32, u32)>,
+    ) {
+        self.animation_frames.insert(tag_name.clone(), frames);
         self.animations.insert(tag_name, handle);
     }
+    pub(crate) fn insert_animation_events(
+        &mut self,
+        tag_name: String,
+        handle: Handle<AnimationEvents>,
+    ) {
+        self.animation_events.insert(tag_name, handle);
+    }
     pub(crate) fn insert_tileset(&mut self, tileset_id: u32, handle: Handle<Tileset>) {
         self.tilesets.insert(tileset_id, handle);
     }
     pub(crate) fn insert_slice(&mut self, slice_name: String, handle: Handle<Slice>) {
         self.slices.insert(slice_name, handle);
     }
+    pub(crate) fn insert_text_placeholder(
+        &mut self,
+        slice_name: String,
+        handle: Handle<UiTextPlaceholder>,
+    ) {
+        self.text_placeholders.insert(slice_name, handle);
+    }
     pub(crate) fn insert_texture(&mut self, frame_index: u32, handle: Handle<Image>) {
+        self.lazy_textures.remove(&frame_index);
+        self.textures.insert(frame_index, handle);
+    }
+    pub(crate) fn insert_lazy_texture(&mut self, frame_index: u32, handle: Handle<Image>) {
+        self.lazy_textures.insert(frame_index);
         self.textures.insert(frame_index, handle);
     }
+    // Returns requested frames which still need an image, without duplicates.
+    pub(crate) fn take_lazy_requests(&mut self) -> Vec<u32> {
+        let requests = self
+            .lazy_requests
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner);
+        let mut frames: Vec<u32> = requests
+            .drain(..)
+            .filter(|frame| self.lazy_textures.contains(frame))
+            .collect();
+        frames.sort_unstable();
+        frames.dedup();
+        frames
+    }
+    pub(crate) fn insert_layer_image(
+        &mut self,
+        frame_index: u32,
+        layer_name: String,
+        handle: Handle<Image>,
+    ) {
+        self.layer_images.insert((frame_index, layer_name), handle);
+    }
+    pub(crate) fn insert_frame_collision(
+        &mut self,
+        frame_index: u32,
+        handle: Handle<FrameCollision>,
+    ) {
+        self.frame_collisions.insert(frame_index, handle);
+    }
     pub(crate) fn insert_atlas(&mut self, handle: Handle<TextureAtlas>) {
         self.atlas = handle;
     }
+    pub(crate) fn set_lod_atlases(&mut self, handles: Vec<Handle<TextureAtlas>>) {
+        self.lod_atlases = handles;
+    }
+    pub(crate) fn set_palette_atlases(&mut self, handles: Vec<Handle<TextureAtlas>>) {
+        self.palette_atlases = handles;
+    }
+    pub(crate) fn set_outline_atlas(&mut self, handle: Option<Handle<TextureAtlas>>) {
+        self.outline_atlas = handle;
+    }
+    pub(crate) fn insert_tag_atlas(&mut self, tag_name: String, handle: Handle<TextureAtlas>) {
+        self.tag_atlases.insert(tag_name, handle);
+    }
+    pub(crate) fn insert_shadow_atlas(&mut self, handle: Handle<TextureAtlas>) {
+        self.shadow_atlas = Some(handle);
+    }
+    pub(crate) fn insert_thumbnail(&mut self, handle: Handle<Image>) {
+        self.thumbnail = Some(handle);
+    }
+    pub(crate) fn set_canvas_size(&mut self, canvas_size: Vec2) {
+        self.canvas_size = canvas_size;
+    }
+    pub(crate) fn set_pixel_ratio(&mut self, (width, height): (u32, u32)) {
+        self.pixel_scale = (width != height && width > 0 && height > 0)
+            .then(|| Vec2::new(width as f32, height as f32));
+    }
+    pub(crate) fn set_atlas_usage(&mut self, atlas_usage: Option<AtlasUsage>) {
+        self.atlas_usage = atlas_usage;
+    }
+    pub(crate) fn set_pixels_per_unit(&mut self, pixels_per_unit: Option<f32>) {
+        self.pixels_per_unit = pixels_per_unit;
+    }
+    pub(crate) fn next_generation(&mut self) {
+        self.generation = NEXT_GENERATION.fetch_add(1, Ordering::Relaxed);
+    }
+    pub(crate) fn set_partial(&mut self, partial: bool) {
+        self.partial = partial;
+    }
+    pub(crate) fn insert_layer_color(&mut self, layer_name: String, color: Color) {
+        self.layer_colors.insert(layer_name, color);
+    }
+    pub(crate) fn insert_layer_z_order(&mut self, layer_name: String, z_order: f32) {
+        self.layer_z_orders.insert(layer_name, z_order);
+    }
+}
+
+/// Controls how an [AseAssetMap] matches animation and slice names.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum NameMatching {
+    /// Names and aliases must match exactly.
+    #[default]
+    Exact,
+    /// Names and aliases match regardless of letter case.
+    ///
+    /// If several names differ only by case, which of them is returned is unspecified.
+    CaseInsensitive,
+}
+
+// Looks up a named asset: first by exact name, then by alias,
+// then (if enabled) by case-insensitive name and alias.
+fn find_named<'a, T>(
+    assets: &'a HashMap<String, T>,
+    aliases: &HashMap<String, String>,
+    name_matching: NameMatching,
+    name: &str,
+) -> Option<&'a T> {
+    find_named_entry(assets, aliases, name_matching, name).map(|(_, asset)| asset)
+}
+
+// Like find_named, but also returns the asset's own name.
+fn find_named_entry<'a, T>(
+    assets: &'a HashMap<String, T>,
+    aliases: &HashMap<String, String>,
+    name_matching: NameMatching,
+    name: &str,
+) -> Option<(&'a String, &'a T)> {
+    if let Some(entry) = assets.get_key_value(name) {
+        return Some(entry);
+    }
+    if let Some(entry) = aliases
+        .get(name)
+        .and_then(|target| assets.get_key_value(target))
+    {
+        return Some(entry);
+    }
+    if name_matching == NameMatching::Exact {
+        return None;
+    }
+    let name = name.to_lowercase();
+    let ignore_case_get = |target: &str| {
+        let target = target.to_lowercase();
+        assets.iter().find(|(key, _)| key.to_lowercase() == target)
+    };
+    ignore_case_get(&name).or_else(|| {
+        aliases
+            .iter()
+            .find(|(alias, _)| alias.to_lowercase() == name)
+            .and_then(|(_, target)| ignore_case_get(target))
+    })
 }
 
 /// Resource type. Provides map access to Ase asset [Handles](Handle),
@@ -105,7 +865,12 @@ impl AseAssetMap {
 ///
 /// // Use access methods to index both the file and an asset, and get a new handle.
 /// fn get_foo_bar(ase_file_map: AseFileMap) -> Option<Handle<Animation>> {
-///     ase_file_map.animation(Path::new("sprites/foo.aseprite"), "bar")
+///     Some(ase_file_map.animation(Path::new("sprites/foo.aseprite"), "bar")?.into())
+/// }
+///
+/// // Paths may also be given as strings.
+/// fn get_foo_bar_str(ase_file_map: AseFileMap) -> Option<Handle<Animation>> {
+///     Some(ase_file_map.animation("sprites/foo.aseprite", "bar")?.into())
 /// }
 ///
 /// // Or compose with [AseAssetMap] methods to get individual assets.
@@ -123,23 +888,152 @@ impl AseAssetMap {
 pub struct AseFileMap(pub(crate) HashMap<PathBuf, AseAssetMap>);
 impl AseFileMap {
     /// Returns the asset map for the file with the given path.
-    pub fn get(&self, path: &Path) -> Option<&AseAssetMap> {
-        self.0.get(path)
+    ///
+    /// Accepts any [FilePath], such as `&str`, `&Path` or [AssetPath].
+    /// A label in the path is ignored.
+    pub fn get(&self, path: impl FilePath) -> Option<&AseAssetMap> {
+        self.0.get(path.file_path())
+    }
+    /// Returns the asset map for the file loaded by the given handle.
+    ///
+    /// This matches the handle's id against each file's [AssetPath], so it also works for
+    /// files whose paths are not valid Unicode, e.g. from a custom [AssetIo](bevy::asset::AssetIo).
+    pub fn get_by_handle(&self, handle: &Handle<AseAsset>) -> Option<&AseAssetMap> {
+        self.0
+            .iter()
+            .find(|(path, _)| HandleId::from(AssetPath::new_ref(path, None)) == handle.id())
+            .map(|(_, asset_map)| asset_map)
     }
-    pub(crate) fn get_mut(&mut self, path: &Path) -> &mut AseAssetMap {
-        let entry = self.0.entry(path.to_path_buf());
+    /// Returns a mutable asset map for the file with the given path.
+    ///
+    /// If the file has not been imported yet, an empty map is created. This allows
+    /// aliases and name matching to be configured before the file finishes loading;
+    /// the configuration is kept when the file's assets are inserted.
+    pub fn get_mut(&mut self, path: impl FilePath) -> &mut AseAssetMap {
+        let entry = self.0.entry(path.file_path().to_path_buf());
         entry.or_default()
     }
     /// Returns the first animation in an Ase file with the given tag name.
-    pub fn animation(&self, path: &Path, tag_name: &str) -> Option<Handle<Animation>> {
-        self.get(path)?.animation(tag_name).cloned()
+    ///
+    /// The returned [AseAnimationHandle] also holds the file's path, the tag's name and the
+    /// animation's frame count, for logging and debugging. The name is resolved like in
+    /// [AseAssetMap::animation].
+    ///
+    /// If the file has no such animation, [external aliases](AseAssetMap::add_external_alias)
+    /// are followed into other files. The returned handle then holds the other file's path.
+    pub fn animation(&self, path: impl FilePath, tag_name: &str) -> Option<AseAnimationHandle> {
+        let (mut path, mut tag_name) = (path.file_path(), tag_name);
+        // Bounded, so external aliases pointing at each other can't loop forever.
+        for _ in 0..=MAX_EXTERNAL_ALIAS_HOPS {
+            let asset_map = self.get(path)?;
+            let found = find_named_entry(
+                &asset_map.animations,
+                &asset_map.animation_aliases,
+                asset_map.name_matching,
+                tag_name,
+            );
+            let Some((found_name, handle)) = found else {
+                (path, tag_name) = asset_map.external_alias(tag_name)?;
+                continue;
+            };
+            let frame_count = asset_map
+                .animation_frames
+                .get(found_name)
+                .map_or(0, |frames| frames.len() as u32);
+            return Some(AseAnimationHandle::new(
+                handle.clone(),
+                path.to_path_buf(),
+                found_name.clone(),
+                frame_count,
+            ));
+        }
+        None
     }
     /// Returns the first slice in an Ase file with the given name.
-    pub fn slice(&self, path: &Path, slice_name: &str) -> Option<Handle<Slice>> {
+    pub fn slice(&self, path: impl FilePath, slice_name: &str) -> Option<Handle<Slice>> {
         self.get(path)?.slice(slice_name).cloned()
     }
     /// Returns the first tileset in an Ase file with the given name.
-    pub fn tileset(&self, path: &Path, tileset_id: u32) -> Option<Handle<Tileset>> {
+    pub fn tileset(&self, path: impl FilePath, tileset_id: u32) -> Option<Handle<Tileset>> {
         self.get(path)?.tileset(tileset_id).cloned()
     }
+    /// Returns the labels of all sub-assets generated for the file with the given path, sorted.
+    ///
+    /// Returns an empty list if the file has not been imported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_ase::asset::AseFileMap;
+    ///
+    /// fn list_hero_assets(file_map: Res<AseFileMap>) {
+    ///     for label in file_map.labels("sprites/hero.aseprite") {
+    ///         info!("sprites/hero.aseprite#{}", label);
+    ///     }
+    /// }
+    /// ```
+    pub fn labels(&self, path: impl FilePath) -> Vec<String> {
+        self.get(path).map(AseAssetMap::labels).unwrap_or_default()
+    }
+    /// Returns the handle of the sub-asset at a labeled path,
+    /// using the labels documented in [handle_id](crate::handle_id).
+    ///
+    /// Returns `None` if the path has no label, or if the file has no such asset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_ase::asset::{Animation, AseFileMap};
+    ///
+    /// fn get_walk(file_map: Res<AseFileMap>) -> Option<Handle<Animation>> {
+    ///     let handle = file_map.labeled("sprites/hero.aseprite#Animation/walk")?;
+    ///     // Equivalent to:
+    ///     // file_map.animation("sprites/hero.aseprite", "walk")
+    ///     Some(handle.typed())
+    /// }
+    /// ```
+    pub fn labeled<'a>(&self, labeled_path: impl Into<AssetPath<'a>>) -> Option<HandleUntyped> {
+        let labeled_path = labeled_path.into();
+        let asset_map = self.get(labeled_path.path())?;
+        let handle = match label::parse(labeled_path.label()?)? {
+            label::Parsed::Animation(tag_name) => self
+                .animation(labeled_path.path(), tag_name)?
+                .into_handle()
+                .clone_untyped(),
+            label::Parsed::Slice(name) => asset_map.slice(name)?.clone_untyped(),
+            label::Parsed::TextPlaceholder(name) => {
+                asset_map.text_placeholder(name)?.clone_untyped()
+            }
+            label::Parsed::AnimationEvents(tag_name) => {
+                asset_map.animation_events(tag_name)?.clone_untyped()
+            }
+            label::Parsed::Tileset(id) => asset_map.tileset(id)?.clone_untyped(),
+            label::Parsed::FrameImage(frame) => asset_map.texture(frame)?.clone_untyped(),
+            label::Parsed::FrameCollision(frame) => {
+                asset_map.frame_collision(frame)?.clone_untyped()
+            }
+            label::Parsed::Atlas => asset_map.atlas().clone_untyped(),
+            label::Parsed::LodAtlas(level) => asset_map.lod_atlas(level)?.clone_untyped(),
+            label::Parsed::PaletteAtlas(number) => {
+                asset_map.atlas_for_palette(number)?.clone_untyped()
+            }
+            label::Parsed::OutlineAtlas => asset_map.outline_atlas()?.clone_untyped(),
+            label::Parsed::TagAtlas(tag_name) => asset_map.tag_atlas(tag_name)?.clone_untyped(),
+            label::Parsed::ShadowAtlas => asset_map.shadow_atlas()?.clone_untyped(),
+            label::Parsed::Thumbnail => asset_map.thumbnail()?.clone_untyped(),
+            // Tileset images are not indexed, but share their tileset's path.
+            label::Parsed::TilesetImage(id) => {
+                asset_map.tileset(id)?;
+                HandleUntyped::weak(handle_id::tileset_image(labeled_path.path(), id))
+            }
+            label::Parsed::LayerImage(frame, layer_name) => {
+                asset_map.layer_image(frame, layer_name)?.clone_untyped()
+            }
+            // Not imported yet.
+            label::Parsed::ChannelImage(..) => return None,
+        };
+        Some(handle)
+    }
 }
@@ -0,0 +1,59 @@
+use bevy::{math::Vec2, reflect::TypeUuid};
+
+/// A convex collision shape generated from a frame's solid pixels.
+///
+/// Generated when [ProcessSettings::collision](crate::process::ProcessSettings::collision)
+/// is set. The hull is relative to the center of the frame, with y pointing up, and its
+/// points are ordered counter-clockwise. It can be passed to a physics engine as a convex
+/// polygon, or used for precise picking of irregular sprites.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::asset::{AseFileMap, FrameCollision};
+///
+/// // Checks a cursor position relative to the center of a sprite showing frame 0.
+/// fn hits_frame(
+///     file_map: Res<AseFileMap>,
+///     collisions: Res<Assets<FrameCollision>>,
+///     cursor: Vec2,
+/// ) -> bool {
+///     file_map
+///         .get("sprites/rock.aseprite")
+///         .and_then(|asset_map| asset_map.frame_collision(0))
+///         .and_then(|handle| collisions.get(handle))
+///         .map_or(false, |collision| collision.contains(cursor))
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, TypeUuid)]
+#[uuid = "3e5b2a1c-8f4d-4c7a-9b6e-2d1f0a9c8b7e"]
+pub struct FrameCollision {
+    hull: Vec<Vec2>,
+}
+
+impl FrameCollision {
+    /// Creates a collision shape from a convex hull in counter-clockwise order.
+    pub fn new(hull: Vec<Vec2>) -> Self {
+        Self { hull }
+    }
+
+    /// Returns the points of the convex hull, in counter-clockwise order.
+    ///
+    /// Empty if the frame has no solid pixels.
+    pub fn hull(&self) -> &[Vec2] {
+        &self.hull
+    }
+
+    /// Returns true if the point is inside the hull or on its boundary.
+    pub fn contains(&self, point: Vec2) -> bool {
+        if self.hull.len() < 3 {
+            return false;
+        }
+        let next = self.hull.iter().cycle().skip(1);
+        self.hull
+            .iter()
+            .zip(next)
+            .all(|(a, b)| (*b - *a).perp_dot(point - *a) >= 0.0)
+    }
+}
@@ -0,0 +1,73 @@
+use bevy::reflect::TypeUuid;
+
+/// A named event at a frame of an animation, authored as Aseprite user data.
+///
+/// Events are written into the user data text of a cel or a tag,
+/// as `event:{name}` or `event:{name}={payload}` entries separated by whitespace or commas.
+/// Cel events fire when their frame is shown. Tag events fire on the tag's first frame.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AnimationEvent {
+    /// Index of the frame within the animation.
+    pub frame: u32,
+    /// Name of the event.
+    pub name: String,
+    /// Optional payload of the event.
+    pub payload: Option<String>,
+}
+
+/// The events of one animation, sorted by frame.
+///
+/// Imported animations refer to their events with [Animation::events](super::Animation::events).
+/// The events are independent of the [player](crate::player), so other animation
+/// crates can consume them as well.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::asset::{AnimationEvents, AseFileMap};
+///
+/// fn footstep_frames(file_map: Res<AseFileMap>, events: Res<Assets<AnimationEvents>>) -> Vec<u32> {
+///     file_map
+///         .get("sprites/hero.aseprite")
+///         .and_then(|asset_map| asset_map.animation_events("walk"))
+///         .and_then(|handle| events.get(handle))
+///         .map(|events| events.named("footstep").map(|event| event.frame).collect())
+///         .unwrap_or_default()
+/// }
+/// ```
+#[derive(Debug, Clone, Default, TypeUuid)]
+#[uuid = "b0a7c6a4-6c1e-4bb8-9d0b-5d7f3c1f2e4a"]
+pub struct AnimationEvents {
+    events: Vec<AnimationEvent>,
+}
+
+impl AnimationEvents {
+    /// Creates an event list. The events are sorted by frame.
+    pub fn new(mut events: Vec<AnimationEvent>) -> Self {
+        events.sort_by_key(|event| event.frame);
+        Self { events }
+    }
+
+    /// Returns all events, sorted by frame.
+    pub fn events(&self) -> &[AnimationEvent] {
+        &self.events
+    }
+
+    /// Returns the events at the given frame.
+    pub fn at_frame(&self, frame: u32) -> &[AnimationEvent] {
+        let start = self.events.partition_point(|event| event.frame < frame);
+        let end = self.events.partition_point(|event| event.frame <= frame);
+        &self.events[start..end]
+    }
+
+    /// Returns the events with the given name.
+    pub fn named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a AnimationEvent> {
+        self.events.iter().filter(move |event| event.name == name)
+    }
+
+    /// Returns true if there are no events.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
@@ -0,0 +1,126 @@
+//! Stable tables of the frames of imported files, for networked games.
+//!
+//! A [FrameTable] numbers every frame of every animation in a file. Peers can send these
+//! compact ids instead of animation handles, which differ between processes, and look the
+//! frame's tag, offset, file frame and atlas index back up on the other side. Ids only depend
+//! on the file's contents, so peers running different builds agree on them as long as they
+//! import the same file; compare [FrameTable::fingerprint]s to check this.
+//!
+//! With the "manifest" feature enabled, tables implement serde's `Serialize` and
+//! `Deserialize` traits, so a server can send its table to clients.
+//!
+//! # Examples
+//!
+//! ```
+//! use bevy::prelude::*;
+//! use bevy_ase::asset::AseFileMap;
+//!
+//! // Encode a remote player's sprite state as a single integer.
+//! fn frame_id(file_map: &AseFileMap, tag_name: &str, offset: u32) -> Option<u32> {
+//!     let table = file_map.frame_table("sprites/hero.aseprite")?;
+//!     table.id(tag_name, offset)
+//! }
+//! ```
+use super::asset_index::AseFileMap;
+use crate::handle_id::FilePath;
+use std::path::PathBuf;
+
+#[cfg(feature = "manifest")]
+use serde::{Deserialize, Serialize};
+
+/// A single frame of an animation, listed in a [FrameTable].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "manifest", derive(Serialize, Deserialize))]
+pub struct FrameEntry {
+    /// Name of the animation's tag.
+    pub tag_name: String,
+    /// Index of the frame in the animation.
+    pub offset: u32,
+    /// Index of the frame in the file.
+    pub frame: u32,
+    /// Index of the frame's sprite in the animation's atlas.
+    pub atlas_index: u32,
+}
+
+/// Every frame of the animations of one file, numbered by id.
+///
+/// Returned by [AseFileMap::frame_table]. A frame's id is its index in `frames`,
+/// which are sorted by tag name and offset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "manifest", derive(Serialize, Deserialize))]
+pub struct FrameTable {
+    /// The path of the Aseprite file.
+    pub path: PathBuf,
+    /// The frames of all animations, sorted by tag name and offset.
+    pub frames: Vec<FrameEntry>,
+}
+impl FrameTable {
+    /// Returns the id of the frame at `offset` in the animation of `tag_name`.
+    pub fn id(&self, tag_name: &str, offset: u32) -> Option<u32> {
+        let start = self
+            .frames
+            .partition_point(|entry| entry.tag_name.as_str() < tag_name);
+        let entry = self.frames.get(start + offset as usize)?;
+        (entry.tag_name == tag_name && entry.offset == offset)
+            .then_some((start + offset as usize) as u32)
+    }
+
+    /// Returns the frame with the given id.
+    pub fn entry(&self, id: u32) -> Option<&FrameEntry> {
+        self.frames.get(id as usize)
+    }
+
+    /// Returns a hash of the table's frames, the same in every build of this crate.
+    ///
+    /// Peers whose fingerprints differ imported different versions of the file, so their
+    /// frame ids can't be exchanged. The path is not hashed.
+    pub fn fingerprint(&self) -> u64 {
+        // FNV-1a, which unlike std's hashers is specified and stable.
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let mut write = |bytes: &[u8]| {
+            for byte in bytes {
+                hash ^= *byte as u64;
+                hash = hash.wrapping_mul(0x0100_0000_01b3);
+            }
+        };
+        for entry in &self.frames {
+            write(&(entry.tag_name.len() as u32).to_le_bytes());
+            write(entry.tag_name.as_bytes());
+            write(&entry.offset.to_le_bytes());
+            write(&entry.frame.to_le_bytes());
+            write(&entry.atlas_index.to_le_bytes());
+        }
+        hash
+    }
+}
+
+impl AseFileMap {
+    /// Creates a [FrameTable] of the animations of an imported file.
+    ///
+    /// Returns `None` if the file has not been imported. Aliases are not listed; look up the
+    /// tag name an alias points to with [AseFileMap::animation].
+    pub fn frame_table(&self, path: impl FilePath) -> Option<FrameTable> {
+        let path = path.file_path();
+        let asset_map = self.get(path)?;
+        let mut tags: Vec<(&String, &Vec<(u32, u32)>)> =
+            asset_map.animation_frames.iter().collect();
+        tags.sort_by_key(|(tag_name, _)| *tag_name);
+        let frames = tags
+            .into_iter()
+            .flat_map(|(tag_name, frames)| {
+                (0..)
+                    .zip(frames)
+                    .map(|(offset, &(frame, atlas_index))| FrameEntry {
+                        tag_name: tag_name.clone(),
+                        offset,
+                        frame,
+                        atlas_index,
+                    })
+            })
+            .collect();
+        Some(FrameTable {
+            path: path.to_path_buf(),
+            frames,
+        })
+    }
+}
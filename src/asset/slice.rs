@@ -1,6 +1,27 @@
 //! Types for slice data.
+//!
+//! Slice geometry is stored in Aseprite's coordinate space: pixels from the canvas'
+//! top-left corner, with y pointing down. [rect] and [center_rect] return it as a [Rect]
+//! in that space. [sprite_rect] converts it into the space of a centered sprite, with y
+//! pointing up, as used by Bevy's [Transform](bevy::prelude::Transform).
+//!
+//! # Examples
+//!
+//! ```
+//! use bevy::prelude::*;
+//! use bevy_ase::asset::slice::{self, Slice};
+//!
+//! // Returns the hitbox of a 32x32 sprite at the given frame, relative to the sprite's center.
+//! fn hitbox(hitbox: &Slice, frame: u32) -> Option<Rect> {
+//!     let key = hitbox.key_at(frame)?;
+//!     Some(slice::sprite_rect(key, Vec2::new(32.0, 32.0)))
+//! }
+//! ```
 pub use asefile::{Slice9, SliceKey};
-use bevy::reflect::TypeUuid;
+use bevy::{
+    math::{Rect, Vec2},
+    reflect::TypeUuid,
+};
 
 /// A slice is a region of an Ase sprite with a name and optional user data.
 #[derive(Debug, TypeUuid)]
@@ -27,4 +48,55 @@ impl Slice {
             user_data: user_data.clone(),
         }
     }
+
+    /// Returns the key in effect at the given frame.
+    ///
+    /// A key applies from its `from_frame` until the next key's.
+    /// Returns `None` if the frame is before the first key.
+    pub fn key_at(&self, frame: u32) -> Option<&SliceKey> {
+        self.keys
+            .iter()
+            .filter(|key| key.from_frame <= frame)
+            .max_by_key(|key| key.from_frame)
+    }
+}
+
+/// Returns a key's bounds in Aseprite's coordinate space: pixels from the canvas'
+/// top-left corner, with y pointing down.
+pub fn rect(key: &SliceKey) -> Rect {
+    let min = Vec2::new(key.origin.0 as f32, key.origin.1 as f32);
+    let size = Vec2::new(key.size.0 as f32, key.size.1 as f32);
+    Rect::from_corners(min, min + size)
+}
+
+/// Returns the bounds of a key's 9-slice center in Aseprite's coordinate space.
+///
+/// Returns `None` if the key has no 9-slice data.
+pub fn center_rect(key: &SliceKey) -> Option<Rect> {
+    let slice9 = key.slice9.as_ref()?;
+    let min = Vec2::new(
+        (key.origin.0 + slice9.center_x) as f32,
+        (key.origin.1 + slice9.center_y) as f32,
+    );
+    let size = Vec2::new(slice9.center_width as f32, slice9.center_height as f32);
+    Some(Rect::from_corners(min, min + size))
+}
+
+/// Returns a key's bounds relative to the center of a sprite showing the whole canvas,
+/// with y pointing up.
+pub fn sprite_rect(key: &SliceKey, canvas_size: Vec2) -> Rect {
+    to_sprite_space(rect(key), canvas_size)
+}
+
+/// Converts a rect from Aseprite's coordinate space into the space of a centered sprite.
+pub fn to_sprite_space(rect: Rect, canvas_size: Vec2) -> Rect {
+    Rect::from_corners(
+        to_sprite_point(rect.min, canvas_size),
+        to_sprite_point(rect.max, canvas_size),
+    )
+}
+
+/// Converts a point from Aseprite's coordinate space into the space of a centered sprite.
+pub fn to_sprite_point(point: Vec2, canvas_size: Vec2) -> Vec2 {
+    Vec2::new(point.x, canvas_size.y - point.y) - canvas_size / 2.0
 }
@@ -0,0 +1,216 @@
+//! Serializable snapshots of the assets indexed by [AseFileMap].
+//!
+//! A snapshot lists every imported file with the labels and kinds of its generated
+//! sub-assets. Entries are sorted, so two snapshots of the same files compare
+//! and diff cleanly. This is intended for build tooling that generates a manifest of
+//! importable assets, or for CI checks that catch accidentally deleted tags.
+//!
+//! With the "manifest" feature enabled, snapshots implement serde's `Serialize`
+//! and `Deserialize` traits and can be written as RON or JSON.
+//!
+//! # Examples
+//!
+//! ```
+//! use bevy::prelude::*;
+//! use bevy_ase::asset::{Animation, AseFileMap};
+//!
+//! fn print_manifest(file_map: Res<AseFileMap>, animations: Res<Assets<Animation>>) {
+//!     let snapshot = file_map.snapshot(&animations);
+//!     for file in &snapshot.files {
+//!         info!("{}: {} sub-assets", file.path.display(), file.assets.len());
+//!     }
+//! }
+//! ```
+use super::{animation::Animation, asset_index::AseFileMap};
+use crate::handle_id::label;
+use bevy::{asset::HandleId, prelude::*};
+use std::path::PathBuf;
+
+#[cfg(feature = "manifest")]
+use serde::{Deserialize, Serialize};
+
+/// The type of asset generated for a label.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "manifest", derive(Serialize, Deserialize))]
+pub enum AssetKind {
+    /// An [Animation] asset.
+    Animation,
+    /// An [AnimationEvents](super::AnimationEvents) asset.
+    AnimationEvents,
+    /// A [TextureAtlas] asset.
+    Atlas,
+    /// A [FrameCollision](super::FrameCollision) asset for a single frame.
+    FrameCollision,
+    /// An [Image] asset for a single frame.
+    FrameImage,
+    /// An [Image] asset for a top-level layer or group in a single frame.
+    LayerImage,
+    /// A downscaled [TextureAtlas] asset.
+    LodAtlas,
+    /// A [TextureAtlas] asset of each frame's outline or silhouette.
+    OutlineAtlas,
+    /// A [TextureAtlas] asset recolored with a variant palette.
+    PaletteAtlas,
+    /// A [TextureAtlas] asset for the file's shadow layer.
+    ShadowAtlas,
+    /// A [Slice](super::slice::Slice) asset.
+    Slice,
+    /// A [TextureAtlas] asset of a single tag's frames.
+    TagAtlas,
+    /// A [UiTextPlaceholder](super::UiTextPlaceholder) asset.
+    TextPlaceholder,
+    /// An [Image] asset previewing the file.
+    Thumbnail,
+    /// A [Tileset](super::Tileset) asset.
+    Tileset,
+    /// An [Image] asset for a tileset.
+    TilesetImage,
+}
+
+/// A single sub-asset generated from an Aseprite file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "manifest", derive(Serialize, Deserialize))]
+pub struct SubAssetSnapshot {
+    /// The asset label, as generated by the [handle_id](crate::handle_id) functions.
+    pub label: String,
+    /// The type of the generated asset.
+    pub kind: AssetKind,
+    /// The number of frames in this asset. Only set for animations.
+    pub frame_count: Option<u32>,
+}
+
+/// All sub-assets generated from one Aseprite file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "manifest", derive(Serialize, Deserialize))]
+pub struct AseFileSnapshot {
+    /// The path of the Aseprite file.
+    pub path: PathBuf,
+    /// The number of frames in the file.
+    pub frame_count: u32,
+    /// The file's sub-assets, sorted by label.
+    pub assets: Vec<SubAssetSnapshot>,
+}
+
+/// A snapshot of every file in an [AseFileMap].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "manifest", derive(Serialize, Deserialize))]
+pub struct AseFileMapSnapshot {
+    /// The snapshot of each file, sorted by path.
+    pub files: Vec<AseFileSnapshot>,
+}
+
+#[cfg(feature = "manifest")]
+impl AseFileMapSnapshot {
+    /// Serializes this snapshot as pretty-printed RON.
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+    }
+    /// Serializes this snapshot as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+impl AseFileMap {
+    /// Creates a [snapshot](AseFileMapSnapshot) of all files in this map.
+    ///
+    /// Animation frame counts are read from `animations`. Animations which are
+    /// not (yet) present in `animations` have no frame count.
+    pub fn snapshot(&self, animations: &Assets<Animation>) -> AseFileMapSnapshot {
+        let mut files: Vec<AseFileSnapshot> = self
+            .0
+            .iter()
+            .map(|(path, asset_map)| {
+                let mut assets = Vec::new();
+                let mut push = |asset_label: String, kind: AssetKind, frame_count: Option<u32>| {
+                    assets.push(SubAssetSnapshot {
+                        label: asset_label,
+                        kind,
+                        frame_count,
+                    })
+                };
+                for (tag_name, handle) in &asset_map.animations {
+                    let frame_count = animations
+                        .get(handle)
+                        .map(|animation| animation.frames().len() as u32);
+                    push(
+                        label::animation(tag_name),
+                        AssetKind::Animation,
+                        frame_count,
+                    );
+                }
+                for tag_name in asset_map.animation_events.keys() {
+                    push(
+                        label::animation_events(tag_name),
+                        AssetKind::AnimationEvents,
+                        None,
+                    );
+                }
+                if asset_map.atlas.id() != HandleId::default::<TextureAtlas>() {
+                    push(label::atlas(), AssetKind::Atlas, None);
+                }
+                for level in 1..=asset_map.lod_atlases.len() as u32 {
+                    push(label::lod_atlas(level), AssetKind::LodAtlas, None);
+                }
+                for number in 1..=asset_map.palette_count() {
+                    push(label::palette_atlas(number), AssetKind::PaletteAtlas, None);
+                }
+                if asset_map.outline_atlas.is_some() {
+                    push(label::outline_atlas(), AssetKind::OutlineAtlas, None);
+                }
+                for tag_name in asset_map.tag_atlases.keys() {
+                    push(label::tag_atlas(tag_name), AssetKind::TagAtlas, None);
+                }
+                if asset_map.shadow_atlas.is_some() {
+                    push(label::shadow_atlas(), AssetKind::ShadowAtlas, None);
+                }
+                if asset_map.thumbnail.is_some() {
+                    push(label::thumbnail(), AssetKind::Thumbnail, None);
+                }
+                for frame in asset_map.textures.keys() {
+                    push(label::frame_image(*frame), AssetKind::FrameImage, None);
+                }
+                for (frame, layer_name) in asset_map.layer_images.keys() {
+                    push(
+                        label::layer_image(*frame, layer_name),
+                        AssetKind::LayerImage,
+                        None,
+                    );
+                }
+                for frame in asset_map.frame_collisions.keys() {
+                    push(
+                        label::frame_collision(*frame),
+                        AssetKind::FrameCollision,
+                        None,
+                    );
+                }
+                for slice_name in asset_map.slices.keys() {
+                    push(label::slice(slice_name), AssetKind::Slice, None);
+                }
+                for slice_name in asset_map.text_placeholders.keys() {
+                    push(
+                        label::text_placeholder(slice_name),
+                        AssetKind::TextPlaceholder,
+                        None,
+                    );
+                }
+                for tileset_id in asset_map.tilesets.keys() {
+                    push(label::tileset(*tileset_id), AssetKind::Tileset, None);
+                    push(
+                        label::tileset_image(*tileset_id),
+                        AssetKind::TilesetImage,
+                        None,
+                    );
+                }
+                assets.sort_by(|a, b| a.label.cmp(&b.label));
+                AseFileSnapshot {
+                    path: path.clone(),
+                    frame_count: asset_map.textures.len() as u32,
+                    assets,
+                }
+            })
+            .collect();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        AseFileMapSnapshot { files }
+    }
+}
@@ -0,0 +1,52 @@
+use bevy::{math::Rect, reflect::TypeUuid};
+
+/// A region of a UI mockup where localized text is rendered at runtime.
+///
+/// Generated when [ProcessSettings::text_slices](crate::process::ProcessSettings::text_slices)
+/// is set, from slices whose user data text starts with a `text:{key}` entry. Such slices
+/// are imported as placeholders instead of [Slice](super::slice::Slice) assets.
+/// The mocked-up text itself stays in the frame images, so it is best drawn on a hidden
+/// or reference layer.
+///
+/// The rect is taken from the slice's first key, in Aseprite's coordinate space;
+/// [slice::to_sprite_space](super::slice::to_sprite_space) converts it for a centered sprite.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::asset::{AseFileMap, UiTextPlaceholder};
+///
+/// // Positions a text node over the "title" placeholder of a menu mockup.
+/// fn title_style(
+///     file_map: Res<AseFileMap>,
+///     placeholders: Res<Assets<UiTextPlaceholder>>,
+/// ) -> Option<(String, Style)> {
+///     let handle = file_map.get("ui/menu.aseprite")?.text_placeholder("title")?;
+///     let placeholder = placeholders.get(handle)?;
+///     let style = Style {
+///         position_type: PositionType::Absolute,
+///         position: UiRect {
+///             left: Val::Px(placeholder.rect.min.x),
+///             top: Val::Px(placeholder.rect.min.y),
+///             ..default()
+///         },
+///         size: Size::new(
+///             Val::Px(placeholder.rect.width()),
+///             Val::Px(placeholder.rect.height()),
+///         ),
+///         ..default()
+///     };
+///     Some((placeholder.key.clone(), style))
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, TypeUuid)]
+#[uuid = "0cdb961e-8395-4a42-843d-dce3327cdfd8"]
+pub struct UiTextPlaceholder {
+    /// The name of the slice the placeholder was made from.
+    pub name: String,
+    /// The localization key, read from the slice's `text:{key}` user data entry.
+    pub key: String,
+    /// The region for the text, in pixels from the canvas' top-left corner, with y pointing down.
+    pub rect: Rect,
+}
@@ -1,9 +1,5 @@
-use asefile::{AsepriteFile, TilesetImageError};
-use bevy::{
-    prelude::*,
-    reflect::TypeUuid,
-    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
-};
+use asefile::TilesetImageError;
+use bevy::{prelude::*, reflect::TypeUuid};
 use std::fmt;
 
 pub(crate) type TilesetResult<T> = std::result::Result<T, TilesetError>;
@@ -39,28 +35,8 @@ impl From<TilesetImageError> for TilesetError {
     }
 }
 
-fn texture_from(ase: &AsepriteFile, tileset: &asefile::Tileset) -> TilesetResult<Image> {
-    let tileset_id = tileset.id();
-    let tileset = ase
-        .tilesets()
-        .get(tileset_id)
-        .ok_or(TilesetError::MissingId(tileset_id))?;
-    let image = tileset.image();
-    let size = Extent3d {
-        width: image.width(),
-        height: image.height(),
-        depth_or_array_layers: 1,
-    };
-    Ok(Image::new_fill(
-        size,
-        TextureDimension::D2,
-        image.as_raw(),
-        TextureFormat::Rgba8UnormSrgb,
-    ))
-}
-
 /// Width and height of a tile in pixels.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct TileSize {
     /// Width of a tile in pixels.
     pub width: u16,
@@ -68,7 +44,7 @@ pub struct TileSize {
     pub height: u16,
 }
 impl TileSize {
-    fn from_ase(ase_size: &asefile::TileSize) -> Self {
+    pub(crate) fn from_ase(ase_size: &asefile::TileSize) -> Self {
         Self {
             width: ase_size.width(),
             height: ase_size.height(),
@@ -100,6 +76,371 @@ impl Tileset {
         let tile_count = self.tile_count as f32;
         Vec2::new(width as f32, height as f32 * tile_count)
     }
+
+    /// Returns the pixel and UV rects of a tile in the tileset's texture,
+    /// or `None` if the tileset has no such tile.
+    ///
+    /// Tile palettes of level editors can show each tile from these rects,
+    /// and map a picked position back to a tile with [TileRect::contains_uv].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_ase::asset::Tileset;
+    ///
+    /// // Returns the tile under a cursor position in UV coordinates of the tileset's texture.
+    /// fn pick_tile(tileset: &Tileset, uv: Vec2) -> Option<u32> {
+    ///     (0..tileset.tile_count).find(|&index| {
+    ///         tileset
+    ///             .tile_rect(index)
+    ///             .map_or(false, |rect| rect.contains_uv(uv))
+    ///     })
+    /// }
+    /// ```
+    pub fn tile_rect(&self, index: u32) -> Option<TileRect> {
+        strip_tile_rect(self.tile_size, self.tile_count, index)
+    }
+
+    /// Overwrites the pixels of one tile in the tileset's texture, e.g. for in-game tile editors.
+    ///
+    /// Tiles are stored in a vertical strip, so each tile's pixels are one contiguous range of the
+    /// texture's data. `pixels` holds the tile's rows from top to bottom, in the texture's format.
+    /// Bevy uploads the modified texture again before the next frame is rendered.
+    /// To copy tiles on the GPU instead, import files with
+    /// [ProcessSettings::editable_tilesets](crate::process::ProcessSettings::editable_tilesets).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_ase::asset::{TileSize, Tileset};
+    ///
+    /// // Clear the first tile of a tileset with an RGBA texture.
+    /// fn clear_tile(tileset: &Tileset, mut images: ResMut<Assets<Image>>) {
+    ///     let TileSize { width, height } = &tileset.tile_size;
+    ///     let pixels = vec![0; *width as usize * *height as usize * 4];
+    ///     if let Err(error) = tileset.write_tile(&mut images, 0, &pixels) {
+    ///         warn!("Can't clear tile: {}", error);
+    ///     }
+    /// }
+    /// ```
+    pub fn write_tile(
+        &self,
+        images: &mut Assets<Image>,
+        index: u32,
+        pixels: &[u8],
+    ) -> Result<(), TileWriteError> {
+        if index >= self.tile_count {
+            return Err(TileWriteError::IndexOutOfRange {
+                index,
+                tile_count: self.tile_count,
+            });
+        }
+        let image = images
+            .get_mut(&self.texture)
+            .ok_or(TileWriteError::MissingTexture)?;
+        let tile_bytes = image.data.len() / self.tile_count as usize;
+        if pixels.len() != tile_bytes {
+            return Err(TileWriteError::WrongLength {
+                expected: tile_bytes,
+                actual: pixels.len(),
+            });
+        }
+        let start = index as usize * tile_bytes;
+        image.data[start..start + tile_bytes].copy_from_slice(pixels);
+        Ok(())
+    }
+}
+
+/// An error from writing a tile with [Tileset::write_tile].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TileWriteError {
+    /// The tileset has no tile with this index.
+    IndexOutOfRange {
+        /// The index of the tile to write.
+        index: u32,
+        /// The number of tiles in the tileset.
+        tile_count: u32,
+    },
+    /// The tileset's texture is not loaded.
+    MissingTexture,
+    /// The pixel data does not have the length of one tile in the texture's format.
+    WrongLength {
+        /// The length of one tile in bytes.
+        expected: usize,
+        /// The length of the given pixel data.
+        actual: usize,
+    },
+}
+impl fmt::Display for TileWriteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TileWriteError::IndexOutOfRange { index, tile_count } => {
+                write!(f, "Tile {} is out of range for {} tiles", index, tile_count)
+            }
+            TileWriteError::MissingTexture => write!(f, "The tileset's texture is not loaded"),
+            TileWriteError::WrongLength { expected, actual } => {
+                write!(
+                    f,
+                    "Expected {} bytes of tile data, got {}",
+                    expected, actual
+                )
+            }
+        }
+    }
+}
+impl std::error::Error for TileWriteError {}
+
+/// Where a tile is in its tileset's texture. Returned by [Tileset::tile_rect].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TileRect {
+    /// The tile's rect in pixels, with its origin at the top left of the texture.
+    pub pixels: Rect,
+    /// The tile's rect in UV coordinates, from 0 to 1 across the texture.
+    pub uv: Rect,
+}
+impl TileRect {
+    /// Returns true if a point in UV coordinates is within the tile.
+    ///
+    /// Points on the tile's bottom edge belong to the tile below, so each point of
+    /// the texture is within exactly one tile.
+    pub fn contains_uv(&self, uv: Vec2) -> bool {
+        (self.uv.min.x..=self.uv.max.x).contains(&uv.x)
+            && (self.uv.min.y..self.uv.max.y).contains(&uv.y)
+    }
+}
+
+// Tiles are stored in a vertical strip, one tile wide.
+fn strip_tile_rect(tile_size: TileSize, tile_count: u32, index: u32) -> Option<TileRect> {
+    if index >= tile_count {
+        return None;
+    }
+    let (width, height) = (tile_size.width as f32, tile_size.height as f32);
+    let top = height * index as f32;
+    let pixels = Rect::new(0.0, top, width, top + height);
+    let texture_height = height * tile_count as f32;
+    let uv = Rect::new(
+        0.0,
+        pixels.min.y / texture_height,
+        1.0,
+        pixels.max.y / texture_height,
+    );
+    Some(TileRect { pixels, uv })
+}
+
+/// Tilesets from several files merged into one texture.
+///
+/// Levels which mix tiles from several files can render them from a single texture.
+/// The tiles of each source tileset are stacked in order into one vertical strip, so a tile's
+/// index in the composite is its index in its source plus that source's offset.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::asset::{CompositeTileset, Tileset};
+///
+/// fn compose_level_tiles(
+///     grass: Handle<Tileset>,
+///     water: Handle<Tileset>,
+///     tilesets: Res<Assets<Tileset>>,
+///     mut images: ResMut<Assets<Image>>,
+///     mut composites: ResMut<Assets<CompositeTileset>>,
+/// ) {
+///     match CompositeTileset::compose(&[grass, water.clone()], &tilesets, &mut images) {
+///         Ok(composite) => {
+///             // Water tile 3 in the combined texture.
+///             let index = composite.tile_index(&water, 3);
+///             composites.add(composite);
+///         }
+///         Err(error) => warn!("Can't compose tilesets: {}", error),
+///     }
+/// }
+/// ```
+#[derive(Debug, TypeUuid)]
+#[uuid = "7a4f2c91-3e6b-4d08-a5c7-1b9e8d2f6c30"]
+pub struct CompositeTileset {
+    /// Number of tiles from all sources.
+    pub tile_count: u32,
+    /// Pixel size of the tiles, shared by all sources.
+    pub tile_size: TileSize,
+    /// The source tilesets, in the order their tiles are stacked.
+    pub sources: Vec<CompositeSource>,
+    /// A handle to the combined texture.
+    pub texture: Handle<Image>,
+}
+impl CompositeTileset {
+    /// Merges the textures of `sources` into one texture added to `images`.
+    ///
+    /// All sources must be loaded, have the same tile size and use the same texture format.
+    /// The combined texture keeps the first source texture's usages and sampler.
+    pub fn compose(
+        sources: &[Handle<Tileset>],
+        tilesets: &Assets<Tileset>,
+        images: &mut Assets<Image>,
+    ) -> Result<Self, CompositeTilesetError> {
+        let mut composite_sources = Vec::with_capacity(sources.len());
+        let mut texture: Option<Image> = None;
+        let mut tile_size = None;
+        let mut tile_count = 0;
+        for (index, handle) in sources.iter().enumerate() {
+            let tileset = tilesets
+                .get(handle)
+                .ok_or(CompositeTilesetError::MissingTileset(index))?;
+            let image = images
+                .get(&tileset.texture)
+                .ok_or(CompositeTilesetError::MissingTexture(index))?;
+            if *tile_size.get_or_insert(tileset.tile_size) != tileset.tile_size {
+                return Err(CompositeTilesetError::TileSizeMismatch(index));
+            }
+            match texture.as_mut() {
+                Some(texture) => {
+                    let format = texture.texture_descriptor.format;
+                    if image.texture_descriptor.format != format {
+                        return Err(CompositeTilesetError::FormatMismatch(index));
+                    }
+                    texture.data.extend_from_slice(&image.data);
+                }
+                None => texture = Some(image.clone()),
+            }
+            composite_sources.push(CompositeSource {
+                tileset: handle.clone(),
+                offset: tile_count,
+                tile_count: tileset.tile_count,
+            });
+            tile_count += tileset.tile_count;
+        }
+        let (Some(mut texture), Some(tile_size)) = (texture, tile_size) else {
+            return Err(CompositeTilesetError::NoSources);
+        };
+        texture.texture_descriptor.label = None;
+        texture.texture_descriptor.size.width = tile_size.width as u32;
+        texture.texture_descriptor.size.height = tile_size.height as u32 * tile_count;
+        Ok(Self {
+            tile_count,
+            tile_size,
+            sources: composite_sources,
+            texture: images.add(texture),
+        })
+    }
+
+    /// Returns the index of the first tile of `tileset` in the composite,
+    /// or `None` if it is not a source.
+    pub fn offset(&self, tileset: &Handle<Tileset>) -> Option<u32> {
+        self.sources
+            .iter()
+            .find(|source| source.tileset == *tileset)
+            .map(|source| source.offset)
+    }
+
+    /// Returns the composite index of tile `tile` of `tileset`.
+    ///
+    /// Returns `None` if `tileset` is not a source or has no such tile.
+    pub fn tile_index(&self, tileset: &Handle<Tileset>, tile: u32) -> Option<u32> {
+        self.sources
+            .iter()
+            .find(|source| source.tileset == *tileset && tile < source.tile_count)
+            .map(|source| source.offset + tile)
+    }
+
+    /// Returns the source tileset of a composite tile index, and the tile's index in it.
+    pub fn source_tile(&self, index: u32) -> Option<(&Handle<Tileset>, u32)> {
+        self.sources
+            .iter()
+            .find(|source| (source.offset..source.offset + source.tile_count).contains(&index))
+            .map(|source| (&source.tileset, index - source.offset))
+    }
+
+    /// Returns the size of the combined texture. See [Tileset::texture_size].
+    pub fn texture_size(&self) -> Vec2 {
+        let TileSize { width, height } = self.tile_size;
+        Vec2::new(width as f32, height as f32 * self.tile_count as f32)
+    }
+
+    /// Returns the pixel and UV rects of a tile in the combined texture. See [Tileset::tile_rect].
+    pub fn tile_rect(&self, index: u32) -> Option<TileRect> {
+        strip_tile_rect(self.tile_size, self.tile_count, index)
+    }
+}
+
+/// A source tileset of a [CompositeTileset].
+#[derive(Debug, Clone)]
+pub struct CompositeSource {
+    /// A handle to the source tileset.
+    pub tileset: Handle<Tileset>,
+    /// Composite index of the tileset's first tile.
+    pub offset: u32,
+    /// Number of tiles in the tileset.
+    pub tile_count: u32,
+}
+
+/// An error from composing tilesets with [CompositeTileset::compose].
+///
+/// Variants hold the index of the source which caused the error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompositeTilesetError {
+    /// No source tilesets were given.
+    NoSources,
+    /// The source tileset is not loaded.
+    MissingTileset(usize),
+    /// The source tileset's texture is not loaded.
+    MissingTexture(usize),
+    /// The source tileset's tile size differs from the first source's.
+    TileSizeMismatch(usize),
+    /// The source tileset's texture format differs from the first source's.
+    FormatMismatch(usize),
+}
+impl fmt::Display for CompositeTilesetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompositeTilesetError::NoSources => write!(f, "No tilesets to compose"),
+            CompositeTilesetError::MissingTileset(index) => {
+                write!(f, "Tileset {} is not loaded", index)
+            }
+            CompositeTilesetError::MissingTexture(index) => {
+                write!(f, "Texture of tileset {} is not loaded", index)
+            }
+            CompositeTilesetError::TileSizeMismatch(index) => {
+                write!(f, "Tileset {} has a different tile size", index)
+            }
+            CompositeTilesetError::FormatMismatch(index) => {
+                write!(f, "Tileset {} has a different texture format", index)
+            }
+        }
+    }
+}
+impl std::error::Error for CompositeTilesetError {}
+
+// Compares the tiles of a tileset before and after a reimport, given the texture data of each
+// and the bytes per tile. Returns the old and new index of each tile found at another index,
+// and the old indices of tiles past the end of the new tileset. Tiles edited in place and empty
+// tiles are not reported, since tiles only match by their pixels.
+pub(crate) fn tile_index_changes(
+    old: &[u8],
+    new: &[u8],
+    tile_bytes: usize,
+) -> (Vec<(u32, u32)>, Vec<u32>) {
+    let mut moved = Vec::new();
+    let mut removed = Vec::new();
+    if tile_bytes == 0 {
+        return (moved, removed);
+    }
+    let new_tiles: Vec<&[u8]> = new.chunks_exact(tile_bytes).collect();
+    for (index, tile) in old.chunks_exact(tile_bytes).enumerate() {
+        if new_tiles.get(index) == Some(&tile) {
+            continue;
+        }
+        let new_index = new_tiles.iter().position(|new_tile| *new_tile == tile);
+        match new_index {
+            Some(new_index) if tile.iter().any(|byte| *byte != 0) => {
+                moved.push((index as u32, new_index as u32));
+            }
+            _ if index >= new_tiles.len() => removed.push(index as u32),
+            _ => {}
+        }
+    }
+    (moved, removed)
 }
 
 #[derive(Debug)]
@@ -110,27 +451,3 @@ pub(crate) struct TilesetData<T> {
     pub(crate) name: String,
     pub(crate) texture: T,
 }
-impl<T> TilesetData<T> {
-    fn from_ase<F>(f: F, ase: &AsepriteFile, ase_tileset: &asefile::Tileset) -> TilesetResult<Self>
-    where
-        F: FnOnce(&AsepriteFile, &asefile::Tileset) -> TilesetResult<T>,
-    {
-        let texture = f(ase, ase_tileset)?;
-        let ase_size = ase_tileset.tile_size();
-        Ok(Self {
-            id: ase_tileset.id(),
-            tile_count: ase_tileset.tile_count(),
-            tile_size: TileSize::from_ase(&ase_size),
-            name: ase_tileset.name().to_string(),
-            texture,
-        })
-    }
-}
-impl TilesetData<Image> {
-    pub(crate) fn from_ase_with_texture(
-        ase: &AsepriteFile,
-        ase_tileset: &asefile::Tileset,
-    ) -> TilesetResult<Self> {
-        TilesetData::<Image>::from_ase(texture_from, ase, ase_tileset)
-    }
-}
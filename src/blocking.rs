@@ -0,0 +1,107 @@
+use crate::loader::{AseAssetResources, AseFileMapChanged, ImportError, Loader};
+use crate::process::{FileHeader, Palette};
+use crate::processing::ResourceData;
+use asefile::{AsepriteFile, AsepriteParseError};
+use bevy::{ecs::system::SystemState, prelude::*};
+use std::{fmt, fs, path::Path};
+
+/// Describes why [load_file_blocking] could not load a file.
+#[derive(Debug)]
+pub enum BlockingLoadError {
+    /// The file could not be read or parsed.
+    Parse(AsepriteParseError),
+    /// The file was parsed, but could not be imported.
+    Import(ImportError),
+}
+impl fmt::Display for BlockingLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BlockingLoadError::Parse(e) => write!(f, "{}", e),
+            BlockingLoadError::Import(e) => write!(f, "{}", e),
+        }
+    }
+}
+impl std::error::Error for BlockingLoadError {}
+impl From<AsepriteParseError> for BlockingLoadError {
+    fn from(e: AsepriteParseError) -> Self {
+        Self::Parse(e)
+    }
+}
+impl From<ImportError> for BlockingLoadError {
+    fn from(e: ImportError) -> Self {
+        Self::Import(e)
+    }
+}
+
+/// Reads, processes and imports a single Aseprite file synchronously.
+///
+/// The file is read from `path`, and its assets are inserted into `world` under `path`,
+/// exactly as [ase_importer](crate::loader::ase_importer) would insert them. Files are
+/// processed with the [Loader]'s settings if `world` has one, and otherwise with
+/// default settings. An [AseFileMapChanged] event is sent if the event is registered.
+/// Variant palettes next to the file are always read, see
+/// [AseAssetLoader::palette_variants](crate::loader::AseAssetLoader::palette_variants).
+///
+/// This suits editors, thumbnail generation and tests, where the asynchronous
+/// pipeline of the [Loader] is not needed. Resources missing from `world` are skipped,
+/// as with the [Loader].
+///
+/// # Examples
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_ase::asset::AseFileMap;
+/// use bevy_ase::load_file_blocking;
+/// use bevy_ase::loader::AseLoaderDefaultPlugin;
+///
+/// fn has_walk_cycle(path: &str) -> bool {
+///     let mut app = App::new();
+///     app.add_plugins(MinimalPlugins)
+///         .add_plugin(AssetPlugin::default())
+///         .add_plugin(AseLoaderDefaultPlugin);
+///     load_file_blocking(path, &mut app.world).unwrap();
+///     let file_map = app.world.resource::<AseFileMap>();
+///     file_map
+///         .get(path)
+///         .map_or(false, |asset_map| asset_map.contains_animation("walk"))
+/// }
+/// ```
+pub fn load_file_blocking(
+    path: impl AsRef<Path>,
+    world: &mut World,
+) -> Result<(), BlockingLoadError> {
+    let path = path.as_ref();
+    let file = AsepriteFile::read_file(path)?;
+    let header = fs::read(path)
+        .map(|bytes| FileHeader::read(&bytes))
+        .unwrap_or_default();
+    let settings = world
+        .get_resource::<Loader>()
+        .map(|loader| loader.settings().clone())
+        .unwrap_or_default();
+    let palettes = read_palette_variants(path);
+    let data = ResourceData::new(path, &file, &header, &palettes, &settings)?;
+
+    let mut state: SystemState<(AseAssetResources, Option<ResMut<Events<AseFileMapChanged>>>)> =
+        SystemState::new(world);
+    let (mut resources, changes) = state.get_mut(world);
+    data.move_into_resources(path.to_path_buf(), &mut resources);
+    if let Some(mut changes) = changes {
+        changes.send(AseFileMapChanged {
+            path: path.to_path_buf(),
+        });
+    }
+    Ok(())
+}
+
+// Reads the variant palettes next to a file, up to the first missing or invalid one.
+fn read_palette_variants(path: &Path) -> Vec<Palette> {
+    (1..)
+        .map_while(|number| {
+            Palette::EXTENSIONS.iter().find_map(|extension| {
+                let text = fs::read_to_string(Palette::variant_path(path, number, extension));
+                Palette::parse(&text.ok()?)
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,56 @@
+/// Aseprite features supported by this crate and the linked asefile version.
+///
+/// Returned by [capabilities]. Downstream code can check a feature before relying on it,
+/// instead of finding out at runtime that its data is missing. Whether a single file was
+/// imported completely is reported by [AseAssetMap::is_partial](crate::asset::AseAssetMap::is_partial).
+///
+/// More fields may be added as asefile gains features, so this struct can't be constructed
+/// outside of this crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Capabilities {
+    /// Tilesets and their images, from files created with Aseprite v1.3.
+    pub tilesets: bool,
+    /// User data of individual tiles.
+    pub tile_user_data: bool,
+    /// 9-slice data of slices.
+    pub slice9: bool,
+    /// Pivots of slices.
+    pub slice_pivots: bool,
+    /// Colors of tags and layers, set in their user data.
+    pub user_data_colors: bool,
+    /// Repeat counts of tags, from files created with Aseprite v1.3.
+    pub tag_repeat: bool,
+    /// Pixel aspect ratios other than 1:1.
+    pub pixel_ratio: bool,
+    /// Color profiles embedded in files.
+    pub color_profile: bool,
+    /// References to external files, such as external palettes or tilesets.
+    pub external_files: bool,
+}
+
+/// Returns the Aseprite features supported by this build of the crate.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_ase::capabilities;
+///
+/// // Tile user data can't be read yet, so store tile properties in code instead.
+/// if !capabilities().tile_user_data {
+///     println!("Tile user data is ignored");
+/// }
+/// ```
+pub const fn capabilities() -> Capabilities {
+    Capabilities {
+        tilesets: true,
+        tile_user_data: false,
+        slice9: true,
+        slice_pivots: true,
+        user_data_colors: true,
+        tag_repeat: true,
+        pixel_ratio: true,
+        color_profile: true,
+        external_files: false,
+    }
+}
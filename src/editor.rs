@@ -0,0 +1,195 @@
+use crate::asset::AseFileMap;
+use crate::handle_id::FilePath;
+use crate::loader::AseFileMapChanged;
+use crate::player::AnimationPlayer;
+use bevy::prelude::*;
+use std::path::PathBuf;
+
+/// Adds the [AseBrowser] resource, the [SpawnPreview] event, and the
+/// [update_browser] and [spawn_previews] systems.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::editor::AseBrowserPlugin;
+/// use bevy_ase::loader::AseLoaderDefaultPlugin;
+/// use bevy_ase::player::AnimationPlayerPlugin;
+///
+/// fn app() {
+///     App::new()
+///         .add_plugins(DefaultPlugins)
+///         .add_plugin(AseLoaderDefaultPlugin)
+///         .add_plugin(AnimationPlayerPlugin::default())
+///         .add_plugin(AseBrowserPlugin);
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AseBrowserPlugin;
+
+impl Plugin for AseBrowserPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AseBrowser>()
+            .add_event::<SpawnPreview>()
+            .add_system(update_browser)
+            .add_system(spawn_previews.after(update_browser));
+    }
+}
+
+/// Resource listing every imported file, for asset browser panels.
+///
+/// Entries are kept up to date with [AseFileMap] by the [update_browser] system,
+/// and sorted by path. A panel can show each entry's thumbnail, tags, slices and
+/// tilesets, and send a [SpawnPreview] event to preview a file in the world.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::editor::AseBrowser;
+///
+/// fn list_files(browser: Res<AseBrowser>) {
+///     for entry in browser.entries() {
+///         info!("{}: tags {:?}", entry.path.display(), entry.tags);
+///     }
+/// }
+/// ```
+#[derive(Debug, Default, Resource)]
+pub struct AseBrowser {
+    entries: Vec<BrowserEntry>,
+}
+
+impl AseBrowser {
+    /// Returns an entry for each imported file, sorted by path.
+    pub fn entries(&self) -> &[BrowserEntry] {
+        &self.entries
+    }
+
+    /// Returns the entry for the file with the given path.
+    pub fn entry(&self, path: impl FilePath) -> Option<&BrowserEntry> {
+        let path = path.file_path();
+        self.entries.iter().find(|entry| entry.path == path)
+    }
+
+    /// Rebuilds the entries from `file_map`.
+    ///
+    /// This is already done by the [update_browser] system when files change.
+    pub fn refresh(&mut self, file_map: &AseFileMap) {
+        self.entries = file_map
+            .0
+            .iter()
+            .map(|(path, asset_map)| BrowserEntry {
+                path: path.clone(),
+                thumbnail: asset_map.thumbnail.clone(),
+                atlas: asset_map.atlas.clone(),
+                tags: sorted(asset_map.animations.keys().cloned()),
+                slices: sorted(asset_map.slices.keys().cloned()),
+                tilesets: sorted(asset_map.tilesets.keys().copied()),
+            })
+            .collect();
+        self.entries.sort_by(|a, b| a.path.cmp(&b.path));
+    }
+}
+
+fn sorted<T: Ord>(items: impl Iterator<Item = T>) -> Vec<T> {
+    let mut items: Vec<T> = items.collect();
+    items.sort();
+    items
+}
+
+/// An imported file listed by the [AseBrowser].
+#[derive(Debug, Clone)]
+pub struct BrowserEntry {
+    /// Path of the Aseprite file.
+    pub path: PathBuf,
+    /// The file's thumbnail, if generated with
+    /// [ProcessSettings::thumbnail](crate::process::ProcessSettings::thumbnail).
+    pub thumbnail: Option<Handle<Image>>,
+    /// The file's texture atlas.
+    pub atlas: Handle<TextureAtlas>,
+    /// Names of the file's tags, sorted.
+    pub tags: Vec<String>,
+    /// Names of the file's slices, sorted.
+    pub slices: Vec<String>,
+    /// Ids of the file's tilesets, sorted.
+    pub tilesets: Vec<u32>,
+}
+
+/// Event which spawns a preview entity of an imported file, e.g. from a browser panel's button.
+///
+/// The preview shows the file's atlas, playing the tag's animation if `tag` is set.
+/// It replaces any previous preview. Previews are marked with [AsePreview].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpawnPreview {
+    /// Path of the Aseprite file.
+    pub path: PathBuf,
+    /// Name of the tag to play. `None` shows the file's first frame.
+    pub tag: Option<String>,
+    /// Position of the preview.
+    pub translation: Vec3,
+}
+
+/// Marker component for preview entities spawned by [SpawnPreview] events.
+#[derive(Debug, Clone, PartialEq, Eq, Component)]
+pub struct AsePreview {
+    /// Path of the previewed Aseprite file.
+    pub path: PathBuf,
+}
+
+/// System function which keeps the [AseBrowser] up to date with [AseFileMap].
+///
+/// This system is already added by [AseBrowserPlugin].
+pub fn update_browser(
+    mut browser: ResMut<AseBrowser>,
+    file_map: Option<Res<AseFileMap>>,
+    mut changes: EventReader<AseFileMapChanged>,
+) {
+    let Some(file_map) = file_map else {
+        return;
+    };
+    // Unloaded files are not announced by an event, but change the number of files.
+    let changed = !changes.is_empty();
+    changes.clear();
+    if changed || browser.entries.len() != file_map.0.len() {
+        browser.refresh(&file_map);
+    }
+}
+
+/// System function which spawns a preview entity for each [SpawnPreview] event.
+///
+/// This system is already added by [AseBrowserPlugin].
+pub fn spawn_previews(
+    mut commands: Commands,
+    mut requests: EventReader<SpawnPreview>,
+    file_map: Option<Res<AseFileMap>>,
+    previews: Query<Entity, With<AsePreview>>,
+) {
+    let Some(file_map) = file_map else {
+        return;
+    };
+    let Some(request) = requests.iter().last() else {
+        return;
+    };
+    let Some(asset_map) = file_map.get(request.path.as_path()) else {
+        return;
+    };
+    for preview in &previews {
+        commands.entity(preview).despawn_recursive();
+    }
+    let mut preview = commands.spawn((
+        SpriteSheetBundle {
+            transform: Transform::from_translation(request.translation),
+            ..asset_map.sprite_sheet_bundle()
+        },
+        AsePreview {
+            path: request.path.clone(),
+        },
+    ));
+    let animation = request
+        .tag
+        .as_deref()
+        .and_then(|tag_name| asset_map.animation(tag_name));
+    if let Some(animation) = animation {
+        preview.insert(AnimationPlayer::new(animation.clone()));
+    }
+}
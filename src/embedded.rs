@@ -0,0 +1,78 @@
+//! Aseprite files embedded into the executable at compile time.
+//!
+//! Embedded files run through the same pipeline as files loaded by the
+//! [AssetServer]. Their generated assets use the file's virtual path,
+//! so the [handle_id](crate::handle_id) functions and [AseFileMap](crate::asset::AseFileMap)
+//! work the same as for files loaded from disk.
+//!
+//! This allows shipping a game as a single executable, without an assets folder.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use bevy::prelude::*;
+//! use bevy_ase::asset::AseAsset;
+//! use bevy_ase::include_ase;
+//! use bevy_ase::loader::Loader;
+//!
+//! // Embeds "assets/sprites/hello.aseprite" and imports it as "sprites/hello.aseprite".
+//! fn load_embedded(mut loader: ResMut<Loader>, mut aseassets: ResMut<Assets<AseAsset>>) {
+//!     loader
+//!         .add_embedded(&mut aseassets, include_ase!("sprites/hello.aseprite"))
+//!         .unwrap();
+//! }
+//! ```
+use crate::asset::{ase::AseData, AseAsset};
+use crate::loader::Loader;
+use crate::process::FileHeader;
+use asefile::{AsepriteFile, AsepriteParseError};
+use bevy::{asset::HandleId, prelude::*};
+use std::path::PathBuf;
+
+/// An Aseprite file's bytes, embedded at compile time by [include_ase](crate::include_ase).
+#[derive(Debug, Clone, Copy)]
+pub struct EmbeddedAse {
+    /// The virtual path of the file, relative to the assets folder.
+    pub path: &'static str,
+    /// The file's contents.
+    pub bytes: &'static [u8],
+}
+
+/// Embeds an Aseprite file into the executable.
+///
+/// The path is relative to the crate's `assets` folder, as with [AssetServer::load].
+/// It is also used as the file's virtual path. Returns an [EmbeddedAse],
+/// to be added with [Loader::add_embedded].
+#[macro_export]
+macro_rules! include_ase {
+    ($path:literal) => {
+        $crate::embedded::EmbeddedAse {
+            path: $path,
+            bytes: include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/assets/", $path)),
+        }
+    };
+}
+
+impl Loader {
+    /// Parses an [EmbeddedAse] and adds it to the [Loader] for loading.
+    ///
+    /// The parsed file is added to `aseassets` under its virtual path.
+    /// Returns the handle of the added [AseAsset].
+    pub fn add_embedded(
+        &mut self,
+        aseassets: &mut Assets<AseAsset>,
+        embedded: EmbeddedAse,
+    ) -> Result<Handle<AseAsset>, AsepriteParseError> {
+        let file = AsepriteFile::read(embedded.bytes)?;
+        let ase = AseAsset {
+            data: AseData::Loaded(Box::new(file)),
+            name: PathBuf::from(embedded.path),
+            header: FileHeader::read(embedded.bytes),
+            palettes: Vec::new(),
+        };
+        let handle_id = HandleId::from(embedded.path);
+        let handle = aseassets.set(handle_id, ase);
+        self.add(handle.clone());
+        Ok(handle)
+    }
+}
@@ -0,0 +1,133 @@
+use crate::player::{animate_sprites, AnimationEventFired};
+use bevy::{prelude::*, utils::HashMap};
+
+// Converts a fired animation event into a game event.
+type Mapping<E> = fn(&AnimationEventFired) -> Option<E>;
+
+/// Plugin which turns [AnimationEventFired] events into events of a game's own type `E`.
+///
+/// Each registered event name maps to a function converting the fired event, e.g. by
+/// parsing its payload. Whenever a registered event fires, the function's result is sent
+/// as an `E` event, so game systems read strongly typed events instead of parsing strings.
+/// Functions may return `None` to drop an event, e.g. with an invalid payload.
+/// Add one plugin per event type. Requires [AnimationPlayerPlugin](crate::player::AnimationPlayerPlugin).
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::event_registry::AnimationEventRegistryPlugin;
+/// use bevy_ase::player::AnimationPlayerPlugin;
+///
+/// // Sent for frames with `event:hit=12` in their user data.
+/// struct HitFrame {
+///     attacker: Entity,
+///     damage: u32,
+/// }
+///
+/// fn app() {
+///     App::new()
+///         .add_plugins(DefaultPlugins)
+///         .add_plugin(AnimationPlayerPlugin::default())
+///         .add_plugin(AnimationEventRegistryPlugin::new().with("hit", |fired| {
+///             Some(HitFrame {
+///                 attacker: fired.entity,
+///                 damage: fired.event.payload.as_deref()?.parse().ok()?,
+///             })
+///         }))
+///         .add_system(apply_hits);
+/// }
+///
+/// fn apply_hits(mut hits: EventReader<HitFrame>) {
+///     for hit in hits.iter() {
+///         info!("{:?} hits for {}", hit.attacker, hit.damage);
+///     }
+/// }
+/// ```
+pub struct AnimationEventRegistryPlugin<E> {
+    mappings: Vec<(String, Mapping<E>)>,
+}
+
+impl<E> AnimationEventRegistryPlugin<E> {
+    /// Creates a plugin without mappings.
+    pub fn new() -> Self {
+        Self {
+            mappings: Vec::new(),
+        }
+    }
+    /// Maps fired events with the given name through `mapping`.
+    pub fn with(mut self, name: impl Into<String>, mapping: Mapping<E>) -> Self {
+        self.mappings.push((name.into(), mapping));
+        self
+    }
+}
+
+impl<E> Default for AnimationEventRegistryPlugin<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Event> Plugin for AnimationEventRegistryPlugin<E> {
+    fn build(&self, app: &mut App) {
+        let mut registry = AnimationEventRegistry::<E>::default();
+        for (name, mapping) in &self.mappings {
+            registry.register(name.clone(), *mapping);
+        }
+        app.insert_resource(registry)
+            .add_event::<E>()
+            .add_system(send_registered_events::<E>.after(animate_sprites));
+    }
+}
+
+/// Resource mapping animation event names to events of type `E`.
+///
+/// Added by [AnimationEventRegistryPlugin]. Systems can register more mappings at runtime.
+#[derive(Resource)]
+pub struct AnimationEventRegistry<E> {
+    mappings: HashMap<String, Mapping<E>>,
+}
+
+impl<E> Default for AnimationEventRegistry<E> {
+    fn default() -> Self {
+        Self {
+            mappings: HashMap::default(),
+        }
+    }
+}
+
+impl<E> AnimationEventRegistry<E> {
+    /// Maps fired events with the given name through `mapping`,
+    /// replacing the previous mapping of the name.
+    pub fn register(&mut self, name: impl Into<String>, mapping: Mapping<E>) {
+        self.mappings.insert(name.into(), mapping);
+    }
+    /// Removes the mapping of the given name. Returns true if there was one.
+    pub fn unregister(&mut self, name: &str) -> bool {
+        self.mappings.remove(name).is_some()
+    }
+    /// Returns true if events with the given name are mapped.
+    pub fn contains(&self, name: &str) -> bool {
+        self.mappings.contains_key(name)
+    }
+    /// Converts a fired event, if its name is mapped.
+    pub fn map(&self, fired: &AnimationEventFired) -> Option<E> {
+        let mapping = self.mappings.get(&fired.event.name)?;
+        mapping(fired)
+    }
+}
+
+/// System function which sends an `E` event for each mapped [AnimationEventFired] event.
+///
+/// This system is already added by [AnimationEventRegistryPlugin].
+pub fn send_registered_events<E: Event>(
+    registry: Res<AnimationEventRegistry<E>>,
+    mut fired: EventReader<AnimationEventFired>,
+    mut events: EventWriter<E>,
+) {
+    for fired in fired.iter() {
+        if let Some(event) = registry.map(fired) {
+            events.send(event);
+        }
+    }
+}
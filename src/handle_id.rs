@@ -1,16 +1,33 @@
 //! Provides functions to generate [HandleId](bevy::asset::HandleId)s for assets generated from aseprite files.
 //!
+//! Paths may be given as `&str`, `String`, [Path], [PathBuf] or [AssetPath]; see [FilePath].
+//! Paths don't need to be valid Unicode, so files from archives or custom
+//! [AssetIo](bevy::asset::AssetIo) implementations are supported.
+//!
 //! This crate uses asset labels to identify generated sub-assets. The following label forms are supported:
 //!
 //! | Label                      | Asset type                                                                                    |
 //! | -------------------------- | ------------------------------------------------------------------------  |
 //! | `Animation/{tag_name}`     | [`Animation`][crate::asset::animation::Animation] with a given tag name*. |
 //! | `Atlas`                    | [`TextureAtlas`][bevy::prelude::TextureAtlas] for the entire sprite.      |
+//! | `Atlas/Lod{level}`         | Downscaled [`TextureAtlas`][bevy::prelude::TextureAtlas] for a given level. |
+//! | `Atlas/Palette{number}`    | Recolored [`TextureAtlas`][bevy::prelude::TextureAtlas] for a given variant palette. |
+//! | `Atlas/Outline`            | [`TextureAtlas`][bevy::prelude::TextureAtlas] of each frame's outline or silhouette. |
+//! | `Atlas/Tag/{tag_name}`     | [`TextureAtlas`][bevy::prelude::TextureAtlas] of a given tag's frames*. |
+//! | `ShadowAtlas`              | [`TextureAtlas`][bevy::prelude::TextureAtlas] of the file's shadow layer. |
+//! | `Thumbnail`                | [`Image`][bevy::prelude::Image] previewing the file.                      |
 //! | `FrameImage{index}`        | [`Image`][bevy::prelude::Image] for a given frame index.                  |
+//! | `FrameImage{index}/Layer/{layer_name}` | [`Image`][bevy::prelude::Image] of one layer in a given frame.** |
+//! | `FrameImage{index}/Channel/{channel_name}` | [`Image`][bevy::prelude::Image] of one channel in a given frame.** |
+//! | `FrameCollision{index}`    | [`FrameCollision`][crate::asset::FrameCollision] for a given frame index. |
 //! | `Slice/{name}`             | [`Slice`][crate::asset::slice::Slice] with a given slice name*.           |
+//! | `TextPlaceholder/{name}`   | [`UiTextPlaceholder`][crate::asset::UiTextPlaceholder] with a given slice name*. |
+//! | `Events/{tag_name}`        | [`AnimationEvents`][crate::asset::AnimationEvents] for a given tag name*. |
 //! | `Tileset{tileset_id}`      | [`Tileset`][crate::asset::tileset::Tileset] for a given tileset id.       |
 //! | `TilesetImage{tileset_id}` | [`Image`][bevy::prelude::Image]  for the tileset with the given id.       |
 //!
+//! Each label form has a [LabelKind], for tools which scan assets by label.
+//!
 //! # Examples
 //!
 //! ```
@@ -33,6 +50,11 @@
 //! }
 //! ```
 //!
+//! \*\* Per-layer images are imported for top-level layers and groups with
+//! [LayerFlattening::TopLevelGroups](crate::process::LayerFlattening::TopLevelGroups).
+//! Per-channel images are not imported yet. Their label is reserved,
+//! so these sub-assets have stable paths once they are.
+//!
 //! # * Warning!
 //!
 //! This crate keys slices and animations by name. When using slices and animations data with this crate,
@@ -41,17 +63,363 @@
 //!
 use bevy::asset::AssetPath;
 use bevy::asset::HandleId;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-fn make(path: &str, kind: &str, suffix: Option<&str>) -> HandleId {
-    let mut label = kind.to_string();
-    if let Some(suffix) = suffix {
-        label.push_str(suffix);
+/// The path of an Aseprite file, as accepted by this module and by
+/// [AseFileMap](crate::asset::AseFileMap).
+///
+/// Strings are read like an [AssetPath]: anything after a `#` is a label, and is ignored.
+/// [Path] and [PathBuf] values are used as they are, like the path the [AssetServer](bevy::asset::AssetServer)
+/// passes to the loader. This way, each form of a path finds the assets the loader stored for it.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::asset::AssetPath;
+/// use bevy_ase::handle_id::{self, FilePath};
+/// use std::path::Path;
+///
+/// let path = Path::new("sprites/hero.aseprite");
+/// assert_eq!("sprites/hero.aseprite#Atlas".file_path(), path);
+/// assert_eq!(AssetPath::from(path).file_path(), path);
+/// assert_eq!(handle_id::atlas(path), handle_id::atlas(AssetPath::from(path)));
+/// ```
+pub trait FilePath {
+    /// Returns the path of the file, without any label.
+    fn file_path(&self) -> &Path;
+}
+impl FilePath for str {
+    fn file_path(&self) -> &Path {
+        let path = self.split_once('#').map_or(self, |(path, _)| path);
+        Path::new(path)
+    }
+}
+impl FilePath for String {
+    fn file_path(&self) -> &Path {
+        self.as_str().file_path()
+    }
+}
+impl FilePath for Path {
+    fn file_path(&self) -> &Path {
+        self
+    }
+}
+impl FilePath for PathBuf {
+    fn file_path(&self) -> &Path {
+        self
+    }
+}
+impl FilePath for AssetPath<'_> {
+    fn file_path(&self) -> &Path {
+        self.path()
+    }
+}
+impl<T: FilePath + ?Sized> FilePath for &T {
+    fn file_path(&self) -> &Path {
+        (**self).file_path()
     }
-    let asset_path = AssetPath::new(PathBuf::from(path), Some(label));
+}
+
+fn make(path: &Path, label: String) -> HandleId {
+    let asset_path = AssetPath::new_ref(path, Some(&label));
     asset_path.into()
 }
 
+/// The kinds of sub-asset labels generated by this crate.
+///
+/// Tools that scan assets by label can use this instead of hard-coding label strings.
+/// [LabelKind::as_str] returns the name which starts each label of the kind, and
+/// [LabelKind::parse] returns the kind of a label.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_ase::handle_id::LabelKind;
+///
+/// assert_eq!(LabelKind::parse("Animation/walk"), Some(LabelKind::Animation));
+/// assert_eq!(LabelKind::parse("FrameImage3"), Some(LabelKind::FrameImage));
+/// assert_eq!(LabelKind::parse(LabelKind::Atlas.as_str()), Some(LabelKind::Atlas));
+/// assert_eq!(LabelKind::parse("Unknown"), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LabelKind {
+    /// `Animation/{tag_name}`
+    Animation,
+    /// `Atlas`
+    Atlas,
+    /// `Atlas/Lod{level}`
+    LodAtlas,
+    /// `Atlas/Palette{number}`
+    PaletteAtlas,
+    /// `Atlas/Outline`
+    OutlineAtlas,
+    /// `Atlas/Tag/{tag_name}`
+    TagAtlas,
+    /// `ShadowAtlas`
+    ShadowAtlas,
+    /// `Thumbnail`
+    Thumbnail,
+    /// `FrameImage{index}`
+    FrameImage,
+    /// `FrameCollision{index}`
+    FrameCollision,
+    /// `Slice/{name}`
+    Slice,
+    /// `TextPlaceholder/{name}`
+    TextPlaceholder,
+    /// `Events/{tag_name}`
+    AnimationEvents,
+    /// `Tileset{tileset_id}`
+    Tileset,
+    /// `TilesetImage{tileset_id}`
+    TilesetImage,
+    /// `FrameImage{index}/Layer/{layer_name}`
+    LayerImage,
+    /// `FrameImage{index}/Channel/{channel_name}`
+    ChannelImage,
+}
+
+impl LabelKind {
+    /// All label kinds.
+    pub const ALL: [LabelKind; 17] = [
+        LabelKind::Animation,
+        LabelKind::Atlas,
+        LabelKind::LodAtlas,
+        LabelKind::PaletteAtlas,
+        LabelKind::OutlineAtlas,
+        LabelKind::TagAtlas,
+        LabelKind::ShadowAtlas,
+        LabelKind::Thumbnail,
+        LabelKind::FrameImage,
+        LabelKind::FrameCollision,
+        LabelKind::Slice,
+        LabelKind::TextPlaceholder,
+        LabelKind::AnimationEvents,
+        LabelKind::Tileset,
+        LabelKind::TilesetImage,
+        LabelKind::LayerImage,
+        LabelKind::ChannelImage,
+    ];
+
+    /// Returns the name of the kind.
+    ///
+    /// Labels of the kind start with this name, except for layer and channel images,
+    /// which are nested in a `FrameImage{index}` label, and downscaled, recolored, outline
+    /// and tag atlases, which are nested in the `Atlas` label.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LabelKind::Animation => "Animation",
+            LabelKind::Atlas => "Atlas",
+            LabelKind::LodAtlas => "Lod",
+            LabelKind::PaletteAtlas => "Palette",
+            LabelKind::OutlineAtlas => "Outline",
+            LabelKind::TagAtlas => "Tag",
+            LabelKind::ShadowAtlas => "ShadowAtlas",
+            LabelKind::Thumbnail => "Thumbnail",
+            LabelKind::FrameImage => "FrameImage",
+            LabelKind::FrameCollision => "FrameCollision",
+            LabelKind::Slice => "Slice",
+            LabelKind::TextPlaceholder => "TextPlaceholder",
+            LabelKind::AnimationEvents => "Events",
+            LabelKind::Tileset => "Tileset",
+            LabelKind::TilesetImage => "TilesetImage",
+            LabelKind::LayerImage => "Layer",
+            LabelKind::ChannelImage => "Channel",
+        }
+    }
+
+    /// Returns the kind of a label, or the kind with the given name.
+    ///
+    /// Returns `None` if the string is neither a label generated by this crate nor a kind's name.
+    pub fn parse(label: &str) -> Option<LabelKind> {
+        let by_name = LabelKind::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == label);
+        by_name.or_else(|| label::parse(label).map(|parsed| parsed.kind()))
+    }
+}
+
+impl std::fmt::Display for LabelKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// Label strings for each sub-asset kind, shared with the asset map snapshot.
+pub(crate) mod label {
+    use super::LabelKind;
+
+    fn named(kind: LabelKind, name: &str) -> String {
+        format!("{}/{}", kind.as_str(), name)
+    }
+    fn indexed(kind: LabelKind, index: u32) -> String {
+        format!("{}{}", kind.as_str(), index)
+    }
+
+    pub(crate) fn animation(tag_name: &str) -> String {
+        named(LabelKind::Animation, tag_name)
+    }
+    pub(crate) fn frame_image(frame: u32) -> String {
+        indexed(LabelKind::FrameImage, frame)
+    }
+    pub(crate) fn atlas() -> String {
+        LabelKind::Atlas.as_str().to_string()
+    }
+    pub(crate) fn lod_atlas(level: u32) -> String {
+        format!("{}/{}", atlas(), indexed(LabelKind::LodAtlas, level))
+    }
+    pub(crate) fn palette_atlas(number: u32) -> String {
+        format!("{}/{}", atlas(), indexed(LabelKind::PaletteAtlas, number))
+    }
+    pub(crate) fn outline_atlas() -> String {
+        format!("{}/{}", atlas(), LabelKind::OutlineAtlas.as_str())
+    }
+    pub(crate) fn tag_atlas(tag_name: &str) -> String {
+        format!("{}/{}", atlas(), named(LabelKind::TagAtlas, tag_name))
+    }
+    pub(crate) fn shadow_atlas() -> String {
+        LabelKind::ShadowAtlas.as_str().to_string()
+    }
+    pub(crate) fn thumbnail() -> String {
+        LabelKind::Thumbnail.as_str().to_string()
+    }
+    pub(crate) fn tileset(tileset_id: u32) -> String {
+        indexed(LabelKind::Tileset, tileset_id)
+    }
+    pub(crate) fn tileset_image(tileset_id: u32) -> String {
+        indexed(LabelKind::TilesetImage, tileset_id)
+    }
+    pub(crate) fn slice(name: &str) -> String {
+        named(LabelKind::Slice, name)
+    }
+    pub(crate) fn text_placeholder(name: &str) -> String {
+        named(LabelKind::TextPlaceholder, name)
+    }
+    pub(crate) fn animation_events(tag_name: &str) -> String {
+        named(LabelKind::AnimationEvents, tag_name)
+    }
+    pub(crate) fn frame_collision(frame: u32) -> String {
+        indexed(LabelKind::FrameCollision, frame)
+    }
+    pub(crate) fn layer_image(frame: u32, layer_name: &str) -> String {
+        let layer = named(LabelKind::LayerImage, layer_name);
+        format!("{}/{}", frame_image(frame), layer)
+    }
+    pub(crate) fn channel_image(frame: u32, channel_name: &str) -> String {
+        let channel = named(LabelKind::ChannelImage, channel_name);
+        format!("{}/{}", frame_image(frame), channel)
+    }
+
+    // A parsed sub-asset label.
+    pub(crate) enum Parsed<'a> {
+        Animation(&'a str),
+        FrameImage(u32),
+        Atlas,
+        LodAtlas(u32),
+        PaletteAtlas(u32),
+        OutlineAtlas,
+        TagAtlas(&'a str),
+        ShadowAtlas,
+        Thumbnail,
+        Tileset(u32),
+        TilesetImage(u32),
+        Slice(&'a str),
+        TextPlaceholder(&'a str),
+        AnimationEvents(&'a str),
+        FrameCollision(u32),
+        LayerImage(u32, &'a str),
+        ChannelImage(u32, &'a str),
+    }
+
+    impl Parsed<'_> {
+        pub(crate) fn kind(&self) -> LabelKind {
+            match self {
+                Parsed::Animation(_) => LabelKind::Animation,
+                Parsed::FrameImage(_) => LabelKind::FrameImage,
+                Parsed::Atlas => LabelKind::Atlas,
+                Parsed::LodAtlas(_) => LabelKind::LodAtlas,
+                Parsed::PaletteAtlas(_) => LabelKind::PaletteAtlas,
+                Parsed::OutlineAtlas => LabelKind::OutlineAtlas,
+                Parsed::TagAtlas(_) => LabelKind::TagAtlas,
+                Parsed::ShadowAtlas => LabelKind::ShadowAtlas,
+                Parsed::Thumbnail => LabelKind::Thumbnail,
+                Parsed::Tileset(_) => LabelKind::Tileset,
+                Parsed::TilesetImage(_) => LabelKind::TilesetImage,
+                Parsed::Slice(_) => LabelKind::Slice,
+                Parsed::TextPlaceholder(_) => LabelKind::TextPlaceholder,
+                Parsed::AnimationEvents(_) => LabelKind::AnimationEvents,
+                Parsed::FrameCollision(_) => LabelKind::FrameCollision,
+                Parsed::LayerImage(..) => LabelKind::LayerImage,
+                Parsed::ChannelImage(..) => LabelKind::ChannelImage,
+            }
+        }
+    }
+
+    // Returns the name in a `{kind}/{name}` label.
+    fn strip_named(label: &str, kind: LabelKind) -> Option<&str> {
+        label.strip_prefix(kind.as_str())?.strip_prefix('/')
+    }
+    // Returns the index in a `{kind}{index}` label.
+    fn strip_indexed(label: &str, kind: LabelKind) -> Option<u32> {
+        label.strip_prefix(kind.as_str())?.parse().ok()
+    }
+
+    pub(crate) fn parse(label: &str) -> Option<Parsed> {
+        if label == LabelKind::Atlas.as_str() {
+            return Some(Parsed::Atlas);
+        }
+        if label == LabelKind::ShadowAtlas.as_str() {
+            return Some(Parsed::ShadowAtlas);
+        }
+        if label == LabelKind::Thumbnail.as_str() {
+            return Some(Parsed::Thumbnail);
+        }
+        if let Some(tag_name) = strip_named(label, LabelKind::Animation) {
+            return Some(Parsed::Animation(tag_name));
+        }
+        if let Some(name) = strip_named(label, LabelKind::Slice) {
+            return Some(Parsed::Slice(name));
+        }
+        if let Some(name) = strip_named(label, LabelKind::TextPlaceholder) {
+            return Some(Parsed::TextPlaceholder(name));
+        }
+        if let Some(tag_name) = strip_named(label, LabelKind::AnimationEvents) {
+            return Some(Parsed::AnimationEvents(tag_name));
+        }
+        if let Some(atlas) = strip_named(label, LabelKind::Atlas) {
+            if atlas == LabelKind::OutlineAtlas.as_str() {
+                return Some(Parsed::OutlineAtlas);
+            }
+            if let Some(tag_name) = strip_named(atlas, LabelKind::TagAtlas) {
+                return Some(Parsed::TagAtlas(tag_name));
+            }
+            if let Some(level) = strip_indexed(atlas, LabelKind::LodAtlas) {
+                return Some(Parsed::LodAtlas(level));
+            }
+            return strip_indexed(atlas, LabelKind::PaletteAtlas).map(Parsed::PaletteAtlas);
+        }
+        if let Some((frame_image, image)) = label.split_once('/') {
+            let frame = strip_indexed(frame_image, LabelKind::FrameImage)?;
+            if let Some(layer_name) = strip_named(image, LabelKind::LayerImage) {
+                return Some(Parsed::LayerImage(frame, layer_name));
+            }
+            let channel_name = strip_named(image, LabelKind::ChannelImage)?;
+            return Some(Parsed::ChannelImage(frame, channel_name));
+        }
+        if let Some(frame) = strip_indexed(label, LabelKind::FrameImage) {
+            return Some(Parsed::FrameImage(frame));
+        }
+        if let Some(frame) = strip_indexed(label, LabelKind::FrameCollision) {
+            return Some(Parsed::FrameCollision(frame));
+        }
+        // Checked before "Tileset", which is a prefix of "TilesetImage".
+        if let Some(id) = strip_indexed(label, LabelKind::TilesetImage) {
+            return Some(Parsed::TilesetImage(id));
+        }
+        strip_indexed(label, LabelKind::Tileset).map(Parsed::Tileset)
+    }
+}
+
 /// Makes a `HandleId` for an [`Animation`][crate::asset::animation::Animation].
 ///
 /// The path of each tag's [`Animation`][crate::asset::animation::Animation]
@@ -74,8 +442,8 @@ fn make(path: &str, kind: &str, suffix: Option<&str>) -> HandleId {
 ///     "assets/my_ase_file.aseprite#Animation/my_tag".into()
 /// );
 /// ```
-pub fn animation(path: &str, tag_name: &str) -> HandleId {
-    make(path, "Animation/", Some(tag_name))
+pub fn animation(path: impl FilePath, tag_name: &str) -> HandleId {
+    make(path.file_path(), label::animation(tag_name))
 }
 
 /// Makes a `HandleId` for a frame's [`Image`][bevy::prelude::Image].
@@ -95,8 +463,8 @@ pub fn animation(path: &str, tag_name: &str) -> HandleId {
 ///   "assets/my_ase_file.aseprite#FrameImage2".into()
 /// );
 /// ```
-pub fn frame_image(path: &str, frame: u32) -> HandleId {
-    make(path, "FrameImage", Some(&frame.to_string()))
+pub fn frame_image(path: impl FilePath, frame: u32) -> HandleId {
+    make(path.file_path(), label::frame_image(frame))
 }
 
 /// Makes a `HandleId` for a [`TextureAtlas`][bevy::prelude::TextureAtlas].
@@ -114,8 +482,135 @@ pub fn frame_image(path: &str, frame: u32) -> HandleId {
 ///   "assets/my_ase_file.aseprite#Atlas".into()
 /// );
 /// ```
-pub fn atlas(path: &str) -> HandleId {
-    make(path, "Atlas", None)
+pub fn atlas(path: impl FilePath) -> HandleId {
+    make(path.file_path(), label::atlas())
+}
+
+/// Makes a `HandleId` for a downscaled [`TextureAtlas`][bevy::prelude::TextureAtlas].
+///
+/// The path of each level's [`TextureAtlas`][bevy::prelude::TextureAtlas] takes the form
+/// `{file_path}#Atlas/Lod{level}`. Level 1 is at half size, level 2 at a quarter, and so on.
+/// Downscaled atlases are only generated when
+/// [`ProcessSettings::lod_levels`][crate::process::ProcessSettings::lod_levels] is set.
+///
+/// # Examples
+/// ```
+/// use bevy_ase::handle_id;
+///
+/// let my_file_path = "assets/my_ase_file.aseprite";
+///
+/// assert_eq!(
+///   handle_id::lod_atlas(my_file_path, 1),
+///   "assets/my_ase_file.aseprite#Atlas/Lod1".into()
+/// );
+/// ```
+pub fn lod_atlas(path: impl FilePath, level: u32) -> HandleId {
+    make(path.file_path(), label::lod_atlas(level))
+}
+
+/// Makes a `HandleId` for a [`TextureAtlas`][bevy::prelude::TextureAtlas] recolored with a variant palette.
+///
+/// The path of each variant's [`TextureAtlas`][bevy::prelude::TextureAtlas] takes the form
+/// `{file_path}#Atlas/Palette{number}`, numbered like the variant palette files.
+/// Variants are only baked when
+/// [`AseAssetLoader::palette_variants`][crate::loader::AseAssetLoader::palette_variants] is set.
+///
+/// # Examples
+/// ```
+/// use bevy_ase::handle_id;
+///
+/// let my_file_path = "assets/my_ase_file.aseprite";
+///
+/// assert_eq!(
+///   handle_id::palette_atlas(my_file_path, 1),
+///   "assets/my_ase_file.aseprite#Atlas/Palette1".into()
+/// );
+/// ```
+pub fn palette_atlas(path: impl FilePath, number: u32) -> HandleId {
+    make(path.file_path(), label::palette_atlas(number))
+}
+
+/// Makes a `HandleId` for the [`TextureAtlas`][bevy::prelude::TextureAtlas] of a file's outlines.
+///
+/// The path of the outline [`TextureAtlas`][bevy::prelude::TextureAtlas] takes the form
+/// `{file_path}#Atlas/Outline`. Outlines are only generated when
+/// [`ProcessSettings::outline`][crate::process::ProcessSettings::outline] is set.
+///
+/// # Examples
+/// ```
+/// use bevy_ase::handle_id;
+///
+/// let my_file_path = "assets/my_ase_file.aseprite";
+///
+/// assert_eq!(
+///   handle_id::outline_atlas(my_file_path),
+///   "assets/my_ase_file.aseprite#Atlas/Outline".into()
+/// );
+/// ```
+pub fn outline_atlas(path: impl FilePath) -> HandleId {
+    make(path.file_path(), label::outline_atlas())
+}
+
+/// Makes a `HandleId` for the [`TextureAtlas`][bevy::prelude::TextureAtlas] of a tag's frames.
+///
+/// The path of each tag's [`TextureAtlas`][bevy::prelude::TextureAtlas] takes the form
+/// `{file_path}#Atlas/Tag/{tag_name}`. Tag atlases are only packed when
+/// [`ProcessSettings::atlas_per_tag`][crate::process::ProcessSettings::atlas_per_tag] is set.
+///
+/// # Examples
+/// ```
+/// use bevy_ase::handle_id;
+///
+/// let my_file_path = "assets/my_ase_file.aseprite";
+///
+/// assert_eq!(
+///   handle_id::tag_atlas(my_file_path, "walk"),
+///   "assets/my_ase_file.aseprite#Atlas/Tag/walk".into()
+/// );
+/// ```
+pub fn tag_atlas(path: impl FilePath, tag_name: &str) -> HandleId {
+    make(path.file_path(), label::tag_atlas(tag_name))
+}
+
+/// Makes a `HandleId` for the [`TextureAtlas`][bevy::prelude::TextureAtlas] of a file's shadow.
+///
+/// The path of the shadow's [`TextureAtlas`][bevy::prelude::TextureAtlas] takes the form
+/// `{file_path}#ShadowAtlas`. Shadows are only split off when
+/// [`ProcessSettings::shadow_layer`][crate::process::ProcessSettings::shadow_layer] is set.
+///
+/// # Examples
+/// ```
+/// use bevy_ase::handle_id;
+///
+/// let my_file_path = "assets/my_ase_file.aseprite";
+///
+/// assert_eq!(
+///   handle_id::shadow_atlas(my_file_path),
+///   "assets/my_ase_file.aseprite#ShadowAtlas".into()
+/// );
+/// ```
+pub fn shadow_atlas(path: impl FilePath) -> HandleId {
+    make(path.file_path(), label::shadow_atlas())
+}
+
+/// Makes a `HandleId` for a file's thumbnail [`Image`][bevy::prelude::Image].
+///
+/// The path of the thumbnail takes the form `{file_path}#Thumbnail`. Thumbnails are only
+/// generated when [`ProcessSettings::thumbnail`][crate::process::ProcessSettings::thumbnail] is set.
+///
+/// # Examples
+/// ```
+/// use bevy_ase::handle_id;
+///
+/// let my_file_path = "assets/my_ase_file.aseprite";
+///
+/// assert_eq!(
+///   handle_id::thumbnail(my_file_path),
+///   "assets/my_ase_file.aseprite#Thumbnail".into()
+/// );
+/// ```
+pub fn thumbnail(path: impl FilePath) -> HandleId {
+    make(path.file_path(), label::thumbnail())
 }
 
 /// Makes a `HandleId` for a [`Tileset`][crate::asset::Tileset].
@@ -135,8 +630,8 @@ pub fn atlas(path: &str) -> HandleId {
 ///   "assets/my_ase_file.aseprite#Tileset5".into()
 /// );
 /// ```
-pub fn tileset(path: &str, tileset_id: u32) -> HandleId {
-    make(path, "Tileset", Some(&tileset_id.to_string()))
+pub fn tileset(path: impl FilePath, tileset_id: u32) -> HandleId {
+    make(path.file_path(), label::tileset(tileset_id))
 }
 
 /// Makes a `HandleId` for a tileset [`Image`][bevy::prelude::Image].
@@ -156,8 +651,8 @@ pub fn tileset(path: &str, tileset_id: u32) -> HandleId {
 ///   "assets/my_ase_file.aseprite#TilesetImage1".into()
 /// );
 /// ```
-pub fn tileset_image(path: &str, tileset_id: u32) -> HandleId {
-    make(path, "TilesetImage", Some(&tileset_id.to_string()))
+pub fn tileset_image(path: impl FilePath, tileset_id: u32) -> HandleId {
+    make(path.file_path(), label::tileset_image(tileset_id))
 }
 
 /// Makes a `HandleId` for a [`Slice`][crate::asset::slice::Slice].
@@ -182,6 +677,116 @@ pub fn tileset_image(path: &str, tileset_id: u32) -> HandleId {
 ///     "assets/my_ase_file.aseprite#Slice/my_slice".into()
 /// );
 /// ```
-pub fn slice(path: &str, name: &str) -> HandleId {
-    make(path, "Slice/", Some(name))
+pub fn slice(path: impl FilePath, name: &str) -> HandleId {
+    make(path.file_path(), label::slice(name))
+}
+
+/// Makes a `HandleId` for a [`UiTextPlaceholder`][crate::asset::UiTextPlaceholder].
+///
+/// The path of each [`UiTextPlaceholder`][crate::asset::UiTextPlaceholder]
+/// takes the form `{file_path}#TextPlaceholder/{slice_name}`. Placeholders are only generated
+/// when [`ProcessSettings::text_slices`][crate::process::ProcessSettings::text_slices] is set.
+///
+/// # Examples
+/// ```
+/// use bevy_ase::handle_id;
+///
+/// let my_file_path = "assets/my_ase_file.aseprite";
+///
+/// assert_eq!(
+///     handle_id::text_placeholder(my_file_path, "title"),
+///     "assets/my_ase_file.aseprite#TextPlaceholder/title".into()
+/// );
+/// ```
+pub fn text_placeholder(path: impl FilePath, name: &str) -> HandleId {
+    make(path.file_path(), label::text_placeholder(name))
+}
+
+/// Makes a `HandleId` for the [`AnimationEvents`][crate::asset::AnimationEvents] of a tag.
+///
+/// The path of each tag's [`AnimationEvents`][crate::asset::AnimationEvents]
+/// takes the form `{file_path}#Events/{tag_name}`.
+/// Only tags with events have an [`AnimationEvents`][crate::asset::AnimationEvents] asset.
+///
+/// # Examples
+/// ```
+/// use bevy_ase::handle_id;
+///
+/// let my_file_path = "assets/my_ase_file.aseprite";
+/// let tag_name = "my_tag";
+///
+/// assert_eq!(
+///     handle_id::animation_events(my_file_path, tag_name),
+///     "assets/my_ase_file.aseprite#Events/my_tag".into()
+/// );
+/// ```
+pub fn animation_events(path: impl FilePath, tag_name: &str) -> HandleId {
+    make(path.file_path(), label::animation_events(tag_name))
+}
+
+/// Makes a `HandleId` for a frame's [`FrameCollision`][crate::asset::FrameCollision].
+///
+/// The path of each frame's [`FrameCollision`][crate::asset::FrameCollision]
+/// takes the form `{file_path}#FrameCollision{index}`.
+/// Collision shapes are only generated when
+/// [`ProcessSettings::collision`][crate::process::ProcessSettings::collision] is set.
+///
+/// # Examples
+/// ```
+/// use bevy_ase::handle_id;
+///
+/// let my_file_path = "assets/my_ase_file.aseprite";
+/// let frame: u32 = 2;
+///
+/// assert_eq!(
+///   handle_id::frame_collision(my_file_path, frame),
+///   "assets/my_ase_file.aseprite#FrameCollision2".into()
+/// );
+/// ```
+pub fn frame_collision(path: impl FilePath, frame: u32) -> HandleId {
+    make(path.file_path(), label::frame_collision(frame))
+}
+
+/// Makes a `HandleId` for the [`Image`][bevy::prelude::Image] of one layer in a frame.
+///
+/// The path of each layer's [`Image`][bevy::prelude::Image]
+/// takes the form `{file_path}#FrameImage{index}/Layer/{layer_name}`.
+/// These images are imported for top-level layers and groups with
+/// [`LayerFlattening::TopLevelGroups`][crate::process::LayerFlattening::TopLevelGroups].
+///
+/// # Examples
+/// ```
+/// use bevy_ase::handle_id;
+///
+/// let my_file_path = "assets/my_ase_file.aseprite";
+///
+/// assert_eq!(
+///   handle_id::layer_image(my_file_path, 2, "Body"),
+///   "assets/my_ase_file.aseprite#FrameImage2/Layer/Body".into()
+/// );
+/// ```
+pub fn layer_image(path: impl FilePath, frame: u32, layer_name: &str) -> HandleId {
+    make(path.file_path(), label::layer_image(frame, layer_name))
+}
+
+/// Makes a `HandleId` for the [`Image`][bevy::prelude::Image] of one channel in a frame.
+///
+/// The path of each channel's [`Image`][bevy::prelude::Image]
+/// takes the form `{file_path}#FrameImage{index}/Channel/{channel_name}`,
+/// e.g. for normal or emissive maps. Multi-channel images are not imported yet;
+/// this label is reserved for them.
+///
+/// # Examples
+/// ```
+/// use bevy_ase::handle_id;
+///
+/// let my_file_path = "assets/my_ase_file.aseprite";
+///
+/// assert_eq!(
+///   handle_id::channel_image(my_file_path, 0, "Normal"),
+///   "assets/my_ase_file.aseprite#FrameImage0/Channel/Normal".into()
+/// );
+/// ```
+pub fn channel_image(path: impl FilePath, frame: u32, channel_name: &str) -> HandleId {
+    make(path.file_path(), label::channel_image(frame, channel_name))
 }
@@ -14,8 +14,13 @@
 //! - [Image](bevy::render::texture::Image) data, which contains the file's images.
 //! - [TextureAtlas](bevy::sprite::TextureAtlas) data, which contains mapping information for each sprite in a spritesheet.
 //! - [Animation](asset::Animation) data.
+//! - [AnimationEvents](asset::AnimationEvents) data, authored in tag and cel user data.
 //! - [Slice](asset::slice::Slice) data.
 //! - [Tileset](asset::Tileset) data (from files created in Aseprite v1.3 beta).
+//! - [FrameCollision](asset::FrameCollision) data, when enabled with
+//!   [ProcessSettings::collision](process::ProcessSettings::collision).
+//! - [UiTextPlaceholder](asset::UiTextPlaceholder) data for text regions of UI mockups, when
+//!   enabled with [ProcessSettings::text_slices](process::ProcessSettings::text_slices).
 //!
 //! # Configuration
 //!
@@ -23,8 +28,25 @@
 //! This plugin initializes all of the above resources as Asset types,
 //! adds [Loader](loader::Loader) and [AseAssetLoader](loader::AseAssetLoader) resources,
 //! and adds an [importer system function](loader::ase_importer) to process loaded ase data.
+//! It also adds a [system](loader::generate_lazy_frame_images) creating frame images on demand,
+//! for files processed without keeping frame images.
+//! These systems run in the [loader::AseLoaderSet] system sets.
+//! [loader::AseLoaderPlugin] adds the same with configurable settings, e.g. the sets' base set.
 //! For a custom configuration, import the constituent parts and add them to AppBuilder directly.
-//! The Texture resource is required to be initialized. Other asset types are optional.
+//! All asset types are optional. Without the [Image](bevy::render::texture::Image) asset type,
+//! no textures, atlases or tileset textures are created, which suits headless servers
+//! that only need animation, slice or tileset data.
+//! In that case, each animation frame's atlas index is its frame index.
+//! Other missing resources are skipped as follows:
+//!
+//! - Without [TextureAtlas](bevy::sprite::TextureAtlas), no atlases or frame images are created.
+//! - Without [Tileset](asset::Tileset), no tilesets or tileset images are created.
+//! - Without [Animation](asset::Animation), no animations or animation events are created.
+//! - Without [AnimationEvents](asset::AnimationEvents), animations are created without events.
+//! - Without [Slice](asset::slice::Slice), [FrameCollision](asset::FrameCollision) or
+//!   [UiTextPlaceholder](asset::UiTextPlaceholder), those assets are not created.
+//! - Without [AseFileMap](asset::AseFileMap), all other assets are created under their
+//!   [handle ids](handle_id), but not indexed. Frame images can't be created lazily then.
 //!
 //! # Examples
 //!
@@ -81,6 +103,40 @@
 //!     }
 //! }
 //! ```
+//!
+//! Add the [player::clamp_sprite_indices] system to keep sprite indices in range
+//! when hot reloading shrinks an atlas.
+//!
+//! ## Test support
+//!
+//! When compiled with the "test_support" feature, the [test_support] module provides
+//! helpers to load and validate Aseprite files in unit tests, without a running app.
+//!
+//! ## Rayon
+//!
+//! When compiled with the "rayon" feature, texture atlases are filled in parallel
+//! during processing. Run `cargo bench` to compare processing performance.
+//!
+//! ## Manifest
+//!
+//! When compiled with the "manifest" feature, [asset map snapshots](asset::snapshot)
+//! can be serialized to RON or JSON, e.g. to generate a manifest of every imported asset.
+//!
+//! The [manifest] module also loads manifests in the other direction: artists curate which
+//! files and tags the game imports with the `scripts/export_manifest.lua` Aseprite script,
+//! and [AseManifestPlugin](manifest::AseManifestPlugin) queues the listed files.
+//!
+//! ## seldom_state
+//!
+//! When compiled with the "seldom_state" feature, the [seldom_state] module provides
+//! triggers and states integrating the animation player with the seldom_state crate.
+//!
+//! ## Embedded
+//!
+//! When compiled with the "embedded" feature, the [include_ase] macro embeds Aseprite files
+//! into the executable. [Loader::add_embedded](loader::Loader::add_embedded) imports them
+//! under their virtual path, so games can ship without an assets folder.
+//!
 //! [`Bevy`]: https://bevyengine.org/
 //! [`Aseprite`]: https://www.aseprite.org/
 //! [`Resources`]: https://bevyengine.org/learn/book/getting-started/resources/
@@ -117,13 +173,122 @@ pub mod asset;
 /// ```
 #[cfg(feature = "benimator")]
 pub mod benimator;
+mod blocking;
+pub use blocking::{load_file_blocking, BlockingLoadError};
+mod capability;
+pub use capability::{capabilities, Capabilities};
 
+/// Data model for asset browser panels in editors.
+///
+/// Enabled by the "editor" feature. [editor::AseBrowser] lists every imported file with its
+/// thumbnail, tags, slices and tilesets, and [editor::SpawnPreview] events spawn a preview
+/// entity of a file. UI libraries such as egui can render the browser from this data.
+#[cfg(feature = "editor")]
+pub mod editor;
+/// Aseprite files embedded at compile time.
+#[cfg(feature = "embedded")]
+pub mod embedded;
+/// Strongly typed game events from animation events.
+///
+/// Add an [event_registry::AnimationEventRegistryPlugin] to map animation events authored
+/// in Aseprite, such as `event:hit=12`, to a game's own event type.
+pub mod event_registry;
 pub mod handle_id;
 /// Provides systems and resources for loading Aseprite files.
 ///
 /// The default loader configuration provided by [loader::AseLoaderDefaultPlugin] contains
 /// asset types and processing for all Aseprite data types provided by this library.
 pub mod loader;
+/// Manifests of Aseprite files for the game to import.
+///
+/// Enabled by the "manifest" feature. [manifest::AseManifestPlugin] loads
+/// [manifest::Manifest] assets, written by an included Aseprite script, and queues
+/// the files they list on the [Loader](loader::Loader).
+#[cfg(feature = "manifest")]
+pub mod manifest;
+mod memory;
+pub use memory::{memory_report, FileMemory, MemoryReport};
+/// Meshes for world-space 9-slice panels.
+///
+/// [nine_slice::nine_slice_mesh] stretches a slice's center and edges while keeping
+/// its corners fixed, for speech bubbles and panels rendered as 2D meshes.
+/// [nine_slice::nine_slice_tiles] builds panels out of tiles instead, for UI rendered on a tilemap.
+pub mod nine_slice;
+/// Plays [Animation](asset::Animation) assets on sprite entities.
+///
+/// Add the [player::AnimationPlayerPlugin] and insert an [player::AnimationPlayer]
+/// component on entities with a [SpriteSheetBundle](bevy::prelude::SpriteSheetBundle).
+/// Players loop or play once, can be held on any frame, and stop on Aseprite hold frames.
+pub mod player;
+/// Shows [Animation](asset::Animation) frames at a manually set time.
+///
+/// Insert a [preview::AnimationPreview] instead of an [AnimationPlayer](player::AnimationPlayer)
+/// to scrub through an animation, e.g. with a slider in an editor UI.
+pub mod preview;
+/// Processes Aseprite files into plain data, independent of Bevy resources.
+///
+/// [process::process_file] performs the same processing as the [loader], returning images
+/// as raw RGBA buffers alongside animation, slice and tileset metadata.
+/// Files in RGBA, indexed and grayscale color mode all produce RGBA images; see [process::RawImage].
+/// External tools and asset bakers can use it to reuse the runtime loader's logic.
+pub mod process;
 mod processing;
+/// Integrates the [animation player](player) with the seldom_state crate.
+///
+/// Enabled by the "seldom_state" feature. Provides a [seldom_state::DoneTrigger]
+/// firing when an entity's animation finishes, and a [seldom_state::PlayAnimation]
+/// state which plays an animation when entered.
+///
+/// # Examples
+///
+/// ```
+/// #[cfg(feature = "seldom_state")]
+/// fn hero_state_machine(
+///     attack: bevy::prelude::Handle<bevy_ase::asset::Animation>,
+///     idle: bevy::prelude::Handle<bevy_ase::asset::Animation>,
+/// ) -> seldom_state::prelude::StateMachine {
+///     use bevy_ase::seldom_state::{DoneTrigger, PlayAnimation};
+///     use seldom_state::prelude::*;
+///
+///     // Return to idle once the attack animation finished.
+///     StateMachine::new(PlayAnimation::once(attack))
+///         .trans::<PlayAnimation>(DoneTrigger, PlayAnimation::new(idle))
+/// }
+/// ```
+#[cfg(feature = "seldom_state")]
+pub mod seldom_state;
+/// Drop shadows rendered as separate sprites.
+///
+/// Files processed with [ProcessSettings::shadow_layer](process::ProcessSettings::shadow_layer)
+/// have their shadow layer split into its own atlas. [shadow::spawn_shadow] spawns it under a
+/// sprite, where it follows the sprite's frame and flip.
+pub mod shadow;
+/// A minimal state machine switching between animations.
+///
+/// [state_machine::AseStateMachine] maps named states to [Animation](asset::Animation)
+/// handles, and transitions between them when an animation finishes,
+/// an event is triggered, or a flag changes.
+pub mod state_machine;
+/// Utilities for testing asset pipelines against real Aseprite files.
+///
+/// Enabled by the "test_support" feature. Functions in this module process files
+/// headlessly, without a running Bevy app, using the same logic as the [loader].
+/// Games can use them to write asset-pipeline unit tests against real files.
+#[cfg(any(test, feature = "test_support"))]
+pub mod test_support;
 #[cfg(test)]
 mod tests;
+/// Tinting sprites with tag and layer colors set in Aseprite.
+///
+/// Add an [tint::AseTint] to a sprite to tint it on spawn, e.g. for palette variants.
+pub mod tint;
+/// Imports every Aseprite file in an asset folder, including files added later.
+///
+/// Add [watch::AseFolderWatchPlugin] to queue new and changed files of a folder on the
+/// [Loader](loader::Loader), e.g. for rapid prototyping.
+pub mod watch;
+/// Draw order authored in Aseprite.
+///
+/// Add a [z_order::AseZOrder] to a sprite to set its z translation from a layer's user data,
+/// e.g. for decor sprites whose draw order artists control.
+pub mod z_order;
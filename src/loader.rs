@@ -1,19 +1,32 @@
-use crate::asset::asset_index::AseFileMap;
-use crate::asset::{ase::AseData, slice::Slice, Animation, AseAsset, Tileset};
-use crate::processing::{self, ResourceDataByFile};
+use crate::asset::asset_index::{AseAssetMap, AseFileMap};
+use crate::asset::{
+    ase::AseData, slice::Slice, Animation, AnimationEvents, AseAsset, CompositeTileset,
+    FrameCollision, Tileset, UiTextPlaceholder,
+};
+use crate::handle_id::{self, FilePath, LabelKind};
+use crate::process::{
+    ColorProfile, FileHeader, LimitExceeded, Palette, ProcessSettings, RawImage, UnsupportedFormat,
+};
+use crate::processing::{self, ResourceData, ResourceDataByFile};
 use asefile::AsepriteFile;
 use bevy::{
-    asset::{AssetLoader, BoxedFuture, LoadState, LoadedAsset},
+    asset::{AssetLoader, AssetPath, BoxedFuture, HandleId, LoadContext, LoadState, LoadedAsset},
     ecs::system::Res,
     prelude::*,
-    tasks::AsyncComputeTaskPool,
+    tasks::{AsyncComputeTaskPool, IoTaskPool, TaskPool, TaskPoolBuilder},
+    utils::{Duration, HashMap, HashSet, Instant},
 };
 use std::{
-    path::PathBuf,
+    collections::VecDeque,
+    fmt,
+    future::Future,
+    path::{Path, PathBuf},
+    pin::Pin,
     sync::{
         atomic::{AtomicU32, Ordering},
-        Arc, Mutex,
+        Arc, Mutex, MutexGuard, PoisonError, TryLockError,
     },
+    task::{Context, Poll, Waker},
 };
 
 /// Provides a default Bevy app configuration for loading Aseprite files.
@@ -21,6 +34,12 @@ use std::{
 /// This initializes all of bevy_ase's asset types, a [Loader] resource,
 /// an [AseAssetLoader] asset loader, and the [ase_importer] system function.
 ///
+/// The plugin does not require Bevy's render plugins. It runs with only
+/// `MinimalPlugins` and `AssetPlugin`, e.g. in CI tests or on a headless server.
+///
+/// Its systems run in the [AseLoaderSet] sets, within [CoreSet::Update]. Add an
+/// [AseLoaderPlugin] instead to configure the loader.
+///
 /// # Examples
 ///
 /// ```
@@ -32,25 +51,140 @@ use std::{
 ///         // Add the default plugin to the bevy app build.
 ///         .add_plugin(AseLoaderDefaultPlugin);
 /// }
+///
+/// // Headless apps work as well.
+/// fn headless_app() {
+///     App::new()
+///         .add_plugins(MinimalPlugins)
+///         .add_plugin(AssetPlugin::default())
+///         .add_plugin(AseLoaderDefaultPlugin);
+/// }
 /// ```
+#[derive(Debug, Clone, Copy, Default)]
 pub struct AseLoaderDefaultPlugin;
 
 impl Plugin for AseLoaderDefaultPlugin {
+    fn build(&self, app: &mut App) {
+        AseLoaderPlugin::default().build(app);
+    }
+}
+
+/// Like [AseLoaderDefaultPlugin], with configurable settings.
+///
+/// Its systems run in the [AseLoaderSet] sets, within `base_set`. By default, this is
+/// [CoreSet::Update]. Running them in [CoreSet::PreUpdate] instead makes imported assets
+/// and [AseFileMapChanged] events available to every system in [CoreSet::Update] on the same frame.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::loader::AseLoaderPlugin;
+///
+/// // Import files before any system in CoreSet::Update runs.
+/// fn pre_update_app() {
+///     App::new()
+///         .add_plugins(DefaultPlugins)
+///         .add_plugin(AseLoaderPlugin {
+///             base_set: CoreSet::PreUpdate,
+///             ..Default::default()
+///         });
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AseLoaderPlugin {
+    /// The base set the loader's systems run in.
+    pub base_set: CoreSet,
+    /// The task pool files are processed on. Defaults to [ProcessingPool::AsyncCompute].
+    ///
+    /// Sets [Loader::set_processing_pool] when the plugin is built.
+    pub processing_pool: ProcessingPool,
+    /// Whether every Aseprite file loaded by the [AssetServer] is imported, without
+    /// [Loader::add]. Defaults to false.
+    ///
+    /// Sets [Loader::set_auto_import] when the plugin is built.
+    pub auto_import: bool,
+}
+
+impl Default for AseLoaderPlugin {
+    fn default() -> Self {
+        Self {
+            base_set: CoreSet::Update,
+            processing_pool: ProcessingPool::default(),
+            auto_import: false,
+        }
+    }
+}
+
+impl Plugin for AseLoaderPlugin {
     fn build(&self, app: &mut App) {
         app.add_asset::<AseAsset>()
             .add_asset::<Image>()
             .add_asset::<TextureAtlas>()
             .add_asset::<Animation>()
             .add_asset::<Tileset>()
+            .add_asset::<CompositeTileset>()
             .add_asset::<Slice>()
+            .add_asset::<AnimationEvents>()
+            .add_asset::<FrameCollision>()
+            .add_asset::<UiTextPlaceholder>()
             .init_resource::<Loader>()
             .init_resource::<AseFileMap>()
+            .add_event::<AseImportError>()
+            .add_event::<AseFileMapChanged>()
+            .add_event::<AseFileImported>()
+            .add_event::<AseSubAssetCreated>()
+            .add_event::<AseColorProfileWarning>()
+            .add_event::<AseCompatibilityWarning>()
+            .add_event::<AseTileIndexWarning>()
             .init_asset_loader::<AseAssetLoader>()
-            .add_system(ase_importer);
+            .configure_set(AseLoaderSet::Import.in_base_set(self.base_set.clone()))
+            .configure_set(
+                AseLoaderSet::FrameImages
+                    .in_base_set(self.base_set.clone())
+                    .after(AseLoaderSet::Import),
+            )
+            .add_system(ase_importer.in_set(AseLoaderSet::Import))
+            .add_system(generate_lazy_frame_images.in_set(AseLoaderSet::FrameImages));
+        let mut loader = app.world.resource_mut::<Loader>();
+        loader.set_processing_pool(self.processing_pool);
+        loader.set_auto_import(self.auto_import);
     }
 }
 
-const DEFAULT_EXTENSIONS: &[&str; 2] = &["aseprite", "ase"];
+/// System sets of the systems added by [AseLoaderDefaultPlugin] and [AseLoaderPlugin].
+///
+/// Systems which react to imported assets can be ordered after these sets,
+/// so they see each file's assets on the frame it was imported.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::loader::{AseFileMapChanged, AseLoaderDefaultPlugin, AseLoaderSet};
+///
+/// fn app() {
+///     App::new()
+///         .add_plugins(DefaultPlugins)
+///         .add_plugin(AseLoaderDefaultPlugin)
+///         .add_system(spawn_sprites.after(AseLoaderSet::Import));
+/// }
+///
+/// fn spawn_sprites(mut events: EventReader<AseFileMapChanged>) {
+///     for event in events.iter() {
+///         info!("Spawning sprites from {}", event.path.display());
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, SystemSet)]
+pub enum AseLoaderSet {
+    /// Contains [ase_importer], which moves processed files into resources.
+    Import,
+    /// Contains [generate_lazy_frame_images]. Runs after [AseLoaderSet::Import].
+    FrameImages,
+}
+
+pub(crate) const DEFAULT_EXTENSIONS: &[&str; 2] = &["aseprite", "ase"];
 
 /// Asset loader resource for bevy files.
 ///
@@ -75,37 +209,93 @@ const DEFAULT_EXTENSIONS: &[&str; 2] = &["aseprite", "ase"];
 ///
 /// fn build(app: &mut App) {
 ///     let my_loader = AseAssetLoader {
-///         extensions: &["aseprite", "my_custom_extension"]
+///         extensions: &["aseprite", "my_custom_extension"],
+///         ..Default::default()
 ///     };
 ///     app.add_asset_loader(my_loader);
 /// }
 ///
 /// ```
+/// ## Palette variants
+/// Adding a loader after the [AseLoaderDefaultPlugin] replaces its default loader.
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::loader::{AseAssetLoader, AseLoaderDefaultPlugin};
+///
+/// fn build(app: &mut App) {
+///     app.add_plugin(AseLoaderDefaultPlugin)
+///         .add_asset_loader(AseAssetLoader {
+///             palette_variants: true,
+///             ..Default::default()
+///         });
+/// }
+/// ```
 pub struct AseAssetLoader {
     /// Specifies which file extensions to load as Aseprite files.
     /// Defaults to ["aseprite", "ase"].
     pub extensions: &'static [&'static str],
+    /// Whether to read variant palettes from sidecar files. Defaults to false.
+    ///
+    /// Palettes are read from `{file_stem}.palette{n}.gpl` or `{file_stem}.palette{n}.hex`,
+    /// for `n` from 1 up to the first missing file; see [Palette]. Each one bakes a recolored
+    /// copy of the file's atlas, see [AseAssetMap::atlas_for_palette](crate::asset::AseAssetMap::atlas_for_palette).
+    /// Changed palette files are hot reloaded along with the Aseprite file.
+    pub palette_variants: bool,
 }
 impl Default for AseAssetLoader {
     fn default() -> Self {
         Self {
             extensions: DEFAULT_EXTENSIONS,
+            palette_variants: false,
+        }
+    }
+}
+
+// Reads the variant palettes of the loaded file, up to the first missing number.
+async fn read_palette_variants(load_context: &LoadContext<'_>) -> Vec<Palette> {
+    let mut palettes = Vec::new();
+    for number in 1.. {
+        let mut found = None;
+        for extension in Palette::EXTENSIONS {
+            let path = Palette::variant_path(load_context.path(), number, extension);
+            if let Ok(bytes) = load_context.read_asset_bytes(&path).await {
+                found = Some((path, bytes));
+                break;
+            }
+        }
+        let Some((path, bytes)) = found else {
+            break;
+        };
+        match std::str::from_utf8(&bytes).ok().and_then(Palette::parse) {
+            Some(palette) => palettes.push(palette),
+            None => {
+                warn!("Invalid palette file: {}", path.display());
+                break;
+            }
         }
     }
+    palettes
 }
 
 impl AssetLoader for AseAssetLoader {
     fn load<'a>(
         &'a self,
         bytes: &'a [u8],
-        load_context: &'a mut bevy::asset::LoadContext,
+        load_context: &'a mut LoadContext,
     ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
         Box::pin(async move {
             debug!("Loading/parsing asefile: {}", load_context.path().display());
             let data = AsepriteFile::read(bytes)?;
+            let palettes = match self.palette_variants {
+                true => read_palette_variants(load_context).await,
+                false => Vec::new(),
+            };
+            let header = FileHeader::read(bytes);
             let ase = AseAsset {
                 data: AseData::Loaded(Box::new(data)),
                 name: load_context.path().to_owned(),
+                header,
+                palettes,
             };
             load_context.set_default_asset(LoadedAsset::new(ase));
             Ok(())
@@ -136,15 +326,477 @@ impl AssetLoader for AseAssetLoader {
 pub struct Loader {
     todo_handles: Vec<Handle<AseAsset>>,
     in_progress: Arc<AtomicU32>,
-    done: Arc<Mutex<Vec<processing::ResourceDataByFile>>>,
+    done: Arc<Mutex<Vec<ResourceDataByFile>>>,
+    ready: VecDeque<(PathBuf, ImportResult<ResourceData>)>,
+    budget: ImportBudget,
+    settings: ProcessSettings,
+    load_policy: LoadPolicy,
+    placeholder: Placeholder,
+    // Placeholders of files which started processing, inserted by the next run of ase_importer.
+    pending_placeholders: Vec<(PathBuf, u32, RawImage)>,
+    // When each todo handle started loading, and how often it was retried.
+    waiting: HashMap<HandleId, (Instant, u32)>,
+    scopes: HashMap<String, Scope>,
+    groups: HashMap<LoadGroup, Group>,
+    next_group: u64,
+    // Files of unloaded groups, removed by the next run of ase_importer.
+    unloading: Vec<Handle<AseAsset>>,
+    // Files unloaded by path, removed by the next run of ase_importer.
+    unloading_paths: Vec<PathBuf>,
+    // Files of unloaded groups which were being processed. Their results are dropped.
+    cancelled: HashSet<PathBuf>,
+    // Tickets of todo handles, and of files being processed.
+    handle_tickets: HashMap<HandleId, Vec<TicketSender>>,
+    path_tickets: HashMap<PathBuf, Vec<TicketSender>>,
+    // Files with tickets which were imported before they were added again.
+    imported: Vec<PathBuf>,
+    // Files being processed, and files whose last import failed.
+    processing: HashSet<PathBuf>,
+    failed: HashSet<PathBuf>,
+    processing_limits: ProcessingLimits,
+    // Estimated decoded size of each file being processed.
+    processing_bytes: HashMap<PathBuf, u64>,
+    processing_pool: ProcessingPool,
+    // Created on first use with ProcessingPool::Dedicated.
+    dedicated_pool: Option<TaskPool>,
+    auto_import: bool,
+}
+
+// Files added through a ScopedLoader which are not imported yet.
+#[derive(Debug, Default)]
+struct Scope {
+    todo: HashSet<HandleId>,
+    processing: HashSet<PathBuf>,
+}
+
+impl Scope {
+    fn pending_count(&self) -> u32 {
+        (self.todo.len() + self.processing.len()) as u32
+    }
+}
+
+// Files added with Loader::add_group. Keeps the files' AseAssets alive until unloaded.
+#[derive(Debug, Default)]
+struct Group {
+    handles: Vec<Handle<AseAsset>>,
+    scope: Scope,
 }
 
+/// Token for a group of files added with [Loader::add_group].
+///
+/// Tokens are unique for the lifetime of a [Loader], so a token of an unloaded group
+/// is never reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LoadGroup(u64);
+
 impl Default for Loader {
     fn default() -> Self {
         Self {
             todo_handles: Vec::new(),
             in_progress: Arc::new(AtomicU32::new(0)),
             done: Arc::new(Mutex::new(Vec::new())),
+            ready: VecDeque::new(),
+            budget: ImportBudget::default(),
+            settings: ProcessSettings::default(),
+            load_policy: LoadPolicy::default(),
+            placeholder: Placeholder::default(),
+            pending_placeholders: Vec::new(),
+            waiting: HashMap::default(),
+            scopes: HashMap::default(),
+            groups: HashMap::default(),
+            next_group: 0,
+            unloading: Vec::new(),
+            unloading_paths: Vec::new(),
+            cancelled: HashSet::default(),
+            handle_tickets: HashMap::default(),
+            path_tickets: HashMap::default(),
+            imported: Vec::new(),
+            processing: HashSet::default(),
+            failed: HashSet::default(),
+            processing_limits: ProcessingLimits::default(),
+            processing_bytes: HashMap::default(),
+            processing_pool: ProcessingPool::default(),
+            dedicated_pool: None,
+            auto_import: false,
+        }
+    }
+}
+
+/// A pending import of a single file, returned by [Loader::add_awaitable].
+///
+/// The ticket resolves to the file's [LoadedFile] once [ase_importer] has moved the file
+/// into resources, or to an [ImportError] if it could not be imported. It can be polled
+/// from a system with [AseLoadTicket::try_recv], or awaited as a [Future], e.g. in an async task.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::loader::{AseLoadTicket, ImportError, LoadedFile, Loader};
+///
+/// fn load_hero(asset_server: &AssetServer, loader: &mut Loader) -> AseLoadTicket {
+///     loader.add_awaitable(asset_server.load("sprites/hero.aseprite"))
+/// }
+///
+/// async fn hero_atlas(ticket: AseLoadTicket) -> Result<Handle<TextureAtlas>, ImportError> {
+///     let file: LoadedFile = ticket.await?;
+///     Ok(file.get("Atlas").expect("Imported files have an atlas"))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AseLoadTicket {
+    state: Arc<Mutex<TicketState>>,
+}
+
+impl AseLoadTicket {
+    /// Returns the ticket's result if the file was imported, without waiting.
+    ///
+    /// Returns `None` while the file is loading or being processed. The result is
+    /// only returned once; later calls return [ImportError::Cancelled].
+    pub fn try_recv(&mut self) -> Option<Result<LoadedFile, ImportError>> {
+        lock_ticket(&self.state).take()
+    }
+}
+
+impl Future for AseLoadTicket {
+    type Output = Result<LoadedFile, ImportError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = lock_ticket(&self.state);
+        match state.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// The handles of a file imported for an [AseLoadTicket].
+///
+/// Handles are collected from [AseFileMap], so there are none without it.
+#[derive(Debug, Clone)]
+pub struct LoadedFile {
+    /// Path of the Aseprite file.
+    pub path: PathBuf,
+    handles: HashMap<String, HandleUntyped>,
+}
+
+impl LoadedFile {
+    fn new(path: &Path, file_map: Option<&AseFileMap>) -> Self {
+        let handles = file_map
+            .map(|file_map| {
+                file_map
+                    .labels(path)
+                    .into_iter()
+                    .filter_map(|label| {
+                        let handle =
+                            file_map.labeled(AssetPath::new_ref(path, Some(label.as_str())))?;
+                        Some((label, handle))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            path: path.to_path_buf(),
+            handles,
+        }
+    }
+
+    /// Returns the handle of the sub-asset with the given label, e.g. `Atlas` or `Animation/walk`.
+    ///
+    /// See [handle_id](crate::handle_id) for the labels of each asset type.
+    pub fn get<T: bevy::asset::Asset>(&self, label: &str) -> Option<Handle<T>> {
+        self.handles.get(label).map(|handle| handle.clone().typed())
+    }
+
+    /// Returns the labels of the file's sub-assets, in no particular order.
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.handles.keys().map(String::as_str)
+    }
+}
+
+// State shared by an AseLoadTicket and its sender.
+#[derive(Debug, Default)]
+struct TicketState {
+    result: Option<Result<LoadedFile, ImportError>>,
+    closed: bool,
+    waker: Option<Waker>,
+}
+
+impl TicketState {
+    fn take(&mut self) -> Option<Result<LoadedFile, ImportError>> {
+        match self.result.take() {
+            Some(result) => Some(result),
+            None if self.closed => Some(Err(ImportError::Cancelled)),
+            None => None,
+        }
+    }
+}
+
+fn lock_ticket(state: &Mutex<TicketState>) -> MutexGuard<'_, TicketState> {
+    state.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+// The Loader's side of an AseLoadTicket. Dropping it without sending cancels the ticket.
+#[derive(Debug)]
+struct TicketSender(Arc<Mutex<TicketState>>);
+
+impl TicketSender {
+    fn send(self, result: Result<LoadedFile, ImportError>) {
+        lock_ticket(&self.0).result = Some(result);
+    }
+}
+
+impl Drop for TicketSender {
+    fn drop(&mut self) {
+        let mut state = lock_ticket(&self.0);
+        state.closed = true;
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Adds files to a named scope of the [Loader], and reports the scope's loading status.
+///
+/// Created with [Loader::scoped]. Subsystems such as UI skin loading and level streaming
+/// can each use their own scope, instead of waiting on every file with [Loader::is_loaded].
+pub struct ScopedLoader<'a> {
+    loader: &'a mut Loader,
+    scope: String,
+}
+
+impl ScopedLoader<'_> {
+    /// Adds an [AseAsset] to the [Loader] for loading, as part of this scope.
+    pub fn add(&mut self, handle: Handle<AseAsset>) {
+        let scope = self.loader.scopes.entry(self.scope.clone()).or_default();
+        scope.todo.insert(handle.id());
+        self.loader.add(handle);
+    }
+
+    /// Returns the number of files in this scope still loading or being processed.
+    pub fn pending_count(&self) -> u32 {
+        self.loader
+            .scopes
+            .get(&self.scope)
+            .map_or(0, Scope::pending_count)
+    }
+
+    /// Returns true when no files in this scope are loading or being processed.
+    pub fn is_loaded(&self) -> bool {
+        self.pending_count() == 0
+    }
+}
+
+/// Limits how much processed data the [Loader] moves into resources each frame.
+///
+/// Moving a file's data into resources happens on the main thread. When many files
+/// finish processing at once, moving them all in a single frame causes a visible hitch.
+/// A budget spreads this work across frames. Files over budget wait for a later frame.
+///
+/// Both limits may be combined; the importer stops at whichever is reached first.
+/// At least one file is moved per frame, so loading always makes progress.
+/// By default, there is no limit.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::loader::{ImportBudget, Loader};
+/// use std::time::Duration;
+///
+/// // Move at most 4 files into resources per frame,
+/// // and stop early once 4 milliseconds have been spent.
+/// fn limit_imports(mut loader: ResMut<Loader>) {
+///     loader.set_budget(ImportBudget {
+///         max_files_per_frame: Some(4),
+///         max_time_per_frame: Some(Duration::from_millis(4)),
+///     });
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportBudget {
+    /// Maximum number of files moved into resources per frame.
+    /// `None` means no limit. A limit of zero is treated as one.
+    pub max_files_per_frame: Option<usize>,
+    /// Maximum time spent moving files into resources per frame.
+    /// `None` means no limit.
+    ///
+    /// The time is checked between files, so a single large file may exceed it.
+    pub max_time_per_frame: Option<Duration>,
+}
+
+/// Limits how many files the [Loader] processes at once.
+///
+/// Processing decodes every frame of a file into memory. Without a limit, all queued files
+/// are processed at once, so queueing hundreds of files holds all of their decoded frames
+/// before the first one is moved into resources. Files over the limits wait in the queue
+/// until earlier files were moved into resources.
+///
+/// A file's size is estimated as the size of its decoded frames. Both limits may be
+/// combined. At least one file is processed at a time, so files larger than `max_bytes`
+/// are still imported. By default, there is no limit.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::loader::{Loader, ProcessingLimits};
+///
+/// // Process at most 8 files, holding at most 256 MiB of decoded frames.
+/// fn limit_processing(mut loader: ResMut<Loader>) {
+///     loader.set_processing_limits(ProcessingLimits {
+///         max_files: Some(8),
+///         max_bytes: Some(256 * 1024 * 1024),
+///     });
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProcessingLimits {
+    /// Maximum number of files being processed at once.
+    /// `None` means no limit. A limit of zero is treated as one.
+    pub max_files: Option<usize>,
+    /// Maximum estimated decoded size of the files being processed at once, in bytes.
+    /// `None` means no limit.
+    pub max_bytes: Option<u64>,
+}
+
+impl ProcessingLimits {
+    // Returns true if the given number of files and bytes may be processed at once.
+    fn allows(&self, files: usize, bytes: u64) -> bool {
+        let max_files = self.max_files.unwrap_or(usize::MAX).max(1);
+        files <= max_files && bytes <= self.max_bytes.unwrap_or(u64::MAX)
+    }
+}
+
+// Estimates the size of a file's decoded frames in bytes.
+fn decoded_size(file: &AsepriteFile) -> u64 {
+    file.width() as u64 * file.height() as u64 * 4 * file.num_frames() as u64
+}
+
+/// Controls how the [Loader] handles files which fail to load, or never finish loading.
+///
+/// A file fails when the [AssetServer] reports [LoadState::Failed] for it, e.g. because
+/// it does not exist. It times out when it has not finished loading after `timeout`,
+/// e.g. after a disk hiccup or a hot reload race. Either way, it is reloaded up to
+/// `retries` times. After that, the file is dropped from the queue and reported with
+/// an [AseImportError] event, so the remaining files can be imported.
+///
+/// By default, failed files are reported right away and loads never time out.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::loader::{LoadPolicy, Loader};
+/// use std::time::Duration;
+///
+/// // Retry each file twice, giving it 10 seconds to load each time.
+/// fn retry_loads(mut loader: ResMut<Loader>) {
+///     loader.set_load_policy(LoadPolicy {
+///         retries: 2,
+///         timeout: Some(Duration::from_secs(10)),
+///     });
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoadPolicy {
+    /// Number of times a failed or timed out file is reloaded before it is reported.
+    pub retries: u32,
+    /// Time a file may take to load before it is retried or reported.
+    /// `None` means no limit.
+    pub timeout: Option<Duration>,
+}
+
+/// The task pool the [Loader] processes files on.
+///
+/// Large imports keep the pool busy until they are done. On machines with few cores,
+/// processing on the [AsyncComputeTaskPool] can then starve other async compute work,
+/// which a dedicated pool avoids. Frames of files with at least
+/// [ProcessSettings::parallel_frames_threshold] frames are decoded on scoped threads of
+/// their own, while the file's task on this pool waits for them.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::loader::{AseLoaderPlugin, ProcessingPool};
+///
+/// fn app() {
+///     App::new()
+///         .add_plugins(DefaultPlugins)
+///         .add_plugin(AseLoaderPlugin {
+///             processing_pool: ProcessingPool::Dedicated { threads: 1 },
+///             ..Default::default()
+///         });
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProcessingPool {
+    /// Bevy's [AsyncComputeTaskPool].
+    #[default]
+    AsyncCompute,
+    /// Bevy's [IoTaskPool].
+    Io,
+    /// A pool used only for processing, with the given number of threads.
+    ///
+    /// The pool is created when the first files are processed. `0` threads uses one thread.
+    Dedicated {
+        /// Number of threads in the pool.
+        threads: usize,
+    },
+}
+
+/// What the [Loader] shows under a file's handle ids while the file is processed.
+///
+/// With a placeholder, each frame image and the texture atlas of a file are inserted as
+/// soon as the file has been read, and replaced once processing completes. UI which refers
+/// to these assets by label then shows the placeholder, instead of a missing texture.
+/// Placeholders are only inserted for assets which processing will replace, according to
+/// the [ProcessSettings]. Files which fail to import keep their placeholders.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::loader::{Loader, Placeholder};
+///
+/// fn show_first_frames(mut loader: ResMut<Loader>) {
+///     loader.set_placeholder(Placeholder::FirstFrame);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Placeholder {
+    /// No placeholders are inserted.
+    #[default]
+    None,
+    /// A transparent 1x1 image.
+    Blank,
+    /// The file's first frame, decoded right away on the main thread.
+    ///
+    /// All frames show the first frame until processing completes.
+    FirstFrame,
+}
+
+impl Placeholder {
+    fn image(self, file: &AsepriteFile) -> Option<RawImage> {
+        match self {
+            Placeholder::None => None,
+            Placeholder::Blank => Some(RawImage {
+                width: 1,
+                height: 1,
+                data: vec![0; 4],
+            }),
+            Placeholder::FirstFrame => {
+                let image = file.frame(0).image();
+                Some(RawImage {
+                    width: image.width(),
+                    height: image.height(),
+                    data: image.into_raw(),
+                })
+            }
         }
     }
 }
@@ -152,6 +804,10 @@ impl Default for Loader {
 impl Loader {
     /// Adds an [AseAsset] to the [Loader] for loading.
     ///
+    /// Files added before the same run of [ase_importer] are processed as one batch.
+    /// Their assets are moved into resources, and their [AseFileMapChanged] events are sent,
+    /// in the order the files were added.
+    ///
     /// # Examples
     ///
     /// ```
@@ -172,110 +828,1264 @@ impl Loader {
         self.todo_handles.push(handle);
     }
 
-    /// Returns the number of [AseAsset] handles currently being processed.
+    /// Moves a waiting file to the front of the queue, so it is imported before other files.
+    ///
+    /// Streaming games can call this when a file is about to be needed, e.g. when the player
+    /// approaches a door. A queued file is processed first, as soon as the [ProcessingLimits]
+    /// allow. A processed file waiting for the [ImportBudget] is moved into resources first.
+    /// Files which are being processed can't be reordered.
+    ///
+    /// Returns false if the file is not waiting to be processed or moved into resources.
     ///
     /// # Examples
     ///
     /// ```
     /// use bevy::prelude::*;
     /// use bevy_ase::loader::Loader;
-    /// // System function which checks how many assets are processing.
-    /// pub fn check_loading_sprites(ase_loader: Res<Loader>) {
-    ///     info!("{} ase assets currently processing", ase_loader.pending_count());
+    ///
+    /// #[derive(Component)]
+    /// struct Door {
+    ///     next_room: &'static str,
+    /// }
+    ///
+    /// fn prefetch_next_room(doors: Query<(&Door, &Transform)>, mut loader: ResMut<Loader>) {
+    ///     for (door, transform) in &doors {
+    ///         // A real game would check the player's distance to the door.
+    ///         if transform.translation.x < 100.0 {
+    ///             loader.hint_needed_soon(door.next_room);
+    ///         }
+    ///     }
     /// }
     /// ```
-    pub fn pending_count(&self) -> u32 {
-        self.in_progress.load(Ordering::SeqCst)
+    pub fn hint_needed_soon(&mut self, path: impl FilePath) -> bool {
+        let path = path.file_path();
+        let id = HandleId::from(AssetPath::new_ref(path, None));
+        if let Some(index) = self.todo_handles.iter().position(|h| h.id() == id) {
+            let handle = self.todo_handles.remove(index);
+            self.todo_handles.insert(0, handle);
+            return true;
+        }
+        self.take_finished();
+        let ready = self
+            .ready
+            .iter()
+            .position(|(ready_path, _)| ready_path == path);
+        let Some(file) = ready.and_then(|index| self.ready.remove(index)) else {
+            return false;
+        };
+        self.ready.push_front(file);
+        true
     }
 
-    /// Returns true when no [AseAsset] handles are loading or being processed.
-    pub fn is_loaded(&self) -> bool {
-        self.todo_handles.is_empty() && self.pending_count() == 0
+    /// Returns the [ImportBudget] used to move processed files into resources.
+    pub fn budget(&self) -> &ImportBudget {
+        &self.budget
     }
 
-    fn all_todo_handles_ready(&self, asset_server: &AssetServer) -> bool {
-        let handles = self.todo_handles.iter().map(|h| h.id());
-        asset_server.get_group_load_state(handles) == LoadState::Loaded
+    /// Sets the [ImportBudget] used to move processed files into resources.
+    pub fn set_budget(&mut self, budget: ImportBudget) {
+        self.budget = budget;
     }
 
-    fn spawn_tasks(&mut self, pool: &AsyncComputeTaskPool, aseprites: &mut Assets<AseAsset>) {
-        if self.todo_handles.is_empty() {
-            return;
-        }
-
-        let in_progress = self.in_progress.clone();
-        in_progress.fetch_add(1, Ordering::SeqCst);
+    /// Returns the [LoadPolicy] for files which fail or time out while loading.
+    pub fn load_policy(&self) -> &LoadPolicy {
+        &self.load_policy
+    }
 
-        let mut handles = Vec::new();
-        std::mem::swap(&mut handles, &mut self.todo_handles);
+    /// Sets the [LoadPolicy] for files which fail or time out while loading.
+    pub fn set_load_policy(&mut self, load_policy: LoadPolicy) {
+        self.load_policy = load_policy;
+    }
 
-        let mut ase_files: Vec<(PathBuf, AsepriteFile)> = Vec::with_capacity(handles.len());
-        for h in &handles {
-            let ase_asset = aseprites
-                .get_mut(&h.clone_weak())
-                .expect("Failed to get aseprite from handle");
+    /// Returns the [Placeholder] shown while files are processed.
+    pub fn placeholder(&self) -> Placeholder {
+        self.placeholder
+    }
 
-            // We actually remove the AsepriteFile from the AsepriteAsset so
-            // the memory can be freed after we're done processing. If the file
-            // was changed we get the new data from the asset loader.
-            //
-            // TODO: Add support for changed-on disk events.
-            let mut loaded_ase = AseData::Processed;
-            std::mem::swap(&mut ase_asset.data, &mut loaded_ase);
+    /// Sets the [Placeholder] shown while files are processed.
+    pub fn set_placeholder(&mut self, placeholder: Placeholder) {
+        self.placeholder = placeholder;
+    }
 
-            if let AseData::Loaded(boxed_ase) = loaded_ase {
-                ase_files.push((ase_asset.name.clone(), *boxed_ase));
-            }
-        }
+    /// Returns the [ProcessingLimits] on files processed at once.
+    pub fn processing_limits(&self) -> &ProcessingLimits {
+        &self.processing_limits
+    }
 
-        let output = self.done.clone();
-        let task = pool.spawn(async move {
-            let processed = processing::ResourceDataByFile::new(ase_files);
-            let mut out = output.lock().expect("Failed to get lock");
-            out.push(processed);
-        });
-        task.detach();
+    /// Sets the [ProcessingLimits] on files processed at once.
+    /// Files which are already being processed are not affected.
+    pub fn set_processing_limits(&mut self, processing_limits: ProcessingLimits) {
+        self.processing_limits = processing_limits;
     }
 
-    fn take_finished(&mut self) -> Option<Vec<ResourceDataByFile>> {
-        let results = {
-            let mut lock = self.done.try_lock();
-            if let Ok(ref mut data) = lock {
-                let mut results = Vec::new();
-                std::mem::swap(&mut results, &mut *data);
-                results
-            } else {
-                return None;
-            }
-        };
-        if results.is_empty() {
-            return None;
-        }
-        Some(results)
+    /// Returns true if every loaded Aseprite file is imported. See [Loader::set_auto_import].
+    pub fn auto_import(&self) -> bool {
+        self.auto_import
     }
 
-    fn move_finished_into_resources(&mut self, mut resources: AseAssetResources) {
-        if let Some(finished) = self.take_finished() {
-            for ase in finished {
-                ase.move_into_resources(&mut resources);
-                self.in_progress.fetch_sub(1, Ordering::SeqCst);
+    /// Sets whether every Aseprite file loaded by the [AssetServer] is imported.
+    ///
+    /// When set, [ase_importer] queues each file as soon as it is loaded or reloaded, so
+    /// `asset_server.load_folder("sprites")` alone imports a whole folder. Files which are
+    /// already queued or being processed are not queued again, so calling [Loader::add] as
+    /// well is harmless. Only files loaded while this is set are imported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_ase::loader::Loader;
+    ///
+    /// fn load_sprites(asset_server: Res<AssetServer>, mut loader: ResMut<Loader>) {
+    ///     loader.set_auto_import(true);
+    ///     // Keep the handles, or the files are unloaded again.
+    ///     let _handles = asset_server.load_folder("sprites");
+    /// }
+    /// ```
+    pub fn set_auto_import(&mut self, auto_import: bool) {
+        self.auto_import = auto_import;
+    }
+
+    // Queues each file which was loaded or reloaded, see Loader::set_auto_import.
+    fn queue_loaded_files<'a>(
+        &mut self,
+        asset_server: &AssetServer,
+        aseassets: &Assets<AseAsset>,
+        asset_events: impl Iterator<Item = &'a AssetEvent<AseAsset>>,
+    ) {
+        for event in asset_events {
+            let (AssetEvent::Created { handle } | AssetEvent::Modified { handle }) = event else {
+                continue;
+            };
+            // Files the importer took the data out of send Modified too, and are skipped.
+            let loaded = aseassets
+                .get(handle)
+                .map_or(false, |ase_asset| ase_asset.file().is_some());
+            if !self.auto_import
+                || !loaded
+                || self
+                    .todo_handles
+                    .iter()
+                    .any(|todo| todo.id() == handle.id())
+            {
+                continue;
+            }
+            let processing = asset_server
+                .get_handle_path(handle)
+                .map_or(false, |path| self.processing.contains(path.path()));
+            if !processing {
+                self.add(aseassets.get_handle(handle));
+            }
+        }
+    }
+
+    /// Returns the [ProcessingPool] files are processed on.
+    pub fn processing_pool(&self) -> ProcessingPool {
+        self.processing_pool
+    }
+
+    /// Sets the [ProcessingPool] files are processed on.
+    /// Files which are already being processed are not affected.
+    pub fn set_processing_pool(&mut self, processing_pool: ProcessingPool) {
+        if processing_pool != self.processing_pool {
+            self.dedicated_pool = None;
+        }
+        self.processing_pool = processing_pool;
+    }
+
+    /// Returns the [ProcessSettings] used to process files.
+    pub fn settings(&self) -> &ProcessSettings {
+        &self.settings
+    }
+
+    /// Sets the [ProcessSettings] used to process files.
+    /// Files which are already being processed are not affected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_ase::loader::Loader;
+    /// use bevy_ase::process::ProcessSettings;
+    ///
+    /// // Only keep atlases in memory; frame images are created on first access.
+    /// fn lazy_frame_images(mut loader: ResMut<Loader>) {
+    ///     loader.set_settings(ProcessSettings {
+    ///         keep_frame_images: false,
+    ///         ..Default::default()
+    ///     });
+    /// }
+    /// ```
+    pub fn set_settings(&mut self, settings: ProcessSettings) {
+        self.settings = settings;
+    }
+
+    /// Returns the number of [AseAsset] handles currently being processed.
+    ///
+    /// This includes files which finished processing, but are still waiting
+    /// to be moved into resources.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_ase::loader::Loader;
+    /// // System function which checks how many assets are processing.
+    /// pub fn check_loading_sprites(ase_loader: Res<Loader>) {
+    ///     info!("{} ase assets currently processing", ase_loader.pending_count());
+    /// }
+    /// ```
+    pub fn pending_count(&self) -> u32 {
+        self.in_progress.load(Ordering::SeqCst)
+    }
+
+    /// Adds an [AseAsset] to the [Loader] for loading, and returns a ticket for its import.
+    ///
+    /// The [AseLoadTicket] resolves once this specific file was imported, for async-style
+    /// loading flows. Files which were already imported resolve on the next run of [ase_importer].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_ase::loader::{AseLoadTicket, Loader};
+    ///
+    /// #[derive(Resource)]
+    /// struct HeroTicket(AseLoadTicket);
+    ///
+    /// fn load_hero(mut commands: Commands, server: Res<AssetServer>, mut loader: ResMut<Loader>) {
+    ///     let ticket = loader.add_awaitable(server.load("sprites/hero.aseprite"));
+    ///     commands.insert_resource(HeroTicket(ticket));
+    /// }
+    ///
+    /// fn hero_loaded(mut ticket: ResMut<HeroTicket>) {
+    ///     if let Some(Ok(file)) = ticket.0.try_recv() {
+    ///         info!("Imported {}", file.path.display());
+    ///     }
+    /// }
+    /// ```
+    pub fn add_awaitable(&mut self, handle: Handle<AseAsset>) -> AseLoadTicket {
+        let state = Arc::new(Mutex::new(TicketState::default()));
+        self.handle_tickets
+            .entry(handle.id())
+            .or_default()
+            .push(TicketSender(state.clone()));
+        self.add(handle);
+        AseLoadTicket { state }
+    }
+
+    /// Returns true when no [AseAsset] handles are loading or being processed.
+    pub fn is_loaded(&self) -> bool {
+        self.todo_handles.is_empty() && self.pending_count() == 0
+    }
+
+    /// Returns the load state of a file together with its generated sub-assets.
+    ///
+    /// Bevy's [AssetServer] only tracks the [AseAsset] itself, which finishes loading before
+    /// its sub-assets are generated, and the sub-assets are not known to it as dependencies.
+    /// This combines the [AssetServer]'s state with the [Loader]'s, like a recursive
+    /// dependency load state: [LoadState::Loaded] means that the file's sub-assets are
+    /// in [AseFileMap], and [LoadState::Failed] that the file could not be loaded or imported.
+    /// Files which are loaded but were never added to the [Loader] are [LoadState::NotLoaded].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::asset::LoadState;
+    /// use bevy::prelude::*;
+    /// use bevy_ase::asset::{AseAsset, AseFileMap};
+    /// use bevy_ase::loader::Loader;
+    ///
+    /// fn level_ready(
+    ///     level: &Handle<AseAsset>,
+    ///     loader: Res<Loader>,
+    ///     asset_server: Res<AssetServer>,
+    ///     file_map: Res<AseFileMap>,
+    /// ) -> bool {
+    ///     loader.file_load_state(level, &asset_server, &file_map) == LoadState::Loaded
+    /// }
+    /// ```
+    pub fn file_load_state(
+        &self,
+        handle: &Handle<AseAsset>,
+        asset_server: &AssetServer,
+        file_map: &AseFileMap,
+    ) -> LoadState {
+        let id = handle.id();
+        let server_state = asset_server.get_load_state(id);
+        if server_state == LoadState::Failed {
+            return LoadState::Failed;
+        }
+        if self.todo_handles.iter().any(|todo| todo.id() == id) {
+            return LoadState::Loading;
+        }
+        let is_file = |path: &PathBuf| HandleId::from(AssetPath::new_ref(path, None)) == id;
+        if self.processing.iter().any(&is_file) {
+            return LoadState::Loading;
+        }
+        if self.failed.iter().any(&is_file) {
+            return LoadState::Failed;
+        }
+        if file_map.get_by_handle(handle).is_some() {
+            return LoadState::Loaded;
+        }
+        match server_state {
+            LoadState::Loaded => LoadState::NotLoaded,
+            state => state,
+        }
+    }
+
+    /// Returns the paths of files waiting to be processed, in queue order.
+    ///
+    /// Queued files are only known by their handles, so their paths are looked up in the
+    /// [AssetServer]. Handles which were not loaded from a path are skipped.
+    /// Together with [Loader::processing_paths], this lists every file still pending.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_ase::loader::Loader;
+    ///
+    /// // Lists the files a loading screen is waiting for.
+    /// fn log_pending_files(loader: Res<Loader>, asset_server: Res<AssetServer>) {
+    ///     for path in loader.queued_paths(&asset_server) {
+    ///         info!("Queued: {}", path.display());
+    ///     }
+    ///     for path in loader.processing_paths() {
+    ///         info!("Processing: {}", path.display());
+    ///     }
+    /// }
+    /// ```
+    pub fn queued_paths(&self, asset_server: &AssetServer) -> Vec<PathBuf> {
+        self.todo_handles
+            .iter()
+            .filter_map(|handle| asset_server.get_handle_path(handle))
+            .map(|path| path.path().to_path_buf())
+            .collect()
+    }
+
+    /// Returns the paths of files being processed, in no particular order.
+    ///
+    /// This includes files which finished processing, but are still waiting
+    /// to be moved into resources, like [Loader::pending_count].
+    pub fn processing_paths(&self) -> impl Iterator<Item = &Path> {
+        self.processing.iter().map(PathBuf::as_path)
+    }
+
+    /// Returns a [ScopedLoader], which tracks the files added through it separately.
+    ///
+    /// Scopes are created on first use and identified by name.
+    /// Files in a scope are still processed along with all other files.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_ase::asset::AseAsset;
+    /// use bevy_ase::loader::Loader;
+    ///
+    /// fn load_ui(asset_server: Res<AssetServer>, mut loader: ResMut<Loader>) {
+    ///     let skin: Handle<AseAsset> = asset_server.load("ui/skin.aseprite");
+    ///     loader.scoped("ui").add(skin);
+    /// }
+    ///
+    /// // Only waits for UI files, regardless of other files still loading.
+    /// fn ui_ready(loader: Res<Loader>) -> bool {
+    ///     loader.is_scope_loaded("ui")
+    /// }
+    /// ```
+    pub fn scoped(&mut self, scope: impl Into<String>) -> ScopedLoader<'_> {
+        ScopedLoader {
+            loader: self,
+            scope: scope.into(),
+        }
+    }
+
+    /// Returns true when no files added to the named scope are loading or being processed.
+    ///
+    /// Scopes which were never used are loaded.
+    pub fn is_scope_loaded(&self, scope: &str) -> bool {
+        self.scopes
+            .get(scope)
+            .map_or(true, |scope| scope.pending_count() == 0)
+    }
+
+    /// Adds a group of [AseAsset] handles to the [Loader] for loading, and returns its token.
+    ///
+    /// The group's status is checked with [Loader::is_group_loaded], and its files are
+    /// unloaded with [Loader::unload_group]. This suits level streaming, where groups of
+    /// files are loaded and unloaded continuously.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_ase::asset::AseAsset;
+    /// use bevy_ase::loader::{LoadGroup, Loader};
+    ///
+    /// fn load_chunk(asset_server: &AssetServer, loader: &mut Loader) -> LoadGroup {
+    ///     let files: Vec<Handle<AseAsset>> = vec![
+    ///         asset_server.load("chunks/forest/trees.aseprite"),
+    ///         asset_server.load("chunks/forest/enemies.aseprite"),
+    ///     ];
+    ///     loader.add_group(files)
+    /// }
+    ///
+    /// fn unload_chunk(loader: &mut Loader, chunk: LoadGroup) {
+    ///     if loader.is_group_loaded(chunk) {
+    ///         loader.unload_group(chunk);
+    ///     }
+    /// }
+    /// ```
+    pub fn add_group(&mut self, handles: impl IntoIterator<Item = Handle<AseAsset>>) -> LoadGroup {
+        let token = LoadGroup(self.next_group);
+        self.next_group += 1;
+        let mut group = Group::default();
+        for handle in handles {
+            group.scope.todo.insert(handle.id());
+            group.handles.push(handle.clone());
+            self.add(handle);
+        }
+        self.groups.insert(token, group);
+        token
+    }
+
+    /// Returns true when no files in the group are loading or being processed.
+    ///
+    /// Unloaded groups are not loaded.
+    pub fn is_group_loaded(&self, group: LoadGroup) -> bool {
+        self.groups
+            .get(&group)
+            .map_or(false, |group| group.scope.pending_count() == 0)
+    }
+
+    /// Unloads a group's files, and forgets the group.
+    ///
+    /// On the next run of [ase_importer], the assets of each imported file are removed
+    /// from their resources and from [AseFileMap]. Files which are still loading or being
+    /// processed are not imported. Files which are also part of another group are kept.
+    ///
+    /// Assets can only be found through [AseFileMap], so nothing is removed without it.
+    pub fn unload_group(&mut self, group: LoadGroup) {
+        let Some(group) = self.groups.remove(&group) else {
+            return;
+        };
+        let kept: HashSet<HandleId> = self
+            .groups
+            .values()
+            .flat_map(|group| group.handles.iter().map(Handle::id))
+            .collect();
+        let kept_paths: HashSet<&PathBuf> = self
+            .groups
+            .values()
+            .flat_map(|group| &group.scope.processing)
+            .collect();
+        let cancelled: Vec<PathBuf> = group
+            .scope
+            .processing
+            .iter()
+            .filter(|path| !kept_paths.contains(path))
+            .cloned()
+            .collect();
+        self.cancelled.extend(cancelled);
+        for handle in group.handles {
+            if kept.contains(&handle.id()) {
+                continue;
+            }
+            if group.scope.todo.contains(&handle.id()) {
+                self.todo_handles.retain(|todo| todo.id() != handle.id());
+                self.handle_tickets.remove(&handle.id());
+                for scope in self.scopes.values_mut() {
+                    scope.todo.remove(&handle.id());
+                }
+            } else {
+                self.unloading.push(handle);
+            }
+        }
+    }
+
+    /// Unloads an imported file, removing every asset generated from it.
+    ///
+    /// On the next run of [ase_importer], the file's images, texture atlases, animations,
+    /// tilesets, slices and other sub-assets are removed from their resources, and its
+    /// entry is removed from [AseFileMap]. If the file is still queued or being processed,
+    /// it is dropped instead. Import the file again with [Loader::add].
+    ///
+    /// Assets can only be found through [AseFileMap], so nothing is removed without it.
+    /// Handles to removed assets which are still held elsewhere no longer resolve.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_ase::loader::Loader;
+    ///
+    /// fn leave_level(mut loader: ResMut<Loader>) {
+    ///     loader.unload("levels/forest/tiles.aseprite");
+    ///     loader.unload("levels/forest/enemies.aseprite");
+    /// }
+    /// ```
+    pub fn unload(&mut self, path: impl FilePath) {
+        let path = path.file_path().to_path_buf();
+        if self.processing_bytes.contains_key(&path) {
+            self.cancelled.insert(path.clone());
+        }
+        // Files added but not yet imported are dropped from the queue.
+        let id = HandleId::from(AssetPath::new_ref(&path, None));
+        self.todo_handles.retain(|todo| todo.id() != id);
+        self.waiting.remove(&id);
+        self.handle_tickets.remove(&id);
+        for scope in self.scopes_mut() {
+            scope.todo.remove(&id);
+        }
+        self.unloading_paths.push(path);
+    }
+
+    // Removes the assets of unloaded files.
+    fn unload_files(&mut self, aseassets: &Assets<AseAsset>, resources: &mut AseAssetResources) {
+        for handle in self.unloading.drain(..) {
+            if let Some(ase_asset) = aseassets.get(&handle) {
+                processing::remove_from_resources(&ase_asset.name, resources);
+            }
+        }
+        for path in self.unloading_paths.drain(..) {
+            processing::remove_from_resources(&path, resources);
+        }
+    }
+
+    // All scopes, including each group's.
+    fn scopes_mut(&mut self) -> impl Iterator<Item = &mut Scope> {
+        let groups = self.groups.values_mut().map(|group| &mut group.scope);
+        self.scopes.values_mut().chain(groups)
+    }
+
+    // Retries or drops todo handles which failed or timed out while loading.
+    fn check_stuck_loads(
+        &mut self,
+        asset_server: &AssetServer,
+        aseassets: &Assets<AseAsset>,
+        errors: &mut Option<ResMut<Events<AseImportError>>>,
+    ) {
+        let now = Instant::now();
+        let mut dropped = Vec::new();
+        for handle in &self.todo_handles {
+            let id = handle.id();
+            if aseassets.contains(id) {
+                self.waiting.remove(&id);
+                continue;
+            }
+            let (since, attempts) = self.waiting.entry(id).or_insert((now, 0));
+            let timed_out = match self.load_policy.timeout {
+                Some(timeout) => now.duration_since(*since) >= timeout,
+                None => false,
+            };
+            let failed = asset_server.get_load_state(id) == LoadState::Failed;
+            if !failed && !timed_out {
+                continue;
+            }
+            let path = asset_server.get_handle_path(id);
+            let path = path.map(|path| path.path().to_path_buf());
+            if *attempts < self.load_policy.retries {
+                if let Some(path) = path {
+                    debug!("Retrying to load {}", path.display());
+                    asset_server.reload_asset(path.as_path());
+                }
+                *since = now;
+                *attempts += 1;
+                continue;
+            }
+            let error = if timed_out {
+                ImportError::LoadTimedOut
+            } else {
+                ImportError::LoadFailed
+            };
+            dropped.push((id, path.unwrap_or_default(), error));
+        }
+        for (id, path, error) in dropped {
+            self.todo_handles.retain(|handle| handle.id() != id);
+            self.drop_handle(id, path, error, errors);
+        }
+    }
+
+    // Reports a todo handle which can't be imported, after it was removed from `todo_handles`.
+    fn drop_handle(
+        &mut self,
+        id: HandleId,
+        path: PathBuf,
+        error: ImportError,
+        errors: &mut Option<ResMut<Events<AseImportError>>>,
+    ) {
+        error!("Failed to import {}: {}", path.display(), error);
+        self.waiting.remove(&id);
+        for scope in self.scopes_mut() {
+            scope.todo.remove(&id);
+        }
+        for ticket in self.handle_tickets.remove(&id).into_iter().flatten() {
+            ticket.send(Err(error.clone()));
+        }
+        if let Some(errors) = errors.as_mut() {
+            errors.send(AseImportError { path, error });
+        }
+    }
+
+    fn all_todo_handles_ready(
+        &self,
+        asset_server: &AssetServer,
+        aseassets: &Assets<AseAsset>,
+    ) -> bool {
+        // Assets already present are ready, including embedded files which
+        // the asset server never loaded.
+        let handles = self
+            .todo_handles
+            .iter()
+            .map(|h| h.id())
+            .filter(|id| !aseassets.contains(*id));
+        asset_server.get_group_load_state(handles) == LoadState::Loaded
+    }
+
+    fn spawn_tasks(
+        &mut self,
+        asset_server: &AssetServer,
+        aseprites: &mut Assets<AseAsset>,
+        errors: &mut Option<ResMut<Events<AseImportError>>>,
+    ) {
+        if self.todo_handles.is_empty() {
+            return;
+        }
+
+        // Files over the processing limits stay queued, in order.
+        let mut handles = std::mem::take(&mut self.todo_handles);
+        let mut files = self.pending_count() as usize;
+        let mut bytes: u64 = self.processing_bytes.values().sum();
+        let over_limits = handles.iter().position(|h| {
+            let Some(file) = aseprites.get(h).and_then(AseAsset::file) else {
+                return false;
+            };
+            let size = decoded_size(file);
+            if files > 0 && !self.processing_limits.allows(files + 1, bytes + size) {
+                return true;
+            }
+            files += 1;
+            bytes += size;
+            false
+        });
+        if let Some(index) = over_limits {
+            self.todo_handles = handles.split_off(index);
+        }
+        let todo_handles = &self.todo_handles;
+        self.waiting
+            .retain(|id, _| todo_handles.iter().any(|h| h.id() == *id));
+
+        let mut ase_files: Vec<(PathBuf, AsepriteFile, FileHeader, Vec<Palette>)> =
+            Vec::with_capacity(handles.len());
+        for h in &handles {
+            // The asset may have been removed since it finished loading.
+            let Some(ase_asset) = aseprites.get(&h.clone_weak()) else {
+                let path = asset_server.get_handle_path(h.id());
+                let path = path.map(|path| path.path().to_path_buf());
+                self.drop_handle(
+                    h.id(),
+                    path.unwrap_or_default(),
+                    ImportError::AssetRemoved,
+                    errors,
+                );
+                continue;
+            };
+            let name = ase_asset.name.clone();
+            let processing = ase_asset.file().is_some();
+            if processing {
+                self.processing.insert(name.clone());
+            }
+            for scope in self.scopes_mut() {
+                if scope.todo.remove(&h.id()) && processing {
+                    scope.processing.insert(name.clone());
+                }
             }
+            if let Some(tickets) = self.handle_tickets.remove(&h.id()) {
+                if !processing {
+                    self.imported.push(name.clone());
+                }
+                self.path_tickets
+                    .entry(name.clone())
+                    .or_default()
+                    .extend(tickets);
+            }
+            if !processing {
+                continue;
+            }
+
+            // We actually remove the AsepriteFile from the AsepriteAsset so
+            // the memory can be freed after we're done processing. If the file
+            // was changed we get the new data from the asset loader.
+            // Processed assets are left alone, since get_mut sends AssetEvent::Modified.
+            let Some(ase_asset) = aseprites.get_mut(&h.clone_weak()) else {
+                continue;
+            };
+            let AseData::Loaded(boxed_ase) =
+                std::mem::replace(&mut ase_asset.data, AseData::Processed)
+            else {
+                continue;
+            };
+            self.processing_bytes
+                .insert(name.clone(), decoded_size(&boxed_ase));
+            let header = std::mem::take(&mut ase_asset.header);
+            let palettes = std::mem::take(&mut ase_asset.palettes);
+            ase_files.push((name, *boxed_ase, header, palettes));
+        }
+
+        if ase_files.is_empty() {
+            return;
+        }
+        for (path, file, _, _) in &ase_files {
+            if let Some(image) = self.placeholder.image(file) {
+                let frame_count = file.num_frames();
+                self.pending_placeholders
+                    .push((path.clone(), frame_count, image));
+            }
+        }
+        self.in_progress
+            .fetch_add(ase_files.len() as u32, Ordering::SeqCst);
+
+        let output = self.done.clone();
+        let settings = self.settings.clone();
+        let task = async move {
+            let processed = ResourceDataByFile::new(ase_files, &settings);
+            // Processing panics are caught per file, so the lock is not poisoned by them.
+            let mut out = output.lock().unwrap_or_else(PoisonError::into_inner);
+            out.push(processed);
+        };
+        self.task_pool().spawn(task).detach();
+    }
+
+    // Returns the pool to process files on, creating a dedicated pool if needed.
+    fn task_pool(&mut self) -> &TaskPool {
+        match self.processing_pool {
+            ProcessingPool::AsyncCompute => AsyncComputeTaskPool::get(),
+            ProcessingPool::Io => IoTaskPool::get(),
+            ProcessingPool::Dedicated { threads } => self.dedicated_pool.get_or_insert_with(|| {
+                TaskPoolBuilder::new()
+                    .num_threads(threads.max(1))
+                    .thread_name("bevy_ase processing".to_string())
+                    .build()
+            }),
+        }
+    }
+
+    // Inserts placeholders under the handle ids of files which started processing.
+    fn insert_placeholders(&mut self, resources: &mut AseAssetResources) {
+        let (textures, _, atlases, ..) = resources;
+        let Some(images) = textures.as_deref_mut() else {
+            self.pending_placeholders.clear();
+            return;
+        };
+        let settings = &self.settings;
+        for (path, frame_count, image) in self.pending_placeholders.drain(..) {
+            let size = Vec2::new(image.width as f32, image.height as f32);
+            if settings.frame_images && settings.keep_frame_images {
+                if let Ok(frame_image) = image.clone().into_image(settings.formats.frames) {
+                    for frame in 0..frame_count {
+                        let id = handle_id::frame_image(&path, frame);
+                        images.set_untracked(id, frame_image.clone());
+                    }
+                }
+            }
+            let Some(atlases) = atlases.as_deref_mut().filter(|_| settings.atlas) else {
+                continue;
+            };
+            let Ok(atlas_image) = image.into_image(settings.formats.atlas) else {
+                continue;
+            };
+            let mut atlas = TextureAtlas::new_empty(images.add(atlas_image), size);
+            for _ in 0..frame_count {
+                atlas.add_texture(Rect::from_corners(Vec2::ZERO, size));
+            }
+            atlases.set_untracked(handle_id::atlas(&path), atlas);
+        }
+    }
+
+    fn take_finished(&mut self) {
+        let mut data = match self.done.try_lock() {
+            Ok(data) => data,
+            Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+            Err(TryLockError::WouldBlock) => return,
+        };
+        for batch in data.drain(..) {
+            self.ready.extend(batch.into_files());
+        }
+    }
+
+    fn move_finished_into_resources(
+        &mut self,
+        resources: &mut AseAssetResources,
+        events: &mut ImportEvents,
+    ) {
+        let (
+            errors,
+            changes,
+            files_imported,
+            profile_warnings,
+            compatibility_warnings,
+            sub_assets,
+            tile_warnings,
+        ) = events;
+        for path in std::mem::take(&mut self.imported) {
+            self.resolve_tickets(&path, Ok(()), resources.8.as_deref());
+        }
+        self.take_finished();
+        let start = Instant::now();
+        let max_files = self.budget.max_files_per_frame.unwrap_or(usize::MAX).max(1);
+        for _ in 0..max_files {
+            let Some((path, result)) = self.ready.pop_front() else {
+                break;
+            };
+            self.processing.remove(&path);
+            self.processing_bytes.remove(&path);
+            for scope in self.scopes_mut() {
+                scope.processing.remove(&path);
+            }
+            if self.cancelled.remove(&path) {
+                self.path_tickets.remove(&path);
+                self.in_progress.fetch_sub(1, Ordering::SeqCst);
+                continue;
+            }
+            match result {
+                Ok(data) => {
+                    if let Some(profile) = data.color_profile_warning {
+                        warn!(
+                            "{} has a {:?} color profile; its colors may differ from Aseprite",
+                            path.display(),
+                            profile
+                        );
+                        if let Some(profile_warnings) = profile_warnings.as_mut() {
+                            profile_warnings.send(AseColorProfileWarning {
+                                path: path.clone(),
+                                profile,
+                            });
+                        }
+                    }
+                    if !data.unsupported.is_empty() {
+                        warn!(
+                            "{} was only partially imported: {}",
+                            path.display(),
+                            data.unsupported.join("; ")
+                        );
+                        if let Some(compatibility_warnings) = compatibility_warnings.as_mut() {
+                            compatibility_warnings.send(AseCompatibilityWarning {
+                                path: path.clone(),
+                                unsupported: data.unsupported.clone(),
+                            });
+                        }
+                    }
+                    if let (Some(images), Some(tilesets)) =
+                        (resources.0.as_deref(), resources.3.as_deref())
+                    {
+                        for warning in data.tile_index_warnings(&path, images, tilesets) {
+                            warn!(
+                                "{} moved or removed tiles of tileset {}; \
+                                saved levels may show the wrong tiles",
+                                path.display(),
+                                warning.tileset_id
+                            );
+                            if let Some(tile_warnings) = tile_warnings.as_mut() {
+                                tile_warnings.send(warning);
+                            }
+                        }
+                    }
+                    data.move_into_resources(path.clone(), resources);
+                    if let (Some(sub_assets), Some(file_map)) =
+                        (sub_assets.as_mut(), resources.8.as_deref())
+                    {
+                        sub_assets.extend(AseSubAssetCreated::for_file(&path, file_map));
+                    }
+                    if let (Some(files_imported), Some(asset_map)) = (
+                        files_imported.as_mut(),
+                        resources
+                            .8
+                            .as_deref()
+                            .and_then(|file_map| file_map.get(path.as_path())),
+                    ) {
+                        files_imported.send(AseFileImported::new(&path, asset_map));
+                    }
+                    self.failed.remove(&path);
+                    self.resolve_tickets(&path, Ok(()), resources.8.as_deref());
+                    if let Some(changes) = changes.as_mut() {
+                        changes.send(AseFileMapChanged { path });
+                    }
+                }
+                Err(error) => {
+                    error!("Failed to import {}: {}", path.display(), error);
+                    self.failed.insert(path.clone());
+                    self.resolve_tickets(&path, Err(error.clone()), None);
+                    if let Some(errors) = errors.as_mut() {
+                        errors.send(AseImportError { path, error });
+                    }
+                }
+            }
+            self.in_progress.fetch_sub(1, Ordering::SeqCst);
+            if let Some(max_time) = self.budget.max_time_per_frame {
+                if start.elapsed() >= max_time {
+                    break;
+                }
+            }
+        }
+    }
+
+    // Sends the result of importing a file to its tickets.
+    fn resolve_tickets(
+        &mut self,
+        path: &Path,
+        result: ImportResult<()>,
+        file_map: Option<&AseFileMap>,
+    ) {
+        let Some(tickets) = self.path_tickets.remove(path) else {
+            return;
+        };
+        let result = result.map(|()| LoadedFile::new(path, file_map));
+        for ticket in tickets {
+            ticket.send(result.clone());
+        }
+    }
+}
+
+/// Describes why an Aseprite file could not be imported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportError {
+    /// The file exceeds the configured [ImportLimits](crate::process::ImportLimits).
+    LimitExceeded(LimitExceeded),
+    /// A configured [ImageFormats](crate::process::ImageFormats) format is not supported.
+    UnsupportedFormat(UnsupportedFormat),
+    /// The [AssetServer] failed to load the file, including any retries
+    /// allowed by the [LoadPolicy].
+    LoadFailed,
+    /// The file did not finish loading within the [LoadPolicy]'s timeout,
+    /// including any retries.
+    LoadTimedOut,
+    /// The file's [AseAsset] was removed after it loaded, before it could be processed.
+    AssetRemoved,
+    /// Processing the file panicked, with the given message.
+    ProcessingPanicked(String),
+    /// The import was cancelled before the file was imported, e.g. because its
+    /// [LoadGroup] was unloaded. Only reported by [AseLoadTicket]s.
+    Cancelled,
+}
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::LimitExceeded(e) => write!(f, "{}", e),
+            ImportError::UnsupportedFormat(e) => write!(f, "{}", e),
+            ImportError::LoadFailed => write!(f, "the asset server failed to load the file"),
+            ImportError::LoadTimedOut => write!(f, "the file did not finish loading in time"),
+            ImportError::AssetRemoved => write!(f, "the file was removed before processing"),
+            ImportError::ProcessingPanicked(message) => {
+                write!(f, "processing panicked: {}", message)
+            }
+            ImportError::Cancelled => write!(f, "the import was cancelled"),
+        }
+    }
+}
+impl std::error::Error for ImportError {}
+impl From<LimitExceeded> for ImportError {
+    fn from(e: LimitExceeded) -> Self {
+        Self::LimitExceeded(e)
+    }
+}
+impl From<UnsupportedFormat> for ImportError {
+    fn from(e: UnsupportedFormat) -> Self {
+        Self::UnsupportedFormat(e)
+    }
+}
+
+pub(crate) type ImportResult<T> = std::result::Result<T, ImportError>;
+
+/// Event sent by [ase_importer] when an Aseprite file could not be imported.
+///
+/// No assets are created for the file. The [AseLoaderDefaultPlugin] registers this event.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::loader::AseImportError;
+///
+/// fn report_import_errors(mut events: EventReader<AseImportError>) {
+///     for event in events.iter() {
+///         warn!("Could not import {}: {}", event.path.display(), event.error);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AseImportError {
+    /// Path of the Aseprite file.
+    pub path: PathBuf,
+    /// Why the file could not be imported.
+    pub error: ImportError,
+}
+
+/// Event sent by [ase_importer] when a file's entries in [AseFileMap] were inserted or updated.
+///
+/// Files are updated when they are imported again, e.g. after hot reloading.
+/// Caches built from [AseFileMap] can use this event to invalidate entries for the file.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::loader::AseFileMapChanged;
+///
+/// fn log_changes(mut events: EventReader<AseFileMapChanged>) {
+///     for event in events.iter() {
+///         info!("Assets changed for {}", event.path.display());
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AseFileMapChanged {
+    /// Path of the Aseprite file.
+    pub path: PathBuf,
+}
+
+/// Event sent by [ase_importer] when a file's data was moved into resources.
+///
+/// Holds handles to the file's main assets, so systems can spawn entities as soon as a file
+/// is imported instead of polling [Loader::is_loaded]. Other assets can be looked up in
+/// [AseFileMap]. Requires the [AseFileMap] resource; it is sent again when the file is reimported.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::loader::AseFileImported;
+///
+/// fn spawn_idle_sprites(mut commands: Commands, mut events: EventReader<AseFileImported>) {
+///     for event in events.iter() {
+///         let Some(atlas) = event.atlas.clone() else {
+///             continue;
+///         };
+///         if event.animations.iter().any(|(tag_name, _)| tag_name == "idle") {
+///             commands.spawn(SpriteSheetBundle {
+///                 texture_atlas: atlas,
+///                 ..default()
+///             });
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AseFileImported {
+    /// Path of the Aseprite file.
+    pub path: PathBuf,
+    /// Handle of the file's texture atlas, if one was packed.
+    pub atlas: Option<Handle<TextureAtlas>>,
+    /// Tag name and handle of each animation, sorted by tag name.
+    pub animations: Vec<(String, Handle<Animation>)>,
+    /// Id and handle of each tileset, sorted by id.
+    pub tilesets: Vec<(u32, Handle<Tileset>)>,
+    /// Name and handle of each slice, sorted by name.
+    pub slices: Vec<(String, Handle<Slice>)>,
+}
+
+impl AseFileImported {
+    fn new(path: &Path, asset_map: &AseAssetMap) -> Self {
+        let mut animations: Vec<_> = asset_map
+            .animations
+            .iter()
+            .map(|(tag_name, handle)| (tag_name.clone(), handle.clone()))
+            .collect();
+        animations.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut tilesets: Vec<_> = asset_map
+            .tilesets
+            .iter()
+            .map(|(id, handle)| (*id, handle.clone()))
+            .collect();
+        tilesets.sort_by_key(|(id, _)| *id);
+        let mut slices: Vec<_> = asset_map
+            .slices
+            .iter()
+            .map(|(name, handle)| (name.clone(), handle.clone()))
+            .collect();
+        slices.sort_by(|a, b| a.0.cmp(&b.0));
+        let has_atlas = asset_map.atlas.id() != HandleId::default::<TextureAtlas>();
+        Self {
+            path: path.to_path_buf(),
+            atlas: has_atlas.then(|| asset_map.atlas.clone()),
+            animations,
+            tilesets,
+            slices,
         }
     }
 }
 
+/// Event sent by [ase_importer] for each sub-asset of an imported file.
+///
+/// Systems building derived data, such as collision caches, animation registries or tilemap
+/// spawners, can update just the assets which were created, instead of rescanning [Assets].
+/// Files which are imported again send events for all of their sub-assets again.
+/// The events of a file are sent before its [AseFileMapChanged] event.
+/// Sub-assets are found through [AseFileMap], so no events are sent without it.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::asset::FrameCollision;
+/// use bevy_ase::handle_id::LabelKind;
+/// use bevy_ase::loader::AseSubAssetCreated;
+///
+/// fn cache_collisions(mut events: EventReader<AseSubAssetCreated>) {
+///     for event in events.iter() {
+///         if event.kind == LabelKind::FrameCollision {
+///             let collision: Handle<FrameCollision> = event.handle.clone().typed();
+///             info!("New collision {} in {}", event.label, event.path.display());
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct AseSubAssetCreated {
+    /// Path of the Aseprite file.
+    pub path: PathBuf,
+    /// Label of the sub-asset, as documented in [handle_id](crate::handle_id).
+    pub label: String,
+    /// Kind of the sub-asset's label.
+    pub kind: LabelKind,
+    /// Handle of the sub-asset.
+    pub handle: HandleUntyped,
+}
+
+impl AseSubAssetCreated {
+    // Returns an event for each sub-asset of a file in the file map.
+    fn for_file(path: &Path, file_map: &AseFileMap) -> Vec<Self> {
+        file_map
+            .labels(path)
+            .into_iter()
+            .filter_map(|label| {
+                let kind = LabelKind::parse(&label)?;
+                let handle = file_map.labeled(AssetPath::new_ref(path, Some(&label)))?;
+                Some(Self {
+                    path: path.to_path_buf(),
+                    label,
+                    kind,
+                    handle,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Event sent by [ase_importer] when an imported file has a color profile other than sRGB.
+///
+/// The file's colors are imported as they are stored, so they may look different in-game
+/// than in Aseprite. Set [ProcessSettings::color_profile](crate::process::ProcessSettings::color_profile)
+/// to [ColorProfileHandling::Ignore](crate::process::ColorProfileHandling::Ignore) to disable this event.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::loader::AseColorProfileWarning;
+///
+/// fn report_profiles(mut events: EventReader<AseColorProfileWarning>) {
+///     for event in events.iter() {
+///         warn!("Convert {} to sRGB in Aseprite", event.path.display());
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AseColorProfileWarning {
+    /// Path of the Aseprite file.
+    pub path: PathBuf,
+    /// The file's color profile.
+    pub profile: ColorProfile,
+}
+
+/// Event sent by [ase_importer] when some of a file's contents could not be imported.
+///
+/// The rest of the file is imported as usual, and its [AseAssetMap](crate::asset::AseAssetMap)
+/// is marked as [partial](crate::asset::AseAssetMap::is_partial). This happens e.g. for tilemap
+/// data written by a newer Aseprite version than the one supported by this crate.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::loader::AseCompatibilityWarning;
+///
+/// fn report_partial_imports(mut events: EventReader<AseCompatibilityWarning>) {
+///     for event in events.iter() {
+///         warn!("{} skipped: {:?}", event.path.display(), event.unsupported);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AseCompatibilityWarning {
+    /// Path of the Aseprite file.
+    pub path: PathBuf,
+    /// Descriptions of the contents which could not be imported.
+    pub unsupported: Vec<String>,
+}
+
+/// Event sent by [ase_importer] when a reimported tileset's tiles changed their indices.
+///
+/// Tilemaps and saved level data refer to tiles by index, so they show the wrong tiles after
+/// tiles were moved or removed. Adding tiles at the end of a tileset and editing tiles in place
+/// keep every index. Tiles are matched by their pixels, so empty tiles are never reported as moved.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::loader::AseTileIndexWarning;
+///
+/// fn report_reordered_tiles(mut events: EventReader<AseTileIndexWarning>) {
+///     for event in events.iter() {
+///         for (old, new) in &event.moved {
+///             warn!("{}: tile {} is now tile {}", event.path.display(), old, new);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AseTileIndexWarning {
+    /// Path of the Aseprite file.
+    pub path: PathBuf,
+    /// Id of the tileset.
+    pub tileset_id: u32,
+    /// The previous and current index of each tile which moved.
+    pub moved: Vec<(u32, u32)>,
+    /// The previous indices of tiles past the end of the reimported tileset.
+    pub removed: Vec<u32>,
+}
+
+// Tuple of all event types sent while moving data into resources.
+type ImportEvents<'a> = (
+    Option<ResMut<'a, Events<AseImportError>>>,
+    Option<ResMut<'a, Events<AseFileMapChanged>>>,
+    Option<ResMut<'a, Events<AseFileImported>>>,
+    Option<ResMut<'a, Events<AseColorProfileWarning>>>,
+    Option<ResMut<'a, Events<AseCompatibilityWarning>>>,
+    Option<ResMut<'a, Events<AseSubAssetCreated>>>,
+    Option<ResMut<'a, Events<AseTileIndexWarning>>>,
+);
+
 // Tuple of all resource types to move data into.
 pub(crate) type AseAssetResources<'a> = (
-    ResMut<'a, Assets<Image>>,
+    Option<ResMut<'a, Assets<Image>>>,
     Option<ResMut<'a, Assets<Animation>>>,
     Option<ResMut<'a, Assets<TextureAtlas>>>,
     Option<ResMut<'a, Assets<Tileset>>>,
     Option<ResMut<'a, Assets<Slice>>>,
+    Option<ResMut<'a, Assets<AnimationEvents>>>,
+    Option<ResMut<'a, Assets<FrameCollision>>>,
+    Option<ResMut<'a, Assets<UiTextPlaceholder>>>,
     Option<ResMut<'a, AseFileMap>>,
 );
 
 /// System function for moving loaded Aseprite assets into Resoures.
 ///
+/// Imports the files queued with [Loader::add], and every loaded file when
+/// [Loader::set_auto_import] is set.
+///
 /// # Examples
 ///
 /// ```
@@ -290,18 +2100,53 @@ pub(crate) type AseAssetResources<'a> = (
 /// ```
 pub fn ase_importer(
     mut loader: ResMut<Loader>,
-    // task_pool: ResMut<AsyncComputeTaskPool>,
     mut aseassets: ResMut<Assets<AseAsset>>,
     asset_server: Res<AssetServer>,
-    resources: AseAssetResources,
+    mut asset_events: EventReader<AssetEvent<AseAsset>>,
+    mut resources: AseAssetResources,
+    mut events: ImportEvents,
 ) {
-    let task_pool = AsyncComputeTaskPool::get();
+    loader.queue_loaded_files(&asset_server, &aseassets, asset_events.iter());
     let pending = loader.pending_count();
     if pending > 0 {
-        debug!("Processing asefiles (batches: {})", pending);
+        debug!("Processing asefiles (files: {})", pending);
+    }
+    loader.check_stuck_loads(&asset_server, &aseassets, &mut events.0);
+    if loader.all_todo_handles_ready(&asset_server, &aseassets) {
+        loader.spawn_tasks(&asset_server, &mut aseassets, &mut events.0);
+        loader.insert_placeholders(&mut resources);
     }
-    if loader.all_todo_handles_ready(&asset_server) {
-        loader.spawn_tasks(&task_pool, &mut aseassets);
+    loader.unload_files(&aseassets, &mut resources);
+    loader.move_finished_into_resources(&mut resources, &mut events);
+}
+
+/// System function which creates frame images requested from [AseFileMap].
+///
+/// Frame images are only created lazily when files are processed with
+/// [ProcessSettings::keep_frame_images] set to false. Each requested image is
+/// copied out of its file's texture atlas and inserted under its regular handle id.
+/// This system is already added by default in [AseLoaderDefaultPlugin].
+pub fn generate_lazy_frame_images(
+    file_map: Option<ResMut<AseFileMap>>,
+    images: Option<ResMut<Assets<Image>>>,
+    atlases: Option<Res<Assets<TextureAtlas>>>,
+) {
+    let (Some(mut file_map), Some(mut images), Some(atlases)) = (file_map, images, atlases) else {
+        return;
+    };
+    for (path, asset_map) in file_map.0.iter_mut() {
+        let requests = asset_map.take_lazy_requests();
+        if requests.is_empty() {
+            continue;
+        }
+        let Some(atlas) = atlases.get(&asset_map.atlas) else {
+            continue;
+        };
+        for frame in requests {
+            if let Some(image) = processing::frame_image_from_atlas(atlas, &images, frame) {
+                let handle = images.set(handle_id::frame_image(path, frame), image);
+                asset_map.insert_texture(frame, handle);
+            }
+        }
     }
-    loader.move_finished_into_resources(resources);
 }
@@ -0,0 +1,219 @@
+use crate::asset::AseAsset;
+use crate::loader::{LoadGroup, Loader};
+use bevy::{
+    asset::{AssetLoader, BoxedFuture, HandleId, LoadContext, LoadedAsset},
+    prelude::*,
+    reflect::TypeUuid,
+    utils::HashMap,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::PathBuf};
+
+/// A list of Aseprite files for the game to import, curated by artists.
+///
+/// Manifests are written by the Aseprite script `scripts/export_manifest.lua`, or by hand,
+/// as RON (`*.ase.ron`) or JSON (`*.ase.json`). Loading a manifest through the
+/// [AssetServer] with [AseManifestPlugin] added queues each listed file on the [Loader].
+/// The files are kept loaded while the manifest is, and follow it when it is hot reloaded.
+///
+/// The listed tags and tag settings are read by the game; the importer always imports
+/// whole files.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_ase::manifest::Manifest;
+///
+/// let manifest = Manifest::from_ron(
+///     r#"(
+///         files: [
+///             (
+///                 path: "sprites/hero.aseprite",
+///                 tags: ["idle", "walk"],
+///                 tag_settings: { "walk": (speed: Some(1.5)) },
+///             ),
+///             (path: "sprites/tiles.aseprite"),
+///         ],
+///     )"#,
+/// )
+/// .unwrap();
+/// assert!(manifest.exports_tag("sprites/hero.aseprite", "walk"));
+/// assert!(!manifest.exports_tag("sprites/hero.aseprite", "debug"));
+/// assert!(manifest.exports_tag("sprites/tiles.aseprite", "water"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TypeUuid)]
+#[uuid = "5f0c3a27-9d41-4e8b-b6a2-7c1e0d93f4a8"]
+pub struct Manifest {
+    /// The listed files, in order.
+    pub files: Vec<ManifestFile>,
+}
+
+/// A file listed in a [Manifest].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ManifestFile {
+    /// Path of the Aseprite file, relative to the asset folder.
+    pub path: PathBuf,
+    /// Names of the tags to export. Empty to export every tag.
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Settings for individual tags, by tag name.
+    #[serde(default)]
+    pub tag_settings: BTreeMap<String, ManifestTagSettings>,
+}
+
+/// Settings for one tag of a [ManifestFile].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ManifestTagSettings {
+    /// Number of times the animation repeats, overriding the tag's repeat count.
+    #[serde(default)]
+    pub repeat: Option<u32>,
+    /// Playback speed multiplier, overriding the tag's `speed` user data.
+    #[serde(default)]
+    pub speed: Option<f32>,
+}
+
+impl Manifest {
+    /// Parses a manifest from RON.
+    pub fn from_ron(text: &str) -> Result<Self, ron::error::SpannedError> {
+        ron::from_str(text)
+    }
+    /// Parses a manifest from JSON.
+    pub fn from_json(text: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(text)
+    }
+    /// Serializes this manifest as pretty-printed RON.
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+    }
+    /// Serializes this manifest as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+    /// Returns the entry of the file with the given path.
+    pub fn file(&self, path: impl Into<PathBuf>) -> Option<&ManifestFile> {
+        let path = path.into();
+        self.files.iter().find(|file| file.path == path)
+    }
+    /// Returns true if the file is listed and the tag is exported.
+    pub fn exports_tag(&self, path: impl Into<PathBuf>, tag_name: &str) -> bool {
+        self.file(path)
+            .map_or(false, |file| file.exports_tag(tag_name))
+    }
+}
+
+impl ManifestFile {
+    /// Returns true if the tag is exported.
+    pub fn exports_tag(&self, tag_name: &str) -> bool {
+        self.tags.is_empty() || self.tags.iter().any(|tag| tag == tag_name)
+    }
+    /// Returns the settings of the tag, if it has any.
+    pub fn tag_settings(&self, tag_name: &str) -> Option<&ManifestTagSettings> {
+        self.tag_settings.get(tag_name)
+    }
+}
+
+/// Loads [Manifest] assets from `*.ase.ron` and `*.ase.json` files.
+#[derive(Debug, Default)]
+pub struct ManifestLoader;
+
+impl AssetLoader for ManifestLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let text = std::str::from_utf8(bytes)?;
+            let is_json = load_context
+                .path()
+                .extension()
+                .map_or(false, |extension| extension.eq_ignore_ascii_case("json"));
+            let manifest = if is_json {
+                Manifest::from_json(text)?
+            } else {
+                Manifest::from_ron(text)?
+            };
+            load_context.set_default_asset(LoadedAsset::new(manifest));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ase.ron", "ase.json"]
+    }
+}
+
+/// Plugin which loads [Manifest] assets and queues their files on the [Loader].
+///
+/// Requires the [Loader] resource, e.g. from
+/// [AseLoaderDefaultPlugin](crate::loader::AseLoaderDefaultPlugin).
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::loader::AseLoaderDefaultPlugin;
+/// use bevy_ase::manifest::{AseManifestPlugin, Manifest};
+///
+/// #[derive(Resource)]
+/// struct GameManifest(Handle<Manifest>);
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins(DefaultPlugins)
+///         .add_plugin(AseLoaderDefaultPlugin)
+///         .add_plugin(AseManifestPlugin)
+///         .add_startup_system(load_manifest);
+/// }
+///
+/// // Keep the manifest's handle to keep its files loaded.
+/// fn load_manifest(mut commands: Commands, asset_server: Res<AssetServer>) {
+///     commands.insert_resource(GameManifest(asset_server.load("game.ase.ron")));
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct AseManifestPlugin;
+
+impl Plugin for AseManifestPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<Manifest>()
+            .init_asset_loader::<ManifestLoader>()
+            .add_system(queue_manifest_files);
+    }
+}
+
+/// System function which queues the files of loaded [Manifest]s on the [Loader].
+///
+/// Each manifest's files are added as a [LoadGroup]. When a manifest is modified,
+/// its new files are added before the old group is unloaded, so files listed in both
+/// stay imported. When a manifest is removed, its files are unloaded.
+///
+/// This system is already added by [AseManifestPlugin].
+pub fn queue_manifest_files(
+    mut events: EventReader<AssetEvent<Manifest>>,
+    manifests: Res<Assets<Manifest>>,
+    asset_server: Res<AssetServer>,
+    mut loader: ResMut<Loader>,
+    mut groups: Local<HashMap<HandleId, LoadGroup>>,
+) {
+    for event in events.iter() {
+        let (handle, removed) = match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => (handle, false),
+            AssetEvent::Removed { handle } => (handle, true),
+        };
+        let new_group = manifests.get(handle).filter(|_| !removed).map(|manifest| {
+            let files = manifest
+                .files
+                .iter()
+                .map(|file| asset_server.load::<AseAsset, _>(file.path.as_path()));
+            loader.add_group(files)
+        });
+        let old_group = match new_group {
+            Some(group) => groups.insert(handle.id(), group),
+            None => groups.remove(&handle.id()),
+        };
+        if let Some(group) = old_group {
+            loader.unload_group(group);
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use crate::asset::{AseAssetMap, AseFileMap, Tileset};
+use bevy::{prelude::*, sprite::TextureAtlas};
+use std::{fmt, path::PathBuf};
+
+/// Approximate memory used by the assets of one imported file, in bytes.
+///
+/// Returned in a [MemoryReport]. Only image data is counted; it dominates the size of imported files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileMemory {
+    /// Path of the file.
+    pub path: PathBuf,
+    /// Bytes of frame images, layer images and the thumbnail.
+    pub image_bytes: usize,
+    /// Bytes of atlas textures, including downscaled, recolored, outline, shadow and per-tag atlases.
+    pub atlas_bytes: usize,
+    /// Bytes of tileset textures.
+    pub tileset_bytes: usize,
+}
+impl FileMemory {
+    /// Returns the total bytes used by the file's assets.
+    pub fn total_bytes(&self) -> usize {
+        self.image_bytes + self.atlas_bytes + self.tileset_bytes
+    }
+}
+
+/// Approximate memory used by imported Aseprite files. Returned by [memory_report].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryReport {
+    /// Memory used by each file, largest first.
+    pub files: Vec<FileMemory>,
+}
+impl MemoryReport {
+    /// Returns the total bytes used by all files.
+    pub fn total_bytes(&self) -> usize {
+        self.files.iter().map(FileMemory::total_bytes).sum()
+    }
+}
+impl fmt::Display for MemoryReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} bytes in {} files",
+            self.total_bytes(),
+            self.files.len()
+        )?;
+        for file in &self.files {
+            writeln!(
+                f,
+                "  {}: {} bytes ({} images, {} atlases, {} tilesets)",
+                file.path.display(),
+                file.total_bytes(),
+                file.image_bytes,
+                file.atlas_bytes,
+                file.tileset_bytes
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the approximate memory used by each imported file, to find the files to optimize.
+///
+/// Image data is read from the world's [Assets]. Missing asset resources count as empty,
+/// and an empty report is returned if the world has no [AseFileMap].
+/// Images shared between files, e.g. replaced with the same handle, are counted for each file.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+///
+/// fn log_memory(world: &World) {
+///     let report = bevy_ase::memory_report(world);
+///     if let Some(largest) = report.files.first() {
+///         info!("{} uses {} bytes", largest.path.display(), largest.total_bytes());
+///     }
+/// }
+/// ```
+pub fn memory_report(world: &World) -> MemoryReport {
+    let Some(file_map) = world.get_resource::<AseFileMap>() else {
+        return MemoryReport::default();
+    };
+    let images = world.get_resource::<Assets<Image>>();
+    let atlases = world.get_resource::<Assets<TextureAtlas>>();
+    let tilesets = world.get_resource::<Assets<Tileset>>();
+    let image_bytes = |handle: &Handle<Image>| {
+        images
+            .and_then(|images| images.get(handle))
+            .map_or(0, |image| image.data.len())
+    };
+    let atlas_bytes = |handle: &Handle<TextureAtlas>| {
+        atlases
+            .and_then(|atlases| atlases.get(handle))
+            .map_or(0, |atlas| image_bytes(&atlas.texture))
+    };
+    let tileset_bytes = |handle: &Handle<Tileset>| {
+        tilesets
+            .and_then(|tilesets| tilesets.get(handle))
+            .map_or(0, |tileset| image_bytes(&tileset.texture))
+    };
+
+    let mut files: Vec<FileMemory> = file_map
+        .0
+        .iter()
+        .map(|(path, asset_map)| FileMemory {
+            path: path.clone(),
+            image_bytes: file_images(asset_map).map(image_bytes).sum(),
+            atlas_bytes: file_atlases(asset_map).map(atlas_bytes).sum(),
+            tileset_bytes: asset_map.tilesets.values().map(tileset_bytes).sum(),
+        })
+        .collect();
+    files.sort_by(|a, b| {
+        b.total_bytes()
+            .cmp(&a.total_bytes())
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    MemoryReport { files }
+}
+
+fn file_images(asset_map: &AseAssetMap) -> impl Iterator<Item = &Handle<Image>> {
+    asset_map
+        .textures
+        .values()
+        .chain(asset_map.layer_images.values())
+        .chain(&asset_map.thumbnail)
+}
+
+fn file_atlases(asset_map: &AseAssetMap) -> impl Iterator<Item = &Handle<TextureAtlas>> {
+    std::iter::once(&asset_map.atlas)
+        .chain(&asset_map.lod_atlases)
+        .chain(&asset_map.palette_atlases)
+        .chain(&asset_map.outline_atlas)
+        .chain(&asset_map.shadow_atlas)
+        .chain(asset_map.tag_atlases.values())
+}
@@ -0,0 +1,221 @@
+use crate::asset::slice::SliceKey;
+use bevy::{
+    prelude::*,
+    render::{mesh::Indices, render_resource::PrimitiveTopology},
+};
+
+/// Generates a 9-patch [Mesh] from a slice key with 9-slice data.
+///
+/// The mesh is `size` pixels large and centered on the origin. Its corners keep their
+/// size in pixels while its edges and center stretch; if `size` is smaller than the corners,
+/// the corners shrink to fit. Its UVs map into `atlas`, where the slice is cut from the frame
+/// image at `atlas_index`.
+///
+/// This suits world-space panels and speech bubbles rendered with a 2D mesh.
+/// Returns `None` if the key has no 9-slice data, or if `atlas_index` is out of range.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+/// use bevy_ase::asset::slice::Slice;
+/// use bevy_ase::nine_slice::nine_slice_mesh;
+///
+/// fn spawn_bubble(
+///     mut commands: Commands,
+///     mut meshes: ResMut<Assets<Mesh>>,
+///     mut materials: ResMut<Assets<ColorMaterial>>,
+///     atlas: &TextureAtlas,
+///     bubble: &Slice,
+/// ) {
+///     let Some(mesh) = nine_slice_mesh(&bubble.keys[0], atlas, 0, Vec2::new(96.0, 32.0)) else {
+///         return;
+///     };
+///     commands.spawn(MaterialMesh2dBundle {
+///         mesh: meshes.add(mesh).into(),
+///         material: materials.add(ColorMaterial::from(atlas.texture.clone())),
+///         ..default()
+///     });
+/// }
+/// ```
+pub fn nine_slice_mesh(
+    key: &SliceKey,
+    atlas: &TextureAtlas,
+    atlas_index: usize,
+    size: Vec2,
+) -> Option<Mesh> {
+    let slice9 = key.slice9.as_ref()?;
+    let frame = atlas.textures.get(atlas_index)?;
+    let origin = frame.min + Vec2::new(key.origin.0 as f32, key.origin.1 as f32);
+    let center_x = slice9.center_x as f32;
+    let center_y = slice9.center_y as f32;
+    // Grid lines within the slice in pixels, from its top-left corner.
+    let source_xs = [
+        0.0,
+        center_x,
+        center_x + slice9.center_width as f32,
+        key.size.0 as f32,
+    ];
+    let source_ys = [
+        0.0,
+        center_y,
+        center_y + slice9.center_height as f32,
+        key.size.1 as f32,
+    ];
+    let xs = stretch(source_xs, size.x);
+    let ys = stretch(source_ys, size.y);
+
+    let mut positions = Vec::with_capacity(16);
+    let mut uvs = Vec::with_capacity(16);
+    for row in 0..4 {
+        for column in 0..4 {
+            positions.push([xs[column] - size.x / 2.0, size.y / 2.0 - ys[row], 0.0]);
+            uvs.push([
+                (origin.x + source_xs[column]) / atlas.size.x,
+                (origin.y + source_ys[row]) / atlas.size.y,
+            ]);
+        }
+    }
+    let mut indices = Vec::with_capacity(54);
+    for row in 0..3 {
+        for column in 0..3 {
+            let top_left = row * 4 + column;
+            indices.extend([
+                top_left,
+                top_left + 4,
+                top_left + 5,
+                top_left,
+                top_left + 5,
+                top_left + 1,
+            ]);
+        }
+    }
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 1.0]; 16]);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.set_indices(Some(Indices::U32(indices)));
+    Some(mesh)
+}
+
+// Moves the grid lines of one axis to span `length`, keeping the corners' size.
+fn stretch([_, start, end, total]: [f32; 4], length: f32) -> [f32; 4] {
+    let (head, tail) = (start, total - end);
+    let scale = (length / (head + tail)).min(1.0);
+    [0.0, head * scale, length - tail * scale, length]
+}
+
+/// A panel of tiles generated from a 9-slice by [nine_slice_tiles].
+///
+/// Each cell holds the position of a tile within the slice, in tiles from the slice's top-left
+/// corner. Cells are stored row by row, starting at the top-left corner of the panel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NineSliceTiles {
+    /// Size of the panel in tiles.
+    pub size: UVec2,
+    /// Source tile of each cell, row by row from the top.
+    pub cells: Vec<UVec2>,
+}
+impl NineSliceTiles {
+    /// Returns the source tile of the cell at column `x` and row `y`, counted from the top-left.
+    pub fn get(&self, x: u32, y: u32) -> Option<UVec2> {
+        if x >= self.size.x || y >= self.size.y {
+            return None;
+        }
+        self.cells.get((y * self.size.x + x) as usize).copied()
+    }
+
+    /// Returns the tile index of each cell, row by row from the top.
+    ///
+    /// Tiles of the slice are numbered row by row, starting at `first_index`, in a tileset
+    /// `columns` tiles wide. For a slice cut into its own tileset, `columns` is the slice's
+    /// width in tiles. Tilemap renderers such as bevy_ecs_tilemap count rows from the bottom,
+    /// so place row `y` at `size.y - 1 - y`.
+    pub fn indices(&self, columns: u32, first_index: u32) -> Vec<u32> {
+        self.cells
+            .iter()
+            .map(|cell| first_index + cell.y * columns + cell.x)
+            .collect()
+    }
+}
+
+/// Expands a slice key with 9-slice data into a panel of `size` tiles, for UI rendered on a tilemap.
+///
+/// The slice is cut into tiles of `tile_size` pixels. Corner tiles are placed once, edge tiles
+/// repeat along the panel's edges, and center tiles repeat to fill its inside.
+///
+/// Returns `None` if the key has no 9-slice data, if the slice's size and center are not
+/// multiples of `tile_size`, or if `size` is too small to fit the corners. A slice without center
+/// tiles only fits panels of exactly its corners' size.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::asset::slice::Slice;
+/// use bevy_ase::nine_slice::nine_slice_tiles;
+///
+/// // Tile indices of a 10x4 dialog box, from a slice cut into 8x8 tiles of its own tileset.
+/// fn dialog_tiles(dialog: &Slice) -> Option<Vec<u32>> {
+///     let key = &dialog.keys[0];
+///     let tiles = nine_slice_tiles(key, UVec2::splat(8), UVec2::new(10, 4))?;
+///     Some(tiles.indices(key.size.0 / 8, 0))
+/// }
+/// ```
+pub fn nine_slice_tiles(key: &SliceKey, tile_size: UVec2, size: UVec2) -> Option<NineSliceTiles> {
+    let slice9 = key.slice9.as_ref()?;
+    let columns = tile_axis(
+        key.size.0,
+        slice9.center_x,
+        slice9.center_width,
+        tile_size.x,
+        size.x,
+    )?;
+    let rows = tile_axis(
+        key.size.1,
+        slice9.center_y,
+        slice9.center_height,
+        tile_size.y,
+        size.y,
+    )?;
+    let cells = rows
+        .iter()
+        .flat_map(|&y| columns.iter().map(move |&x| UVec2::new(x, y)))
+        .collect();
+    Some(NineSliceTiles { size, cells })
+}
+
+// Maps each tile of one panel axis to a tile of the slice along that axis.
+fn tile_axis(
+    slice_length: u32,
+    center_start: i32,
+    center_length: u32,
+    tile_length: u32,
+    length: u32,
+) -> Option<Vec<u32>> {
+    let center_start = u32::try_from(center_start).ok()?;
+    if tile_length == 0
+        || slice_length % tile_length != 0
+        || center_start % tile_length != 0
+        || center_length % tile_length != 0
+    {
+        return None;
+    }
+    let source_tiles = slice_length / tile_length;
+    let head = center_start / tile_length;
+    let center = center_length / tile_length;
+    let tail = source_tiles.checked_sub(head + center)?;
+    let inside = length.checked_sub(head + tail)?;
+    if center == 0 && inside > 0 {
+        return None;
+    }
+    let tiles = (0..length)
+        .map(|tile| match tile {
+            tile if tile < head => tile,
+            tile if tile >= head + inside => tile - inside + center,
+            tile => head + (tile - head) % center,
+        })
+        .collect();
+    Some(tiles)
+}
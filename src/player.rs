@@ -0,0 +1,854 @@
+use crate::asset::{Animation, AnimationEvent, AnimationEvents};
+use crate::preview::apply_animation_previews;
+use crate::shadow::sync_shadows;
+use crate::state_machine::drive_state_machines;
+use crate::tint::apply_tints;
+use crate::z_order::apply_z_orders;
+use bevy::{prelude::*, utils::Duration};
+use std::ops::RangeInclusive;
+
+/// Frames with this duration in milliseconds are held until [AnimationPlayer::resume] is called.
+///
+/// This is the longest frame duration Aseprite allows. Artists use it to mark hold frames.
+pub const HOLD_FRAME_DURATION_MS: u32 = u16::MAX as u32;
+
+/// Adds the [animate_sprites], [drive_state_machines], [apply_tints], [sync_shadows],
+/// [apply_z_orders] and [apply_animation_previews] systems, and the [AnimationFinished], [FrameChanged] and
+/// [AnimationEventFired] events.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy::utils::Duration;
+/// use bevy_ase::loader::AseLoaderDefaultPlugin;
+/// use bevy_ase::player::{AnimationPlayerPlugin, CatchUpPolicy, EventPolicy};
+///
+/// fn app() {
+///     App::new()
+///         .add_plugins(DefaultPlugins)
+///         .add_plugin(AseLoaderDefaultPlugin)
+///         .add_plugin(AnimationPlayerPlugin::default());
+/// }
+///
+/// // After a long hitch, advance at most 100ms and only report the frame shown last.
+/// fn app_with_catch_up_policy() {
+///     App::new()
+///         .add_plugins(DefaultPlugins)
+///         .add_plugin(AseLoaderDefaultPlugin)
+///         .add_plugin(AnimationPlayerPlugin {
+///             catch_up: CatchUpPolicy {
+///                 max_delta: Some(Duration::from_millis(100)),
+///                 skip_whole_loops: true,
+///                 events: EventPolicy::Latest,
+///             },
+///         });
+/// }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct AnimationPlayerPlugin {
+    /// How players catch up after long frames. Inserted as a resource.
+    pub catch_up: CatchUpPolicy,
+    /// How players measure time. Inserted as a resource.
+    pub clock: PlayerClock,
+}
+
+impl Plugin for AnimationPlayerPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.catch_up.clone())
+            .insert_resource(self.clock)
+            .add_event::<AnimationFinished>()
+            .add_event::<FrameChanged>()
+            .add_event::<AnimationEventFired>()
+            .add_system(animate_sprites)
+            .add_system(drive_state_machines.after(animate_sprites))
+            .add_system(apply_tints)
+            .add_system(sync_shadows.after(animate_sprites))
+            .add_system(apply_z_orders)
+            .add_system(apply_animation_previews.before(sync_shadows));
+    }
+}
+
+/// Controls how [AnimationPlayer]s catch up when an update takes a long time,
+/// e.g. after a hitch while loading.
+///
+/// By default, players step through every frame they missed and send an event for each.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Resource)]
+pub struct CatchUpPolicy {
+    /// Maximum time a player advances per update. `None` means no limit.
+    pub max_delta: Option<Duration>,
+    /// If true, looping animations skip whole loops they missed,
+    /// instead of stepping through every frame of them.
+    ///
+    /// Loops are not skipped while a transition is pending, or if the animation
+    /// has hold frames.
+    pub skip_whole_loops: bool,
+    /// Which [FrameChanged] and [AnimationEventFired] events are sent
+    /// for the frames passed in one update.
+    pub events: EventPolicy,
+}
+
+/// How [animate_sprites] measures the time players advance by.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::player::{AnimationPlayerPlugin, PlayerClock};
+///
+/// // Deterministic playback for lockstep or rollback netcode.
+/// fn app() {
+///     App::new()
+///         .add_plugins(DefaultPlugins)
+///         .add_plugin(AnimationPlayerPlugin {
+///             clock: PlayerClock::Micros,
+///             ..Default::default()
+///         });
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Resource)]
+pub enum PlayerClock {
+    /// Advance by each update's delta time, with nanosecond precision.
+    #[default]
+    Nanos,
+    /// Advance in whole microseconds, with integer arithmetic.
+    ///
+    /// The sub-microsecond part of each delta time is carried over to the next update, so
+    /// playback doesn't drift from wall-clock time. Frame durations at speeds other than 1
+    /// are rounded to whole microseconds the same way on every platform.
+    Micros,
+}
+
+// Converts a delta time into whole microseconds, carrying the remaining nanoseconds.
+pub(crate) fn micros_delta(delta: Duration, carried_nanos: &mut u64) -> Duration {
+    let nanos = delta.as_nanos() as u64 + *carried_nanos;
+    *carried_nanos = nanos % 1000;
+    Duration::from_micros(nanos / 1000)
+}
+
+/// Which [FrameChanged] events are sent when a player passes several frames in one update.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum EventPolicy {
+    /// Send an event for every frame.
+    #[default]
+    All,
+    /// Only send an event for the last frame.
+    Latest,
+}
+
+/// How an [AnimationPlayer] continues after the last frame.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PlayMode {
+    /// Use the tag's repeat count from Aseprite (see [Animation::repeat]).
+    ///
+    /// Tags which repeat once play as [PlayMode::Once]. All other animations,
+    /// including those with an unknown repeat count, play as [PlayMode::Loop].
+    #[default]
+    Auto,
+    /// Restart from the first frame.
+    Loop,
+    /// Play once, then stop and send an [AnimationFinished] event.
+    Once,
+}
+impl PlayMode {
+    // Resolves `Auto` for the given animation.
+    fn resolve(self, animation: &Animation) -> Self {
+        match (self, animation.repeat()) {
+            (PlayMode::Auto, Some(1)) => PlayMode::Once,
+            (PlayMode::Auto, _) => PlayMode::Loop,
+            (mode, _) => mode,
+        }
+    }
+}
+
+/// When an [AnimationPlayer] switches to the animation passed to
+/// [AnimationPlayer::transition_to].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionPoint {
+    /// Switch right away.
+    Immediate,
+    /// Switch after the last frame, instead of looping or finishing.
+    /// An animation which already finished switches on the next update.
+    EndOfLoop,
+    /// Switch when the current animation advances to the frame with this index.
+    Frame(usize),
+}
+
+/// Playback state of an [AnimationPlayer], returned by [AnimationPlayer::save].
+///
+/// Time is kept in whole nanoseconds, the resolution of the player's clock,
+/// so restoring a state continues playback exactly.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PlayerState {
+    /// Index of the current frame in the animation.
+    pub frame: u32,
+    /// Time spent on the current frame, in nanoseconds.
+    pub elapsed_nanos: u64,
+    /// Whether the player is held on its current frame. See [AnimationPlayer::is_held].
+    pub held: bool,
+    /// Whether the player continues past a hold frame after [AnimationPlayer::resume].
+    pub resuming: bool,
+    /// Whether an animation played once has finished. See [AnimationPlayer::is_finished].
+    pub finished: bool,
+    /// Whether the current frame was shown yet.
+    pub started: bool,
+}
+
+/// Component which plays an [Animation] on the entity's [TextureAtlasSprite].
+///
+/// The [animate_sprites] system advances the player and updates the entity's sprite index
+/// and [TextureAtlas] handle.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::asset::Animation;
+/// use bevy_ase::player::AnimationPlayer;
+///
+/// // A death animation which ends frozen on its last frame.
+/// fn play_death(mut commands: Commands, entity: Entity, death: Handle<Animation>) {
+///     commands
+///         .entity(entity)
+///         .insert(AnimationPlayer::once(death).with_hold_last_frame(true));
+/// }
+/// ```
+#[derive(Debug, Clone, Component)]
+pub struct AnimationPlayer {
+    animation: Handle<Animation>,
+    mode: PlayMode,
+    hold_last_frame: bool,
+    frame: usize,
+    elapsed: Duration,
+    held: bool,
+    // Set by `resume` to continue past a hold frame.
+    skip_hold: bool,
+    finished: bool,
+    // False until the first frame was shown.
+    started: bool,
+    pending: Option<(Handle<Animation>, TransitionPoint)>,
+    // The animation flip last applied to the sprite, on top of its own flip.
+    applied_flip: (bool, bool),
+    lod_level: u32,
+    palette: u32,
+    range: Option<RangeInclusive<usize>>,
+}
+
+impl AnimationPlayer {
+    /// Creates a player which uses the animation's repeat count from Aseprite.
+    /// See [PlayMode::Auto].
+    pub fn new(animation: Handle<Animation>) -> Self {
+        Self {
+            animation,
+            mode: PlayMode::Auto,
+            hold_last_frame: false,
+            frame: 0,
+            elapsed: Duration::ZERO,
+            held: false,
+            skip_hold: false,
+            finished: false,
+            started: false,
+            pending: None,
+            applied_flip: (false, false),
+            lod_level: 0,
+            palette: 0,
+            range: None,
+        }
+    }
+
+    /// Creates a player which plays the animation once.
+    pub fn once(animation: Handle<Animation>) -> Self {
+        Self::new(animation).with_mode(PlayMode::Once)
+    }
+
+    /// Sets the [PlayMode].
+    pub fn with_mode(mut self, mode: PlayMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets whether an animation played once stays on its last frame when it finishes.
+    /// Otherwise, it returns to its first frame.
+    pub fn with_hold_last_frame(mut self, hold_last_frame: bool) -> Self {
+        self.hold_last_frame = hold_last_frame;
+        self
+    }
+
+    /// Sets the level of detail. See [AnimationPlayer::set_lod_level].
+    pub fn with_lod_level(mut self, lod_level: u32) -> Self {
+        self.lod_level = lod_level;
+        self
+    }
+
+    /// Returns the level of detail of the shown atlas.
+    pub fn lod_level(&self) -> u32 {
+        self.lod_level
+    }
+
+    /// Sets the level of detail of the shown atlas. See [Animation::lod_atlas].
+    ///
+    /// Level 0, the default, shows the full-size atlas. Sprites are drawn at the size of
+    /// their atlas region, so give them a `custom_size` to keep their size at other levels.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_ase::player::AnimationPlayer;
+    ///
+    /// // Switch sprites to smaller atlases as the camera zooms out.
+    /// fn pick_lod(
+    ///     camera: Query<&OrthographicProjection, Changed<OrthographicProjection>>,
+    ///     mut players: Query<&mut AnimationPlayer>,
+    /// ) {
+    ///     let Ok(projection) = camera.get_single() else {
+    ///         return;
+    ///     };
+    ///     let level = projection.scale.max(1.0).log2().floor() as u32;
+    ///     for mut player in &mut players {
+    ///         player.set_lod_level(level);
+    ///     }
+    /// }
+    /// ```
+    pub fn set_lod_level(&mut self, lod_level: u32) {
+        self.lod_level = lod_level;
+    }
+
+    /// Sets the variant palette. See [AnimationPlayer::set_palette].
+    pub fn with_palette(mut self, palette: u32) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    /// Returns the variant palette of the shown atlas.
+    pub fn palette(&self) -> u32 {
+        self.palette
+    }
+
+    /// Sets the variant palette of the shown atlas. See [Animation::palette_atlas].
+    ///
+    /// Palette 0, the default, shows the original colors. Recolored atlases are not
+    /// downscaled, so other palettes always show at full size, whatever the level of detail.
+    pub fn set_palette(&mut self, palette: u32) {
+        self.palette = palette;
+    }
+
+    /// Starts playing another animation from its first frame.
+    pub fn play(&mut self, animation: Handle<Animation>) {
+        self.animation = animation;
+        self.pending = None;
+        self.range = None;
+        self.frame = 0;
+        self.elapsed = Duration::ZERO;
+        self.held = false;
+        self.skip_hold = false;
+        self.finished = false;
+        self.started = false;
+    }
+
+    /// Restarts the current animation, playing only the frames in `range`.
+    ///
+    /// Looping players return to the range's first frame after its last, and players which play
+    /// once finish on its last frame. This is meant for the whole-file animation, when frame ranges
+    /// are decided by game logic rather than tags. The range is checked against the animation's
+    /// frame count on each update: frames past the last frame are left out, and ranges which are
+    /// empty or start past the last frame play the whole animation.
+    /// [AnimationPlayer::play] clears the range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_ase::player::AnimationPlayer;
+    ///
+    /// // Play frames 5 to 9 of a file whose tags don't match the game's states.
+    /// fn play_charge(player: &mut AnimationPlayer) {
+    ///     player.play_range(5..=9);
+    /// }
+    /// ```
+    pub fn play_range(&mut self, range: RangeInclusive<usize>) {
+        self.play(self.animation.clone());
+        self.frame = *range.start();
+        self.range = Some(range);
+    }
+
+    /// Returns the frame range set with [AnimationPlayer::play_range], if any.
+    pub fn range(&self) -> Option<&RangeInclusive<usize>> {
+        self.range.as_ref()
+    }
+
+    // Returns the first and last frame to play of an animation with `frame_count` frames.
+    fn frame_bounds(&self, frame_count: usize) -> (usize, usize) {
+        match &self.range {
+            Some(range) if !range.is_empty() && *range.start() < frame_count => {
+                (*range.start(), (*range.end()).min(frame_count - 1))
+            }
+            _ => (0, frame_count - 1),
+        }
+    }
+
+    /// Returns the player's playback state, e.g. to snapshot it for rollback netcode.
+    ///
+    /// The state is a small [Copy] value holding everything that changes while the player
+    /// advances. The animation, [PlayMode], frame range and pending transition only change
+    /// when set by the game, so they are not part of it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_ase::player::{AnimationPlayer, PlayerState};
+    ///
+    /// #[derive(Component, Default)]
+    /// struct Snapshot(Vec<PlayerState>);
+    ///
+    /// fn save_players(mut players: Query<(&AnimationPlayer, &mut Snapshot)>) {
+    ///     for (player, mut snapshot) in &mut players {
+    ///         snapshot.0.push(player.save());
+    ///     }
+    /// }
+    ///
+    /// fn rewind_players(mut players: Query<(&mut AnimationPlayer, &Snapshot)>, frames: usize) {
+    ///     for (mut player, snapshot) in &mut players {
+    ///         if let Some(state) = snapshot.0.iter().rev().nth(frames) {
+    ///             player.restore(*state);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn save(&self) -> PlayerState {
+        PlayerState {
+            frame: self.frame as u32,
+            elapsed_nanos: self.elapsed.as_nanos() as u64,
+            held: self.held,
+            resuming: self.skip_hold,
+            finished: self.finished,
+            started: self.started,
+        }
+    }
+
+    /// Restores a playback state returned by [AnimationPlayer::save].
+    ///
+    /// Advancing the player afterwards shows the same frames as it did after the state was saved.
+    pub fn restore(&mut self, state: PlayerState) {
+        self.frame = state.frame as usize;
+        self.elapsed = Duration::from_nanos(state.elapsed_nanos);
+        self.held = state.held;
+        self.skip_hold = state.resuming;
+        self.finished = state.finished;
+        self.started = state.started;
+    }
+
+    /// Returns the animation being played.
+    pub fn animation(&self) -> &Handle<Animation> {
+        &self.animation
+    }
+
+    /// Returns the [PlayMode].
+    pub fn mode(&self) -> PlayMode {
+        self.mode
+    }
+
+    /// Returns the index of the current frame in the animation.
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Returns true if an animation played once has finished.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Returns true if the player is held on its current frame.
+    pub fn is_held(&self) -> bool {
+        self.held
+    }
+
+    /// Jumps to the given frame and holds it until [AnimationPlayer::resume] is called.
+    pub fn hold_at(&mut self, frame: usize) {
+        self.frame = frame;
+        self.elapsed = Duration::ZERO;
+        self.held = true;
+    }
+
+    /// Continues playing from the current frame.
+    ///
+    /// If the current frame is a hold frame (see [HOLD_FRAME_DURATION_MS]),
+    /// playback continues with the next frame.
+    pub fn resume(&mut self) {
+        if self.held {
+            self.held = false;
+            self.skip_hold = true;
+            self.elapsed = Duration::ZERO;
+        }
+    }
+
+    /// Switches to another animation once the current one reaches the given [TransitionPoint].
+    ///
+    /// The new animation starts from its first frame. Only one transition can be pending;
+    /// a new transition replaces the pending one. Calling [AnimationPlayer::play]
+    /// cancels the pending transition.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// use bevy_ase::asset::Animation;
+    /// use bevy_ase::player::{AnimationPlayer, TransitionPoint};
+    ///
+    /// // Switch from walking to running without popping mid-stride.
+    /// fn start_running(player: &mut AnimationPlayer, run: Handle<Animation>) {
+    ///     player.transition_to(run, TransitionPoint::EndOfLoop);
+    /// }
+    /// ```
+    pub fn transition_to(&mut self, animation: Handle<Animation>, at: TransitionPoint) {
+        if at == TransitionPoint::Immediate {
+            self.play(animation);
+        } else {
+            self.pending = Some((animation, at));
+        }
+    }
+
+    /// Returns the animation of the pending transition, if any.
+    pub fn pending_transition(&self) -> Option<&Handle<Animation>> {
+        self.pending.as_ref().map(|(animation, _)| animation)
+    }
+
+    // Plays the pending animation if its transition point was reached.
+    // Frames entered in the previous animation are replaced by the new first frame.
+    fn take_transition(
+        &mut self,
+        reached: impl Fn(TransitionPoint) -> bool,
+        entered: &mut Vec<usize>,
+    ) -> bool {
+        match self.pending.take() {
+            Some((animation, at)) if reached(at) => {
+                self.play(animation);
+                self.started = true;
+                entered.clear();
+                entered.push(self.frame);
+                true
+            }
+            pending => {
+                self.pending = pending;
+                false
+            }
+        }
+    }
+
+    // Advances playback by `delta`. Returns true if the animation finished.
+    #[cfg(test)]
+    pub(crate) fn tick(&mut self, animation: &Animation, delta: Duration) -> bool {
+        let policy = CatchUpPolicy::default();
+        self.tick_with(
+            animation,
+            delta,
+            &policy,
+            PlayerClock::Nanos,
+            &mut Vec::new(),
+        )
+    }
+
+    // Advances playback by `delta`, pushing the index of each entered frame to `entered`.
+    // Returns true if the animation finished.
+    pub(crate) fn tick_with(
+        &mut self,
+        animation: &Animation,
+        delta: Duration,
+        policy: &CatchUpPolicy,
+        clock: PlayerClock,
+        entered: &mut Vec<usize>,
+    ) -> bool {
+        let frames = animation.frames();
+        if self.finished && self.take_transition(|at| at == TransitionPoint::EndOfLoop, entered) {
+            return false;
+        }
+        if frames.is_empty() {
+            return false;
+        }
+        // The animation may have shrunk after a hot reload, also while held or finished.
+        let (first, last) = self.frame_bounds(frames.len());
+        self.frame = self.frame.clamp(first, last);
+        // Invalid ranges play the whole animation from its start.
+        let invalid_range = self
+            .range
+            .as_ref()
+            .map_or(false, |range| range.is_empty() || *range.start() > last);
+        if !self.started && invalid_range {
+            self.frame = first;
+        }
+        if !self.started {
+            self.started = true;
+            entered.push(self.frame);
+        }
+        if self.held || self.finished {
+            return false;
+        }
+        let mode = self.mode.resolve(animation);
+        self.elapsed += policy.max_delta.map_or(delta, |max| delta.min(max));
+        if policy.skip_whole_loops && mode == PlayMode::Loop && self.pending.is_none() {
+            if let Some(loop_duration) = loop_duration(animation, first..=last, clock) {
+                self.elapsed = Duration::from_nanos(
+                    (self.elapsed.as_nanos() % loop_duration.as_nanos()) as u64,
+                );
+            }
+        }
+        loop {
+            let duration = if let Some(duration) = frame_duration(animation, self.frame, clock) {
+                duration
+            } else if self.skip_hold {
+                Duration::ZERO
+            } else {
+                self.held = true;
+                self.elapsed = Duration::ZERO;
+                return false;
+            };
+            if self.elapsed < duration {
+                return false;
+            }
+            self.elapsed -= duration;
+            self.skip_hold = false;
+            if self.frame < last {
+                self.frame += 1;
+                let frame = self.frame;
+                if self.take_transition(|at| at == TransitionPoint::Frame(frame), entered) {
+                    return false;
+                }
+                entered.push(self.frame);
+                continue;
+            }
+            if self.take_transition(|at| at == TransitionPoint::EndOfLoop, entered) {
+                return false;
+            }
+            match mode {
+                PlayMode::Auto | PlayMode::Loop => {
+                    self.frame = first;
+                    entered.push(self.frame);
+                }
+                PlayMode::Once => {
+                    self.finished = true;
+                    self.elapsed = Duration::ZERO;
+                    if !self.hold_last_frame {
+                        self.frame = first;
+                    }
+                    return true;
+                }
+            }
+        }
+    }
+}
+
+// Returns how long a frame is shown at the animation's speed, or `None` for hold frames.
+fn frame_duration(animation: &Animation, frame: usize, clock: PlayerClock) -> Option<Duration> {
+    let duration_ms = animation.frames()[frame].duration_ms;
+    if duration_ms >= HOLD_FRAME_DURATION_MS {
+        return None;
+    }
+    // Zero-length frames would never let a looping animation catch up.
+    let duration = Duration::from_millis(duration_ms.max(1) as u64);
+    let speed = animation.frame_speed(frame);
+    if speed <= 0.0 || speed.is_nan() {
+        return Some(Duration::MAX);
+    }
+    Some(match clock {
+        // Tiny speeds stretch frames past what a Duration can hold, which pauses on them.
+        PlayerClock::Nanos => Duration::try_from_secs_f32(duration.as_secs_f32() / speed)
+            .unwrap_or(Duration::MAX)
+            .max(Duration::from_micros(1)),
+        // Rounded in f64 so every platform gets the same whole number of microseconds.
+        PlayerClock::Micros => {
+            let micros = (duration.as_micros() as f64 / speed as f64).round();
+            Duration::from_micros((micros as u64).max(1))
+        }
+    })
+}
+
+// Returns the duration of one loop over `frames`, or `None` if they include hold frames.
+fn loop_duration(
+    animation: &Animation,
+    frames: RangeInclusive<usize>,
+    clock: PlayerClock,
+) -> Option<Duration> {
+    frames
+        .map(|frame| frame_duration(animation, frame, clock))
+        .try_fold(Duration::ZERO, |total, duration| {
+            total.checked_add(duration?)
+        })
+}
+
+/// Event sent by [animate_sprites] when an animation played once finishes.
+#[derive(Debug, Clone)]
+pub struct AnimationFinished {
+    /// The entity playing the animation.
+    pub entity: Entity,
+    /// The finished animation.
+    pub animation: Handle<Animation>,
+}
+
+/// Event sent by [animate_sprites] when a player shows another frame,
+/// including the first frame after an animation starts.
+///
+/// The [CatchUpPolicy] controls whether events are sent for every frame passed in one update.
+#[derive(Debug, Clone)]
+pub struct FrameChanged {
+    /// The entity playing the animation.
+    pub entity: Entity,
+    /// The animation being played.
+    pub animation: Handle<Animation>,
+    /// The index of the new frame in the animation.
+    pub frame: usize,
+}
+
+/// Event sent by [animate_sprites] for each [AnimationEvent] on a frame a player shows.
+#[derive(Debug, Clone)]
+pub struct AnimationEventFired {
+    /// The entity playing the animation.
+    pub entity: Entity,
+    /// The animation being played.
+    pub animation: Handle<Animation>,
+    /// The fired event.
+    pub event: AnimationEvent,
+}
+
+// Event writers used by the player.
+type PlayerEvents<'w> = (
+    EventWriter<'w, AnimationFinished>,
+    EventWriter<'w, FrameChanged>,
+    EventWriter<'w, AnimationEventFired>,
+);
+
+/// System function which advances every [AnimationPlayer] and updates its entity's sprite.
+///
+/// This system is already added by [AnimationPlayerPlugin].
+pub fn animate_sprites(
+    time: Res<Time>,
+    policy: Option<Res<CatchUpPolicy>>,
+    clock: Option<Res<PlayerClock>>,
+    animations: Res<Assets<Animation>>,
+    animation_events: Option<Res<Assets<AnimationEvents>>>,
+    mut players: Query<(
+        Entity,
+        &mut AnimationPlayer,
+        &mut TextureAtlasSprite,
+        &mut Handle<TextureAtlas>,
+    )>,
+    events: PlayerEvents,
+    mut entered: Local<Vec<usize>>,
+    mut carried_nanos: Local<u64>,
+) {
+    let (mut finished, mut frame_changed, mut fired) = events;
+    let default_policy = CatchUpPolicy::default();
+    let policy = policy.as_deref().unwrap_or(&default_policy);
+    let clock = clock.as_deref().copied().unwrap_or_default();
+    let delta = match clock {
+        PlayerClock::Nanos => time.delta(),
+        PlayerClock::Micros => micros_delta(time.delta(), &mut carried_nanos),
+    };
+    for (entity, mut player, mut sprite, mut atlas) in &mut players {
+        let Some(animation) = animations.get(&player.animation) else {
+            continue;
+        };
+        entered.clear();
+        if player.tick_with(animation, delta, policy, clock, &mut entered) {
+            finished.send(AnimationFinished {
+                entity,
+                animation: player.animation.clone(),
+            });
+        }
+        // The player may have transitioned to another animation.
+        let Some(animation) = animations.get(&player.animation) else {
+            continue;
+        };
+        let skip = match policy.events {
+            EventPolicy::All => 0,
+            EventPolicy::Latest => entered.len().saturating_sub(1),
+        };
+        let marker_events = animation
+            .events()
+            .zip(animation_events.as_deref())
+            .and_then(|(handle, assets)| assets.get(handle));
+        for frame in entered.iter().skip(skip) {
+            frame_changed.send(FrameChanged {
+                entity,
+                animation: player.animation.clone(),
+                frame: *frame,
+            });
+            let Some(marker_events) = marker_events else {
+                continue;
+            };
+            for event in marker_events.at_frame(*frame as u32) {
+                fired.send(AnimationEventFired {
+                    entity,
+                    animation: player.animation.clone(),
+                    event: event.clone(),
+                });
+            }
+        }
+        let Some(frame) = animation.frames().get(player.frame) else {
+            continue;
+        };
+        let index = frame.sprite.atlas_index as usize;
+        if sprite.index != index {
+            sprite.index = index;
+        }
+        // Toggle rather than set the flip, to keep flips set by the game.
+        let flip = (animation.flip_x(), animation.flip_y());
+        if player.applied_flip != flip {
+            sprite.flip_x ^= player.applied_flip.0 != flip.0;
+            sprite.flip_y ^= player.applied_flip.1 != flip.1;
+            player.applied_flip = flip;
+        }
+        let shown_atlas = match player.palette {
+            0 => animation.lod_atlas(player.lod_level),
+            palette => animation.palette_atlas(palette),
+        };
+        if atlas.id() != shown_atlas.id() {
+            *atlas = shown_atlas;
+        }
+    }
+}
+
+/// System function which clamps the index of each [TextureAtlasSprite] to its [TextureAtlas]
+/// when the atlas changes, e.g. after a hot reload shrank it.
+///
+/// Out-of-range indices make Bevy's sprite renderer panic. [AnimationPlayer]s keep their
+/// sprites in range already; add this system when animating sprites in another way,
+/// e.g. with benimator.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::player::clamp_sprite_indices;
+///
+/// fn app() {
+///     App::new().add_system(clamp_sprite_indices);
+/// }
+/// ```
+pub fn clamp_sprite_indices(
+    mut events: EventReader<AssetEvent<TextureAtlas>>,
+    atlases: Res<Assets<TextureAtlas>>,
+    mut sprites: Query<(&Handle<TextureAtlas>, &mut TextureAtlasSprite)>,
+) {
+    let modified: Vec<&Handle<TextureAtlas>> = events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { handle } => Some(handle),
+            _ => None,
+        })
+        .collect();
+    if modified.is_empty() {
+        return;
+    }
+    for (handle, mut sprite) in &mut sprites {
+        if !modified.contains(&handle) {
+            continue;
+        }
+        let Some(atlas) = atlases.get(handle) else {
+            continue;
+        };
+        let last = atlas.len().saturating_sub(1);
+        if sprite.index > last {
+            sprite.index = last;
+        }
+    }
+}
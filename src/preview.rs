@@ -0,0 +1,75 @@
+use crate::asset::Animation;
+use bevy::prelude::*;
+
+/// Component which shows the frame of an [Animation] at a manually set time.
+///
+/// Unlike an [AnimationPlayer](crate::player::AnimationPlayer), a preview does not play
+/// on its own. Set [AnimationPreview::time] to scrub through the animation, e.g. from a
+/// slider in an editor UI. The [apply_animation_previews] system shows the frame at that time,
+/// as computed by [Animation::frame_at]; it is added by
+/// [AnimationPlayerPlugin](crate::player::AnimationPlayerPlugin).
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::asset::Animation;
+/// use bevy_ase::preview::AnimationPreview;
+///
+/// fn spawn_preview(mut commands: Commands, walk: Handle<Animation>) {
+///     commands.spawn((SpriteSheetBundle::default(), AnimationPreview::new(walk)));
+/// }
+///
+/// // Scrubs to the given point of the animation, from 0 to 1.
+/// fn scrub(mut previews: Query<&mut AnimationPreview>, slider: f32) {
+///     for mut preview in &mut previews {
+///         preview.time = slider;
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Component)]
+pub struct AnimationPreview {
+    /// The previewed animation.
+    pub animation: Handle<Animation>,
+    /// The normalized time of the shown frame, from 0 at the start of the animation
+    /// to 1 at its end.
+    pub time: f32,
+}
+
+impl AnimationPreview {
+    /// Creates a preview of an animation, showing its first frame.
+    pub fn new(animation: Handle<Animation>) -> Self {
+        Self {
+            animation,
+            time: 0.0,
+        }
+    }
+}
+
+/// System function which shows the frame of each [AnimationPreview] at its time.
+///
+/// This system is already added by [AnimationPlayerPlugin](crate::player::AnimationPlayerPlugin).
+pub fn apply_animation_previews(
+    animations: Res<Assets<Animation>>,
+    mut previews: Query<(
+        &AnimationPreview,
+        &mut TextureAtlasSprite,
+        &mut Handle<TextureAtlas>,
+    )>,
+) {
+    for (preview, mut sprite, mut atlas) in &mut previews {
+        let Some(animation) = animations.get(&preview.animation) else {
+            continue;
+        };
+        let Some(frame) = animation.frame_at(preview.time) else {
+            continue;
+        };
+        let index = animation.frames()[frame].sprite.atlas_index as usize;
+        if sprite.index != index {
+            sprite.index = index;
+        }
+        if atlas.id() != animation.atlas().id() {
+            *atlas = animation.atlas();
+        }
+    }
+}
@@ -0,0 +1,2244 @@
+use crate::asset::{
+    events::AnimationEvent,
+    slice::{self, Slice},
+    tileset::{TilesetError, TilesetResult},
+    TileSize, UiTextPlaceholder,
+};
+use asefile::{AsepriteFile, BlendMode, Layer, LayerFlags, Tag, UserData};
+use bevy::{
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+    utils::HashMap,
+};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+};
+
+/// Settings for [process_file].
+#[derive(Debug, Clone)]
+pub struct ProcessSettings {
+    /// Whether to decode the image of each frame. Defaults to true.
+    ///
+    /// When false, [ProcessedFrame::image] is `None`.
+    pub frame_images: bool,
+    /// Whether to decode the image of each tileset. Defaults to true.
+    ///
+    /// When false, [ProcessedTileset::image] is `None`.
+    pub tileset_images: bool,
+    /// Whether to pack all frame images into a texture atlas. Defaults to true.
+    ///
+    /// Requires `frame_images`. When false, [ProcessedFile::atlas] is `None`.
+    pub atlas: bool,
+    /// Whether to keep each frame's image after packing the atlas. Defaults to true.
+    ///
+    /// When false and an atlas is packed, [ProcessedFrame::image] is `None`.
+    /// The [Loader](crate::loader::Loader) then creates frame images lazily from the atlas,
+    /// the first time they are accessed with [AseAssetMap::texture](crate::asset::AseAssetMap::texture).
+    /// This saves memory for projects which mostly use atlases.
+    pub keep_frame_images: bool,
+    /// Files with at least this many frames decode their frames in parallel,
+    /// on one scoped thread per available core. Defaults to 32.
+    ///
+    /// Set to `usize::MAX` to always decode frames on the calling thread.
+    pub parallel_frames_threshold: usize,
+    /// Size limits for processed files. By default, there are no limits.
+    ///
+    /// The [Loader](crate::loader::Loader) checks these limits before processing a file,
+    /// and reports files which exceed them as import errors. [process_file] does not check
+    /// them; call [check_limits] first.
+    pub limits: ImportLimits,
+    /// How to treat a file's embedded color profile. Defaults to [ColorProfileHandling::Warn].
+    pub color_profile: ColorProfileHandling,
+    /// Texture formats of the images the [Loader](crate::loader::Loader) creates.
+    /// [process_file] always returns RGBA images.
+    pub formats: ImageFormats,
+    /// Settings for generating a collision shape per frame. Defaults to `None`,
+    /// which generates no collision shapes.
+    pub collision: Option<CollisionSettings>,
+    /// Whether frame images include reference layers. Defaults to false.
+    ///
+    /// Reference layers hold imported reference images, e.g. concept art, which should
+    /// not end up in shipped atlases. Frames of files with visible reference layers are
+    /// then composited without them, blending the remaining layers with their blend mode
+    /// and opacity like Aseprite does. This also applies to [ProcessedFrame::layer_images].
+    pub reference_layers: bool,
+    /// Which images are composited from the file's layers. Defaults to
+    /// [LayerFlattening::Frames].
+    pub flattening: LayerFlattening,
+    /// Name of a top-level layer or group holding a drop shadow. Defaults to `None`.
+    ///
+    /// When a file has such a layer, it is left out of frame images and split off into
+    /// [ProcessedFile::shadow], so the shadow can be rendered as a separate sprite under
+    /// the rest. Shadows which are the same in every frame are kept as a single image.
+    pub shadow_layer: Option<String>,
+    /// Generates a small preview image of each file when set. Defaults to `None`.
+    ///
+    /// Thumbnails are taken from frame images, so they require [ProcessSettings::frame_images].
+    /// See [ProcessedFile::thumbnail].
+    pub thumbnail: Option<ThumbnailSettings>,
+    /// Whether slices marked as text are imported as [UiTextPlaceholder]s. Defaults to false.
+    ///
+    /// Slices are marked as text when their user data text starts with a `text:{key}`
+    /// entry, e.g. `text:menu.start`. They are then listed in
+    /// [ProcessedFile::text_placeholders] instead of [ProcessedFile::slices].
+    pub text_slices: bool,
+    /// How many pixels of art make up one world unit. Defaults to `None`, which leaves
+    /// sprites at their pixel size.
+    ///
+    /// Files can set their own value with a `ppu:{value}` entry in the user data text of
+    /// any layer, e.g. `ppu:32`, which takes priority over this setting. This way, art drawn
+    /// at different resolutions can be shown at a consistent world scale.
+    /// See [ProcessedFile::pixels_per_unit].
+    pub pixels_per_unit: Option<f32>,
+    /// Number of downscaled copies of the atlas to generate. Defaults to 0.
+    ///
+    /// Each level halves the previous one, so 2 generates atlases at 1/2 and 1/4 of the
+    /// original size. They keep the atlas' layout, with every frame at the same index.
+    /// Requires `atlas`. See [ProcessedFile::lod_atlases].
+    pub lod_levels: u32,
+    /// Generates an outline or silhouette of each frame when set. Defaults to `None`.
+    ///
+    /// Requires `atlas`. See [ProcessedFile::outline].
+    pub outline: Option<OutlineSettings>,
+    /// Whether to pack one atlas per tag instead of one for the whole file. Defaults to false.
+    ///
+    /// Keeps textures small for files with many animations, which would otherwise share
+    /// one very large atlas. Frames in several tags are packed into each of their atlases.
+    /// Requires `atlas`. When true, [ProcessedFile::atlas] is `None` and frame images are
+    /// always kept, so downscaled, recolored and outline atlases are not generated.
+    /// See [ProcessedFile::tag_atlases].
+    pub atlas_per_tag: bool,
+    /// Whether the [Loader](crate::loader::Loader) names the textures it creates. Defaults to false.
+    ///
+    /// Names are set as the texture descriptor's label, which wgpu passes to the graphics API,
+    /// so captures in tools like RenderDoc and validation errors identify each texture's source.
+    /// Frame images are named after the first tag containing them and their index in it,
+    /// e.g. `sprites/hero.aseprite/walk/3`. Other textures are named after their labeled path,
+    /// e.g. `sprites/hero.aseprite#Atlas`. Each distinct name stays allocated until the app
+    /// exits, so this is meant for debug builds.
+    pub debug_labels: bool,
+    /// Whether the [Loader](crate::loader::Loader) creates tileset textures for runtime editing.
+    /// Defaults to false.
+    ///
+    /// Tileset textures then have the `COPY_SRC` usage besides Bevy's default `TEXTURE_BINDING`
+    /// and `COPY_DST` usages, so tile editors can copy tiles between textures on the GPU.
+    /// Tiles keep their vertical strip layout, where each tile is one contiguous range of
+    /// the texture's data. See [Tileset::write_tile](crate::asset::Tileset::write_tile).
+    pub editable_tilesets: bool,
+}
+impl Default for ProcessSettings {
+    fn default() -> Self {
+        Self {
+            frame_images: true,
+            tileset_images: true,
+            atlas: true,
+            keep_frame_images: true,
+            parallel_frames_threshold: 32,
+            limits: ImportLimits::default(),
+            color_profile: ColorProfileHandling::default(),
+            formats: ImageFormats::default(),
+            collision: None,
+            reference_layers: false,
+            flattening: LayerFlattening::default(),
+            shadow_layer: None,
+            thumbnail: None,
+            text_slices: false,
+            pixels_per_unit: None,
+            lod_levels: 0,
+            outline: None,
+            atlas_per_tag: false,
+            debug_labels: false,
+            editable_tilesets: false,
+        }
+    }
+}
+
+/// How a file's thumbnail is generated. See [ProcessSettings::thumbnail].
+///
+/// # Examples
+///
+/// ```
+/// use bevy_ase::process::{ProcessSettings, ThumbnailSettings};
+///
+/// // Preview each file with the first frame of its "idle" tag, at most 32 pixels large.
+/// let settings = ProcessSettings {
+///     thumbnail: Some(ThumbnailSettings {
+///         max_size: 32,
+///         tag: Some("idle".to_owned()),
+///     }),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThumbnailSettings {
+    /// Maximum width and height of the thumbnail in pixels. Defaults to 64.
+    ///
+    /// Larger frames are downscaled with nearest-neighbor sampling, keeping their aspect ratio.
+    pub max_size: u32,
+    /// Name of the tag whose first frame is shown. Defaults to `None`, which shows the
+    /// file's first frame. Files without the tag also show their first frame.
+    pub tag: Option<String>,
+}
+impl Default for ThumbnailSettings {
+    fn default() -> Self {
+        Self {
+            max_size: 64,
+            tag: None,
+        }
+    }
+}
+
+/// How the outline atlas is generated. See [ProcessSettings::outline].
+///
+/// # Examples
+///
+/// ```
+/// use bevy_ase::process::{OutlineSettings, OutlineStyle, ProcessSettings};
+///
+/// // Flash sprites red when hit.
+/// let settings = ProcessSettings {
+///     outline: Some(OutlineSettings {
+///         style: OutlineStyle::Silhouette,
+///         color: [255, 0, 0, 255],
+///     }),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutlineSettings {
+    /// Which pixels are filled. Defaults to [OutlineStyle::Outline].
+    pub style: OutlineStyle,
+    /// Color of the filled pixels as sRGBA. Defaults to opaque white.
+    pub color: [u8; 4],
+}
+impl Default for OutlineSettings {
+    fn default() -> Self {
+        Self {
+            style: OutlineStyle::default(),
+            color: [255; 4],
+        }
+    }
+}
+
+/// Which pixels of a frame an outline atlas fills. See [OutlineSettings].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutlineStyle {
+    /// Transparent pixels next to a visible pixel, see [RawImage::outline].
+    #[default]
+    Outline,
+    /// Visible pixels, see [RawImage::silhouette].
+    Silhouette,
+}
+
+/// How the layers of each frame are composited into images.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_ase::process::{LayerFlattening, ProcessSettings};
+///
+/// // Import "character", "fx" and "shadow" groups as separate images.
+/// let settings = ProcessSettings {
+///     flattening: LayerFlattening::TopLevelGroups,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LayerFlattening {
+    /// All visible layers are composited into one image per frame.
+    #[default]
+    Frames,
+    /// Additionally, each top-level layer or group is composited into its own image per frame,
+    /// from the visible layers it contains. See [ProcessedFrame::layer_images].
+    ///
+    /// Layers within a group are blended with their blend mode and opacity.
+    TopLevelGroups,
+}
+
+/// Size limits for Aseprite files. Each `None` limit is not checked.
+///
+/// Limits protect against allocating huge images for unexpectedly large files,
+/// e.g. on players' machines with little memory.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportLimits {
+    /// Maximum canvas width in pixels.
+    pub max_canvas_width: Option<u32>,
+    /// Maximum canvas height in pixels.
+    pub max_canvas_height: Option<u32>,
+    /// Maximum number of frames.
+    pub max_frames: Option<u32>,
+    /// Maximum size of the texture atlas in bytes.
+    pub max_atlas_bytes: Option<u64>,
+}
+
+/// A file exceeds one of its [ImportLimits].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LimitExceeded {
+    /// The canvas is larger than the maximum width or height.
+    CanvasSize {
+        /// Width of the canvas in pixels.
+        width: u32,
+        /// Height of the canvas in pixels.
+        height: u32,
+    },
+    /// The file has more frames than allowed.
+    FrameCount(u32),
+    /// The texture atlas would need more bytes than allowed.
+    AtlasBytes(u64),
+}
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitExceeded::CanvasSize { width, height } => {
+                write!(f, "Canvas size {}x{} exceeds limit", width, height)
+            }
+            LimitExceeded::FrameCount(frames) => {
+                write!(f, "Frame count {} exceeds limit", frames)
+            }
+            LimitExceeded::AtlasBytes(bytes) => {
+                write!(f, "Atlas size of {} bytes exceeds limit", bytes)
+            }
+        }
+    }
+}
+impl std::error::Error for LimitExceeded {}
+
+// Returns the `key:value` entries in user data text.
+// Entries are separated by whitespace or commas.
+fn user_data_entries(user_data: Option<&UserData>) -> impl Iterator<Item = (&str, &str)> {
+    let text = user_data.and_then(|user_data| user_data.text.as_deref());
+    text.into_iter()
+        .flat_map(|text| text.split(|c: char| c.is_whitespace() || c == ','))
+        .filter_map(|entry| entry.split_once(':'))
+}
+
+// Returns the value of a `key:value` entry in user data text.
+pub(crate) fn user_data_value<'a>(user_data: Option<&'a UserData>, key: &str) -> Option<&'a str> {
+    user_data_entries(user_data)
+        .find(|(entry_key, _)| *entry_key == key)
+        .map(|(_, value)| value)
+}
+
+// Returns the color of user data as sRGBA bytes.
+fn user_data_color(user_data: Option<&UserData>) -> Option<[u8; 4]> {
+    user_data
+        .and_then(|user_data| user_data.color)
+        .map(|color| color.0)
+}
+
+// Parses `event:{name}` and `event:{name}={payload}` entries in user data text.
+fn parse_events(
+    user_data: Option<&UserData>,
+    frame: u32,
+) -> impl Iterator<Item = AnimationEvent> + '_ {
+    user_data_entries(user_data)
+        .filter(|(key, _)| *key == "event")
+        .map(move |(_, value)| {
+            let (name, payload) = match value.split_once('=') {
+                Some((name, payload)) => (name, Some(payload.to_owned())),
+                None => (value, None),
+            };
+            AnimationEvent {
+                frame,
+                name: name.to_owned(),
+                payload,
+            }
+        })
+}
+
+// Returns the events in the user data of every cel, with absolute frame indices.
+fn cel_events(file: &AsepriteFile) -> Vec<AnimationEvent> {
+    (0..file.num_frames())
+        .flat_map(|frame| {
+            (0..file.num_layers()).flat_map(move |layer| {
+                let cel = file.frame(frame).layer(layer);
+                parse_events(cel.user_data(), frame).collect::<Vec<_>>()
+            })
+        })
+        .collect()
+}
+
+/// Checks a file against [ImportLimits], without decoding any images.
+pub fn check_limits(file: &AsepriteFile, limits: &ImportLimits) -> Result<(), LimitExceeded> {
+    let (width, height) = (file.width() as u32, file.height() as u32);
+    let too_wide = limits.max_canvas_width.is_some_and(|max| width > max);
+    let too_high = limits.max_canvas_height.is_some_and(|max| height > max);
+    if too_wide || too_high {
+        return Err(LimitExceeded::CanvasSize { width, height });
+    }
+    let frames = file.num_frames();
+    if limits.max_frames.is_some_and(|max| frames > max) {
+        return Err(LimitExceeded::FrameCount(frames));
+    }
+    let (columns, rows) = atlas_grid(frames);
+    let atlas_bytes = columns as u64 * width as u64 * rows as u64 * height as u64 * 4;
+    if limits.max_atlas_bytes.is_some_and(|max| atlas_bytes > max) {
+        return Err(LimitExceeded::AtlasBytes(atlas_bytes));
+    }
+    Ok(())
+}
+
+/// An image as a raw buffer of 8-bit RGBA pixels, in row-major order.
+///
+/// Images are RGBA whatever the file's color mode. Indexed pixels take their color from
+/// the file's palette, and pixels with the transparent color index are fully transparent
+/// on regular layers. Grayscale pixels become gray RGBA pixels, keeping their alpha.
+/// This applies to frame, atlas and tileset images alike.
+///
+/// Converting into an [Image] uses [TextureFormat::Rgba8UnormSrgb].
+/// Use [RawImage::into_image] to choose another format.
+///
+/// Converting a `RawImage` into an [Image] moves its buffer into the image without
+/// copying. Processing takes each frame's buffer from the decoded Aseprite image the
+/// same way, so a frame's pixels are allocated once between decoding and the asset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawImage {
+    /// Width of the image in pixels.
+    pub width: u32,
+    /// Height of the image in pixels.
+    pub height: u32,
+    /// Pixel data. Contains `width * height * 4` bytes.
+    pub data: Vec<u8>,
+}
+impl From<RawImage> for Image {
+    fn from(raw: RawImage) -> Self {
+        let size = Extent3d {
+            width: raw.width,
+            height: raw.height,
+            depth_or_array_layers: 1,
+        };
+        Image::new(
+            size,
+            TextureDimension::D2,
+            raw.data,
+            TextureFormat::Rgba8UnormSrgb,
+        )
+    }
+}
+impl RawImage {
+    /// Converts the image into an [Image] with the given texture format.
+    ///
+    /// Supports [TextureFormat::Rgba8UnormSrgb] and [TextureFormat::Rgba8Unorm], which keep
+    /// every channel, and [TextureFormat::R8Unorm], which keeps the red channel of each pixel.
+    /// Grayscale files store their values in every color channel, so `R8Unorm` suits masks.
+    pub fn into_image(self, format: TextureFormat) -> Result<Image, UnsupportedFormat> {
+        let data = match format {
+            TextureFormat::Rgba8UnormSrgb | TextureFormat::Rgba8Unorm => self.data,
+            TextureFormat::R8Unorm => self.data.chunks_exact(4).map(|pixel| pixel[0]).collect(),
+            _ => return Err(UnsupportedFormat(format)),
+        };
+        let size = Extent3d {
+            width: self.width,
+            height: self.height,
+            depth_or_array_layers: 1,
+        };
+        Ok(Image::new(size, TextureDimension::D2, data, format))
+    }
+
+    /// Returns a copy of the image which fits within `max_size` pixels in width and height.
+    ///
+    /// Larger images are downscaled with nearest-neighbor sampling, keeping their aspect ratio.
+    /// Smaller images are copied unchanged.
+    pub fn downscaled(&self, max_size: u32) -> RawImage {
+        let max_size = max_size.max(1);
+        let largest = self.width.max(self.height);
+        if largest <= max_size {
+            return self.clone();
+        }
+        let scale = |length: u32| (length as u64 * max_size as u64 / largest as u64).max(1) as u32;
+        let (width, height) = (scale(self.width), scale(self.height));
+        let mut data = Vec::with_capacity((width * height * 4) as usize);
+        for y in 0..height {
+            let source_y = (y as u64 * self.height as u64 / height as u64) as usize;
+            for x in 0..width {
+                let source_x = (x as u64 * self.width as u64 / width as u64) as usize;
+                let start = (source_y * self.width as usize + source_x) * 4;
+                data.extend_from_slice(&self.data[start..start + 4]);
+            }
+        }
+        RawImage {
+            width,
+            height,
+            data,
+        }
+    }
+
+    /// Returns a 1-pixel outline around the image's visible pixels, in the given color.
+    ///
+    /// Transparent pixels which share an edge with a pixel with non-zero alpha take the
+    /// color; all other pixels are transparent. The outline has the image's size, so it
+    /// is cut off where visible pixels touch the image's border.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_ase::process::RawImage;
+    ///
+    /// // A visible pixel between two transparent ones.
+    /// let image = RawImage {
+    ///     width: 3,
+    ///     height: 1,
+    ///     data: vec![0, 0, 0, 0, 9, 9, 9, 255, 0, 0, 0, 0],
+    /// };
+    /// let white = [255, 255, 255, 255];
+    /// assert_eq!(image.outline(white).data, [white, [0; 4], white].concat());
+    /// ```
+    pub fn outline(&self, color: [u8; 4]) -> RawImage {
+        let (width, height) = (self.width as i64, self.height as i64);
+        let visible = |x: i64, y: i64| {
+            (0..width).contains(&x)
+                && (0..height).contains(&y)
+                && self.data[((y * width + x) * 4 + 3) as usize] > 0
+        };
+        let mut data = vec![0; self.data.len()];
+        for (index, pixel) in data.chunks_exact_mut(4).enumerate() {
+            let (x, y) = (index as i64 % width, index as i64 / width);
+            let touches = [(-1, 0), (1, 0), (0, -1), (0, 1)]
+                .into_iter()
+                .any(|(dx, dy)| visible(x + dx, y + dy));
+            if !visible(x, y) && touches {
+                pixel.copy_from_slice(&color);
+            }
+        }
+        RawImage {
+            width: self.width,
+            height: self.height,
+            data,
+        }
+    }
+
+    /// Returns the silhouette of the image: each pixel in the given color, keeping its alpha.
+    ///
+    /// The color's own alpha scales the pixels' alpha.
+    pub fn silhouette(&self, color: [u8; 4]) -> RawImage {
+        let mut data = self.data.clone();
+        for pixel in data.chunks_exact_mut(4) {
+            let alpha = pixel[3] as u32 * color[3] as u32 / 255;
+            pixel.copy_from_slice(&[color[0], color[1], color[2], alpha as u8]);
+        }
+        RawImage {
+            width: self.width,
+            height: self.height,
+            data,
+        }
+    }
+
+    /// Returns a copy of the image at half its width and height, rounded up.
+    ///
+    /// Each pixel averages a block of up to 2x2 pixels, like a mipmap level. Colors are
+    /// weighted by alpha, so transparent pixels don't darken the edges of sprites.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_ase::process::RawImage;
+    ///
+    /// // An opaque white pixel next to a transparent one.
+    /// let image = RawImage {
+    ///     width: 2,
+    ///     height: 1,
+    ///     data: vec![255, 255, 255, 255, 0, 0, 0, 0],
+    /// };
+    /// assert_eq!(image.halved().data, [255, 255, 255, 128]);
+    /// ```
+    pub fn halved(&self) -> RawImage {
+        let (width, height) = ((self.width + 1) / 2, (self.height + 1) / 2);
+        let mut data = Vec::with_capacity((width * height * 4) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                let (mut color, mut alpha, mut count) = ([0u32; 3], 0u32, 0u32);
+                for source_y in y * 2..(y * 2 + 2).min(self.height) {
+                    for source_x in x * 2..(x * 2 + 2).min(self.width) {
+                        let start = ((source_y * self.width + source_x) * 4) as usize;
+                        let pixel = &self.data[start..start + 4];
+                        for channel in 0..3 {
+                            color[channel] += pixel[channel] as u32 * pixel[3] as u32;
+                        }
+                        alpha += pixel[3] as u32;
+                        count += 1;
+                    }
+                }
+                for channel in color {
+                    data.push((channel + alpha / 2).checked_div(alpha).unwrap_or(0) as u8);
+                }
+                data.push(((alpha + count / 2) / count) as u8);
+            }
+        }
+        RawImage {
+            width,
+            height,
+            data,
+        }
+    }
+}
+
+/// Texture formats of the images created from a file. See [RawImage::into_image]
+/// for the supported formats.
+///
+/// By default, all images use [TextureFormat::Rgba8UnormSrgb].
+///
+/// # Examples
+///
+/// ```
+/// use bevy::render::render_resource::TextureFormat;
+/// use bevy_ase::process::{ImageFormats, ProcessSettings};
+///
+/// // Import single-channel masks, e.g. for a dissolve shader.
+/// let settings = ProcessSettings {
+///     formats: ImageFormats {
+///         frames: TextureFormat::R8Unorm,
+///         atlas: TextureFormat::R8Unorm,
+///         ..Default::default()
+///     },
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageFormats {
+    /// Format of frame images. Frame images created lazily from the atlas
+    /// (see [ProcessSettings::keep_frame_images]) use the atlas' format.
+    pub frames: TextureFormat,
+    /// Format of the texture atlas.
+    pub atlas: TextureFormat,
+    /// Format of tileset images.
+    pub tilesets: TextureFormat,
+}
+impl Default for ImageFormats {
+    fn default() -> Self {
+        Self {
+            frames: TextureFormat::Rgba8UnormSrgb,
+            atlas: TextureFormat::Rgba8UnormSrgb,
+            tilesets: TextureFormat::Rgba8UnormSrgb,
+        }
+    }
+}
+
+/// Returns the tight bounds of an image's pixels with non-zero alpha.
+///
+/// Returns `None` if the image is fully transparent.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_ase::process::{opaque_bounds, PixelRect, RawImage};
+///
+/// // A single opaque pixel at (1, 0).
+/// let image = RawImage {
+///     width: 2,
+///     height: 1,
+///     data: vec![0, 0, 0, 0, 0, 0, 0, 255],
+/// };
+/// let bounds = PixelRect { x: 1, y: 0, width: 1, height: 1 };
+/// assert_eq!(opaque_bounds(&image), Some(bounds));
+/// ```
+pub fn opaque_bounds(image: &RawImage) -> Option<PixelRect> {
+    let width = image.width as usize;
+    let opaque = image
+        .data
+        .chunks_exact(4)
+        .enumerate()
+        .filter(|(_, pixel)| pixel[3] > 0)
+        .map(|(index, _)| ((index % width) as u32, (index / width) as u32));
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (u32::MAX, u32::MAX, 0, 0);
+    for (x, y) in opaque {
+        min_x = min_x.min(x);
+        min_y = min_y.min(y);
+        max_x = max_x.max(x);
+        max_y = max_y.max(y);
+    }
+    (min_x <= max_x).then(|| PixelRect {
+        x: min_x,
+        y: min_y,
+        width: max_x - min_x + 1,
+        height: max_y - min_y + 1,
+    })
+}
+
+/// Returns the bounds of the pixels which differ between two images of the same size.
+///
+/// Returns `None` if the images are identical, and the whole image if their sizes differ.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_ase::process::{changed_bounds, PixelRect, RawImage};
+///
+/// // The second pixel turns opaque.
+/// let previous = RawImage {
+///     width: 2,
+///     height: 1,
+///     data: vec![0; 8],
+/// };
+/// let mut image = previous.clone();
+/// image.data[7] = 255;
+/// let bounds = PixelRect { x: 1, y: 0, width: 1, height: 1 };
+/// assert_eq!(changed_bounds(&previous, &image), Some(bounds));
+/// assert_eq!(changed_bounds(&image, &image), None);
+/// ```
+pub fn changed_bounds(previous: &RawImage, image: &RawImage) -> Option<PixelRect> {
+    if (previous.width, previous.height) != (image.width, image.height) {
+        return Some(PixelRect {
+            x: 0,
+            y: 0,
+            width: image.width,
+            height: image.height,
+        });
+    }
+    let width = image.width as usize;
+    let changed = previous
+        .data
+        .chunks_exact(4)
+        .zip(image.data.chunks_exact(4))
+        .enumerate()
+        .filter(|(_, (previous, pixel))| previous != pixel)
+        .map(|(index, _)| ((index % width) as u32, (index / width) as u32));
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (u32::MAX, u32::MAX, 0, 0);
+    for (x, y) in changed {
+        min_x = min_x.min(x);
+        min_y = min_y.min(y);
+        max_x = max_x.max(x);
+        max_y = max_y.max(y);
+    }
+    (min_x <= max_x).then(|| PixelRect {
+        x: min_x,
+        y: min_y,
+        width: max_x - min_x + 1,
+        height: max_y - min_y + 1,
+    })
+}
+
+/// Settings for generating collision shapes from frame images.
+///
+/// Each frame gets the convex hull of its solid pixels, see [ProcessedFrame::collision_hull].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CollisionSettings {
+    /// Pixels with at least this alpha value are solid. Defaults to 128.
+    pub alpha_threshold: u8,
+}
+impl Default for CollisionSettings {
+    fn default() -> Self {
+        Self {
+            alpha_threshold: 128,
+        }
+    }
+}
+
+/// Returns the convex hull of an image's solid pixels, i.e. pixels with at least the given alpha.
+///
+/// Points are pixel corners, measured from the image's top-left corner with y pointing down.
+/// They are ordered counter-clockwise as seen with y pointing up, as in Bevy.
+/// Returns an empty vec if the image has no solid pixels.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_ase::process::{convex_hull, RawImage};
+///
+/// // A single solid pixel.
+/// let image = RawImage {
+///     width: 2,
+///     height: 1,
+///     data: vec![0, 0, 0, 255, 0, 0, 0, 0],
+/// };
+/// assert_eq!(convex_hull(&image, 128), [(0, 1), (1, 1), (1, 0), (0, 0)]);
+/// ```
+pub fn convex_hull(image: &RawImage, alpha_threshold: u8) -> Vec<(u32, u32)> {
+    let row_bytes = image.width as usize * 4;
+    if row_bytes == 0 {
+        return Vec::new();
+    }
+    let mut points = Vec::new();
+    for (y, row) in image.data.chunks_exact(row_bytes).enumerate() {
+        let solid = |x: &usize| row[x * 4 + 3] >= alpha_threshold;
+        let Some(left) = (0..image.width as usize).find(solid) else {
+            continue;
+        };
+        let right = (0..image.width as usize).rev().find(solid).unwrap_or(left) + 1;
+        // Only the outermost pixels of each row can be on the hull.
+        // Flipping y makes the hull's winding counter-clockwise with y pointing up.
+        let (left, right, top, bottom) = (left as i64, right as i64, -(y as i64), -(y as i64 + 1));
+        points.extend([(left, top), (left, bottom), (right, top), (right, bottom)]);
+    }
+    monotone_chain(points)
+        .into_iter()
+        .map(|(x, y)| (x as u32, -y as u32))
+        .collect()
+}
+
+// Andrew's monotone chain algorithm. Returns the hull in counter-clockwise order.
+fn monotone_chain(mut points: Vec<(i64, i64)>) -> Vec<(i64, i64)> {
+    points.sort_unstable();
+    points.dedup();
+    if points.len() < 3 {
+        return points;
+    }
+    let mut hull = Vec::with_capacity(points.len() + 1);
+    push_half_hull(&mut hull, points.iter().copied());
+    push_half_hull(&mut hull, points.iter().rev().copied());
+    hull
+}
+
+// Appends the lower or upper half of a hull. Drops the half's last point,
+// which is the first point of the other half.
+fn push_half_hull(hull: &mut Vec<(i64, i64)>, points: impl Iterator<Item = (i64, i64)>) {
+    let cross = |o: (i64, i64), a: (i64, i64), b: (i64, i64)| {
+        (a.0 - o.0) * (b.1 - o.1) - (a.1 - o.1) * (b.0 - o.0)
+    };
+    let start = hull.len();
+    for point in points {
+        while hull.len() >= start + 2
+            && cross(hull[hull.len() - 2], hull[hull.len() - 1], point) <= 0
+        {
+            hull.pop();
+        }
+        hull.push(point);
+    }
+    hull.pop();
+}
+
+/// A texture format which images can't be converted into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnsupportedFormat(pub TextureFormat);
+impl fmt::Display for UnsupportedFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unsupported texture format {:?}", self.0)
+    }
+}
+impl std::error::Error for UnsupportedFormat {}
+
+/// A rectangle in pixels, with its origin at the top left of an image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PixelRect {
+    /// Horizontal position of the rectangle's left edge.
+    pub x: u32,
+    /// Vertical position of the rectangle's top edge.
+    pub y: u32,
+    /// Width of the rectangle.
+    pub width: u32,
+    /// Height of the rectangle.
+    pub height: u32,
+}
+
+/// A texture atlas containing the images of a tag's frames, packed with
+/// [ProcessSettings::atlas_per_tag].
+///
+/// Frames are laid out like in a [ProcessedAtlas], starting at the tag's first frame:
+/// the atlas index of each frame is its frame index minus [ProcessedTagAtlas::first_frame].
+#[derive(Debug, Clone)]
+pub struct ProcessedTagAtlas {
+    /// The name of the tag.
+    pub tag_name: String,
+    /// Index of the tag's first frame.
+    pub first_frame: u32,
+    /// The atlas of the tag's frames.
+    pub atlas: ProcessedAtlas,
+}
+impl ProcessedTagAtlas {
+    /// Returns true if the atlas contains the given frame.
+    pub fn contains(&self, frame: u32) -> bool {
+        let frame_count = self.atlas.frames.len() as u32;
+        (self.first_frame..self.first_frame + frame_count).contains(&frame)
+    }
+}
+
+/// A texture atlas containing the image of every frame of a file.
+///
+/// Frames are laid out in a grid, in frame order. The atlas index of each frame
+/// is its frame index. Frames are always stored upright: every cell has the size of the
+/// canvas, so rotating frames would not save any space, and Bevy's sprites can't sample
+/// rotated regions. Shaders and tools reading an atlas can rely on this.
+#[derive(Debug, Clone)]
+pub struct ProcessedAtlas {
+    /// The atlas image.
+    pub image: RawImage,
+    /// The region of each frame in the atlas image, indexed by frame index.
+    pub frames: Vec<PixelRect>,
+}
+impl ProcessedAtlas {
+    /// Measures how much of the atlas is covered by visible pixels.
+    ///
+    /// Atlases with a low fill ratio waste texture memory, e.g. on large empty canvas
+    /// margins, and files producing them may be worth trimming or splitting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_ase::process::{pack_atlas, RawImage};
+    ///
+    /// // A single frame, with one of its two pixels visible.
+    /// let image = RawImage {
+    ///     width: 2,
+    ///     height: 1,
+    ///     data: vec![0, 0, 0, 255, 0, 0, 0, 0],
+    /// };
+    /// let usage = pack_atlas(&[&image]).unwrap().usage();
+    /// assert_eq!(usage.fill_ratio(), 0.5);
+    /// assert_eq!(usage.frame_occupancy, [0.5]);
+    /// ```
+    pub fn usage(&self) -> AtlasUsage {
+        let width = self.image.width as usize;
+        let visible =
+            |x: u32, y: u32| self.image.data[(y as usize * width + x as usize) * 4 + 3] > 0;
+        let frame_occupancy = self
+            .frames
+            .iter()
+            .map(|rect| {
+                let area = rect.width as u64 * rect.height as u64;
+                let used = (rect.y..rect.y + rect.height)
+                    .flat_map(|y| (rect.x..rect.x + rect.width).map(move |x| (x, y)))
+                    .filter(|&(x, y)| visible(x, y))
+                    .count();
+                match area {
+                    0 => 0.0,
+                    _ => (used as f64 / area as f64) as f32,
+                }
+            })
+            .collect();
+        AtlasUsage {
+            size: (self.image.width, self.image.height),
+            used_pixels: self
+                .image
+                .data
+                .chunks_exact(4)
+                .filter(|pixel| pixel[3] > 0)
+                .count() as u64,
+            frame_occupancy,
+        }
+    }
+}
+
+/// How much of an atlas is covered by visible pixels, measured with [ProcessedAtlas::usage].
+///
+/// Pixels are visible if their alpha is not zero. Its [Display](fmt::Display) output
+/// summarizes the usage, e.g. `64x64 atlas, 23.4% filled`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AtlasUsage {
+    /// Width and height of the atlas in pixels.
+    pub size: (u32, u32),
+    /// Number of visible pixels in the atlas.
+    pub used_pixels: u64,
+    /// Fraction of each frame's region covered by visible pixels, indexed by atlas index.
+    pub frame_occupancy: Vec<f32>,
+}
+impl AtlasUsage {
+    /// Returns the fraction of the atlas covered by visible pixels, from 0 to 1.
+    ///
+    /// Includes unused grid cells, so it also reflects space lost to the atlas layout.
+    pub fn fill_ratio(&self) -> f32 {
+        let area = self.size.0 as u64 * self.size.1 as u64;
+        match area {
+            0 => 0.0,
+            _ => (self.used_pixels as f64 / area as f64) as f32,
+        }
+    }
+    /// Returns the fraction of the atlas without visible pixels, from 0 to 1.
+    pub fn wasted_ratio(&self) -> f32 {
+        1.0 - self.fill_ratio()
+    }
+}
+impl fmt::Display for AtlasUsage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}x{} atlas, {:.1}% filled",
+            self.size.0,
+            self.size.1,
+            self.fill_ratio() * 100.0
+        )
+    }
+}
+
+/// A list of sRGBA colors, e.g. a variant of a file's palette.
+///
+/// Palettes are read from a file with [Palette::from_file], or parsed from palette files
+/// with [Palette::parse]. Files can have variant palettes in sidecar files next to them,
+/// named `{file_stem}.palette{n}.gpl` or `{file_stem}.palette{n}.hex` for `n` from 1,
+/// e.g. `goblin.palette1.gpl` for `goblin.aseprite`; see [bake_palette_atlas].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Palette {
+    /// The palette's colors as sRGBA, in order.
+    pub colors: Vec<[u8; 4]>,
+}
+impl Palette {
+    /// Extensions of the palette files read by [Palette::parse].
+    pub const EXTENSIONS: [&'static str; 2] = ["gpl", "hex"];
+
+    /// Returns the path of a file's `number`th variant palette, with the given extension.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_ase::process::Palette;
+    /// use std::path::Path;
+    ///
+    /// let path = Palette::variant_path(Path::new("sprites/goblin.aseprite"), 1, "gpl");
+    /// assert_eq!(path, Path::new("sprites/goblin.palette1.gpl"));
+    /// ```
+    pub fn variant_path(file_path: &Path, number: u32, extension: &str) -> PathBuf {
+        file_path.with_extension(format!("palette{}.{}", number, extension))
+    }
+
+    /// Returns the palette of an Aseprite file, if it has one.
+    pub fn from_file(file: &AsepriteFile) -> Option<Self> {
+        let palette = file.palette()?;
+        let colors = (0..palette.num_colors())
+            .map(|index| {
+                palette
+                    .color(index)
+                    .map_or([0; 4], |color| color.raw_rgba8())
+            })
+            .collect();
+        Some(Self { colors })
+    }
+
+    /// Parses a palette file, as saved by Aseprite.
+    ///
+    /// Supports GIMP palettes (`.gpl`), which start with a `GIMP Palette` line, and lists of
+    /// `RRGGBB` hex colors (`.hex`), one per line. Returns `None` if the text is neither.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use bevy_ase::process::Palette;
+    ///
+    /// let hex = Palette::parse("ff0000\n00ff00\n").unwrap();
+    /// let gpl = Palette::parse("GIMP Palette\n#\n255   0   0\tRed\n  0 255   0\n").unwrap();
+    /// assert_eq!(hex.colors, [[255, 0, 0, 255], [0, 255, 0, 255]]);
+    /// assert_eq!(hex, gpl);
+    /// ```
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+        let is_gimp = text.trim_start().starts_with("GIMP Palette");
+        if is_gimp {
+            lines.next();
+        }
+        let mut colors = Vec::new();
+        for line in lines {
+            let color: [u8; 4] = if is_gimp {
+                // Skips comments and the header's `Name:` and `Columns:` entries.
+                if !line.starts_with(|c: char| c.is_ascii_digit()) {
+                    continue;
+                }
+                let mut channels = line
+                    .split_whitespace()
+                    .map(|channel| channel.parse::<u8>().ok());
+                [channels.next()??, channels.next()??, channels.next()??, 255]
+            } else {
+                let hex = line.strip_prefix('#').unwrap_or(line);
+                let value = u32::from_str_radix(hex, 16)
+                    .ok()
+                    .filter(|_| hex.len() == 6)?;
+                let [_, r, g, b] = value.to_be_bytes();
+                [r, g, b, 255]
+            };
+            colors.push(color);
+        }
+        Some(Self { colors })
+    }
+}
+
+/// Recolors an atlas from one palette to another, keeping its layout.
+///
+/// Each pixel whose color is in `base` takes the color at the same index in `variant`,
+/// keeping its own alpha. Colors are compared without alpha, and the first matching
+/// entry of `base` is used. Transparent pixels, and pixels whose color is not in `base`
+/// or whose index is not in `variant`, are copied unchanged. This bakes color variants,
+/// such as differently colored enemies, without shaders.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_ase::process::{bake_palette_atlas, pack_atlas, Palette, RawImage};
+///
+/// let image = RawImage {
+///     width: 2,
+///     height: 1,
+///     data: vec![255, 0, 0, 255, 9, 9, 9, 255],
+/// };
+/// let atlas = pack_atlas(&[&image]).unwrap();
+/// let base = Palette::parse("ff0000").unwrap();
+/// let variant = Palette::parse("0000ff").unwrap();
+/// let baked = bake_palette_atlas(&atlas, &base, &variant);
+/// assert_eq!(baked.image.data, [0, 0, 255, 255, 9, 9, 9, 255]);
+/// ```
+pub fn bake_palette_atlas(
+    atlas: &ProcessedAtlas,
+    base: &Palette,
+    variant: &Palette,
+) -> ProcessedAtlas {
+    let mut indices = HashMap::new();
+    for (index, [r, g, b, _]) in base.colors.iter().copied().enumerate() {
+        indices.entry([r, g, b]).or_insert(index);
+    }
+    let mut image = atlas.image.clone();
+    for pixel in image.data.chunks_exact_mut(4) {
+        if pixel[3] == 0 {
+            continue;
+        }
+        let Some(index) = indices.get(&[pixel[0], pixel[1], pixel[2]]) else {
+            continue;
+        };
+        if let Some(color) = variant.colors.get(*index) {
+            pixel[..3].copy_from_slice(&color[..3]);
+        }
+    }
+    ProcessedAtlas {
+        image,
+        frames: atlas.frames.clone(),
+    }
+}
+
+/// A single frame of an Aseprite file.
+#[derive(Debug, Clone)]
+pub struct ProcessedFrame {
+    /// Index of the frame in the file.
+    pub index: u32,
+    /// Duration of the frame in milliseconds.
+    pub duration_ms: u32,
+    /// The frame's composited image, with the size of the file's canvas.
+    pub image: Option<RawImage>,
+    /// The tight bounds of the frame's pixels with non-zero alpha, see [opaque_bounds].
+    /// `None` if the frame is fully transparent, or if its image was not decoded.
+    pub opaque_bounds: Option<PixelRect>,
+    /// The bounds of the pixels which differ from the previous frame, see [changed_bounds].
+    ///
+    /// The first frame is compared with the last, since animations loop. `None` if no pixel
+    /// changed, and the whole canvas if frame images were not decoded.
+    pub changed_bounds: Option<PixelRect>,
+    /// The convex hull of the frame's solid pixels, see [convex_hull].
+    /// `None` unless [ProcessSettings::collision] is set.
+    pub collision_hull: Option<Vec<(u32, u32)>>,
+    /// The image of each top-level layer or group with the size of the file's canvas,
+    /// from bottom to top, keyed by the layer's name.
+    /// Empty unless [ProcessSettings::flattening] is [LayerFlattening::TopLevelGroups].
+    pub layer_images: Vec<(String, RawImage)>,
+}
+
+/// The color profile of an Aseprite file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorProfile {
+    /// The file's color profile is not known.
+    #[default]
+    Unknown,
+    /// The file has no color profile. Its colors are used as they are.
+    NoProfile,
+    /// The file uses sRGB, like the textures generated by this library.
+    Srgb,
+    /// The file embeds an ICC profile. Its colors may look different in-game than in Aseprite.
+    Icc,
+}
+
+/// How imports treat a file's embedded [ColorProfile].
+///
+/// Images are always converted to RGBA as they are stored in the file. ICC profiles are
+/// not applied to the pixels; convert such files to sRGB in Aseprite instead.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorProfileHandling {
+    /// Report files with an ICC profile, as their colors may differ from Aseprite.
+    /// The [Loader](crate::loader::Loader) sends an
+    /// [AseColorProfileWarning](crate::loader::AseColorProfileWarning) event for them.
+    #[default]
+    Warn,
+    /// Ignore the color profile.
+    Ignore,
+}
+impl ColorProfileHandling {
+    /// Returns true if a file with the given profile should be reported.
+    pub fn warns(&self, profile: ColorProfile) -> bool {
+        *self == ColorProfileHandling::Warn && profile == ColorProfile::Icc
+    }
+}
+
+/// File data read from the raw bytes of an Aseprite file, which asefile does not parse.
+///
+/// The [Loader](crate::loader::Loader) reads the header of every file it loads.
+/// Pass it to [process_file_with_header] when processing files outside of Bevy.
+///
+/// # Examples
+///
+/// ```no_run
+/// use asefile::AsepriteFile;
+/// use bevy_ase::process::{process_file_with_header, FileHeader, ProcessSettings};
+///
+/// let bytes = std::fs::read("assets/sprites/hello.aseprite").unwrap();
+/// let file = AsepriteFile::read(bytes.as_slice()).unwrap();
+/// let header = FileHeader::read(&bytes);
+/// let processed = process_file_with_header(&file, &header, &ProcessSettings::default());
+/// assert_eq!(processed.pixel_ratio, header.pixel_ratio);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileHeader {
+    /// The file's pixel aspect ratio as `(width, height)`. Defaults to `(1, 1)`.
+    pub pixel_ratio: (u32, u32),
+    /// The file's color profile. Defaults to [ColorProfile::Unknown].
+    pub color_profile: ColorProfile,
+    /// The repeat count of each tag, in the file's tag order. `0` repeats forever.
+    ///
+    /// Files saved before Aseprite v1.3 always have `0`. Empty if the file has no tags.
+    pub tag_repeats: Vec<u32>,
+    /// The opacity of each cel which is not fully opaque, keyed by frame index and layer id.
+    ///
+    /// Used to blend frames without some of their layers, e.g. reference layers.
+    pub cel_opacities: HashMap<(u32, u32), u8>,
+}
+impl Default for FileHeader {
+    fn default() -> Self {
+        Self {
+            pixel_ratio: (1, 1),
+            color_profile: ColorProfile::Unknown,
+            tag_repeats: Vec::new(),
+            cel_opacities: HashMap::default(),
+        }
+    }
+}
+impl FileHeader {
+    // Size of the fixed header at the start of every file.
+    const SIZE: usize = 128;
+    // Size of the header at the start of every frame.
+    const FRAME_HEADER_SIZE: usize = 16;
+    const COLOR_PROFILE_CHUNK: u16 = 0x2007;
+    const TAGS_CHUNK: u16 = 0x2018;
+    const CEL_CHUNK: u16 = 0x2005;
+
+    /// Reads the header from the bytes of an Aseprite file.
+    ///
+    /// Values which can't be read, e.g. from truncated bytes, keep their defaults.
+    pub fn read(bytes: &[u8]) -> Self {
+        let mut header = Self::default();
+        if bytes.len() < Self::SIZE {
+            return header;
+        }
+        // Pixel width and height are single bytes at offsets 34 and 35; 0 means 1:1.
+        let (width, height) = (bytes[34], bytes[35]);
+        if width > 0 && height > 0 {
+            header.pixel_ratio = (width as u32, height as u32);
+        }
+        // Files without a color profile chunk predate color profiles.
+        header.color_profile = ColorProfile::NoProfile;
+        for (frame, chunk_type, data) in chunks(bytes) {
+            match chunk_type {
+                // Cels start with their layer id, position and opacity.
+                Self::CEL_CHUNK => {
+                    if let (Some(layer), Some(&opacity)) = (le_u16(data, 0), data.get(6)) {
+                        if opacity < u8::MAX {
+                            header.cel_opacities.insert((frame, layer as u32), opacity);
+                        }
+                    }
+                }
+                // The color profile and tags are stored in the first frame.
+                _ if frame > 0 => {}
+                Self::COLOR_PROFILE_CHUNK => {
+                    header.color_profile = match le_u16(data, 0) {
+                        Some(0) => ColorProfile::NoProfile,
+                        Some(1) => ColorProfile::Srgb,
+                        Some(2) => ColorProfile::Icc,
+                        _ => ColorProfile::Unknown,
+                    };
+                }
+                Self::TAGS_CHUNK => header.tag_repeats = tag_repeats(data),
+                _ => {}
+            }
+        }
+        header
+    }
+}
+
+// Reads the repeat count of each tag in a tags chunk, up to the first truncated tag.
+fn tag_repeats(data: &[u8]) -> Vec<u32> {
+    let tag_count = le_u16(data, 0).unwrap_or(0);
+    // Each tag starts with from, to, direction, repeat, reserved bytes and a color,
+    // followed by its name as a length-prefixed string.
+    let mut offset = 10;
+    let mut repeats = Vec::new();
+    for _ in 0..tag_count {
+        let (repeat, name_len) = match (le_u16(data, offset + 5), le_u16(data, offset + 17)) {
+            (Some(repeat), Some(name_len)) => (repeat, name_len),
+            _ => break,
+        };
+        repeats.push(repeat as u32);
+        offset += 19 + name_len as usize;
+    }
+    repeats
+}
+
+// Returns the frame index, type and data of each chunk in a file's bytes.
+fn chunks(bytes: &[u8]) -> impl Iterator<Item = (u32, u16, &[u8])> {
+    let frame_count = le_u16(bytes, 6).unwrap_or(0) as u32;
+    let mut frames = bytes.get(FileHeader::SIZE..).unwrap_or_default();
+    (0..frame_count)
+        .map_while(move |index| {
+            let frame_size = le_u32(frames, 0)? as usize;
+            let frame = frames.get(..frame_size)?;
+            frames = &frames[frame_size..];
+            Some((index, frame))
+        })
+        .flat_map(|(index, frame)| {
+            frame_chunks(frame).map(move |(chunk_type, data)| (index, chunk_type, data))
+        })
+}
+
+// Returns the type and data of each chunk in a frame's bytes.
+fn frame_chunks(frame: &[u8]) -> impl Iterator<Item = (u16, &[u8])> {
+    // The old 16-bit chunk count is used if the 32-bit one is 0.
+    let chunk_count = match le_u32(frame, 12) {
+        Some(0) | None => le_u16(frame, 6).unwrap_or(0) as u32,
+        Some(count) => count,
+    };
+    let mut remaining = frame
+        .get(FileHeader::FRAME_HEADER_SIZE..)
+        .unwrap_or_default();
+    (0..chunk_count).map_while(move |_| {
+        let size = le_u32(remaining, 0)? as usize;
+        let chunk_type = le_u16(remaining, 4)?;
+        let data = remaining.get(6..size)?;
+        remaining = &remaining[size..];
+        Some((chunk_type, data))
+    })
+}
+
+fn le_u16(bytes: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        bytes.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn le_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        bytes.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+/// A sequence of frames in an Aseprite file.
+#[derive(Debug, Clone)]
+pub struct ProcessedAnimation {
+    /// The name of the tag this animation was created from.
+    /// `None` for the animation spanning all frames of the file.
+    pub tag_name: Option<String>,
+    /// Indices of the animation's frames.
+    pub frames: Vec<u32>,
+    /// How many times the tag's frames repeat, as set in Aseprite.
+    /// `0` repeats forever. `None` if the repeat count is unknown.
+    pub repeat: Option<u32>,
+    /// Playback speed multiplier, read from the tag's user data text (e.g. `speed:1.5`).
+    /// Defaults to 1.
+    pub speed: f32,
+    /// Events authored in the user data of the tag and its cels, sorted by frame.
+    /// Event frames are relative to the animation's first frame.
+    pub events: Vec<AnimationEvent>,
+    /// The tag's color as sRGBA, as set in Aseprite's tag properties.
+    /// `None` for the animation spanning all frames of the file.
+    pub color: Option<[u8; 4]>,
+    /// Whether the animation's frames are shown mirrored horizontally.
+    pub flip_x: bool,
+    /// Whether the animation's frames are shown mirrored vertically.
+    pub flip_y: bool,
+}
+impl ProcessedAnimation {
+    fn from_file(file: &AsepriteFile, frame_events: &[AnimationEvent]) -> Self {
+        Self {
+            tag_name: None,
+            frames: (0..file.num_frames()).collect(),
+            repeat: None,
+            speed: 1.0,
+            events: frame_events.to_vec(),
+            color: None,
+            flip_x: false,
+            flip_y: false,
+        }
+    }
+    fn from_tag(tag: &Tag, repeat: Option<u32>, frame_events: &[AnimationEvent]) -> Self {
+        let (from, to) = (tag.from_frame(), tag.to_frame());
+        let tag_events = parse_events(tag.user_data(), 0);
+        let cel_events = frame_events
+            .iter()
+            .filter(|event| (from..=to).contains(&event.frame))
+            .map(|event| AnimationEvent {
+                frame: event.frame - from,
+                ..event.clone()
+            });
+        Self {
+            tag_name: Some(tag.name().to_owned()),
+            frames: (from..to + 1).collect(),
+            events: tag_events.chain(cel_events).collect(),
+            repeat,
+            speed: user_data_value(tag.user_data(), "speed")
+                .and_then(|speed| speed.parse().ok())
+                .unwrap_or(1.0),
+            color: user_data_color(tag.user_data()),
+            flip_x: false,
+            flip_y: false,
+        }
+    }
+    // Returns the mirrored variants of a tag's animation, declared in its user data text as
+    // `mirror:{axes}={tag_name}` entries, e.g. `mirror:x=walk_left`. Axes are `x`, `y` or `xy`.
+    fn mirrored(&self, tag: &Tag) -> Vec<Self> {
+        user_data_entries(tag.user_data())
+            .filter(|(key, _)| *key == "mirror")
+            .filter_map(|(_, value)| {
+                let (axes, tag_name) = value.split_once('=')?;
+                let (flip_x, flip_y) = match axes {
+                    "x" => (true, false),
+                    "y" => (false, true),
+                    "xy" | "yx" => (true, true),
+                    _ => return None,
+                };
+                Some(Self {
+                    tag_name: Some(tag_name.to_owned()),
+                    flip_x: self.flip_x != flip_x,
+                    flip_y: self.flip_y != flip_y,
+                    ..self.clone()
+                })
+            })
+            .collect()
+    }
+}
+
+/// A tag standing for another file's animation, set as an `alias:{path}#{tag_name}`
+/// entry in the tag's user data text, e.g. `alias:../hero.aseprite#walk`.
+///
+/// No animation is imported from the tag's own frames, so characters can share
+/// animations without duplicating them. Instead, the [Loader](crate::loader::Loader)
+/// registers the tag's name with [AseAssetMap::add_external_alias](crate::asset::AseAssetMap::add_external_alias).
+/// Like other user data entries, the path can't contain spaces or commas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalAlias {
+    /// The name of the tag.
+    pub tag_name: String,
+    /// Path of the other file, relative to the folder of the file with the tag.
+    pub path: PathBuf,
+    /// Tag name of the animation in the other file.
+    pub target_tag: String,
+}
+impl ExternalAlias {
+    fn from_tag(tag: &Tag) -> Option<Self> {
+        let (path, target_tag) = user_data_value(tag.user_data(), "alias")?.split_once('#')?;
+        Some(Self {
+            tag_name: tag.name().to_owned(),
+            path: PathBuf::from(path),
+            target_tag: target_tag.to_owned(),
+        })
+    }
+}
+
+/// A layer of an Aseprite file.
+#[derive(Debug, Clone)]
+pub struct ProcessedLayer {
+    /// Name of the layer.
+    pub name: String,
+    /// The layer's color as sRGBA, as set in Aseprite's layer properties.
+    pub color: Option<[u8; 4]>,
+    /// The layer's z-order, set as a `z:{value}` entry in its user data text, e.g. `z:5`.
+    pub z_order: Option<f32>,
+}
+impl ProcessedLayer {
+    fn new(layer: Layer) -> Self {
+        Self {
+            name: layer.name().to_owned(),
+            color: user_data_color(layer.user_data()),
+            z_order: user_data_value(layer.user_data(), "z").and_then(|z| z.parse().ok()),
+        }
+    }
+}
+
+/// A tileset of an Aseprite file.
+#[derive(Debug)]
+pub struct ProcessedTileset {
+    /// Tileset id.
+    pub id: u32,
+    /// Name of the tileset.
+    pub name: String,
+    /// Number of tiles in the tileset.
+    pub tile_count: u32,
+    /// Pixel size of the tileset's tiles.
+    pub tile_size: TileSize,
+    /// The tileset's image. All tiles are stored in a vertical strip.
+    pub image: Option<RawImage>,
+}
+impl ProcessedTileset {
+    fn new(
+        file: &AsepriteFile,
+        ase_tileset: &asefile::Tileset,
+        settings: &ProcessSettings,
+    ) -> TilesetResult<Self> {
+        let image = if settings.tileset_images {
+            let tileset_id = ase_tileset.id();
+            let tileset = file
+                .tilesets()
+                .get(tileset_id)
+                .ok_or(TilesetError::MissingId(tileset_id))?;
+            let image = tileset.image();
+            Some(RawImage {
+                width: image.width(),
+                height: image.height(),
+                data: image.into_raw(),
+            })
+        } else {
+            None
+        };
+        Ok(Self {
+            id: ase_tileset.id(),
+            name: ase_tileset.name().to_string(),
+            tile_count: ase_tileset.tile_count(),
+            tile_size: TileSize::from_ase(&ase_tileset.tile_size()),
+            image,
+        })
+    }
+}
+
+/// All data processed from an Aseprite file, independent of Bevy resources.
+#[derive(Debug)]
+pub struct ProcessedFile {
+    /// Width of the file's canvas in pixels.
+    pub width: u32,
+    /// Height of the file's canvas in pixels.
+    pub height: u32,
+    /// The file's pixel aspect ratio as `(width, height)`.
+    /// For example, `(2, 1)` means pixels are twice as wide as they are high.
+    pub pixel_ratio: (u32, u32),
+    /// The file's color profile.
+    pub color_profile: ColorProfile,
+    /// The file's frames, in order.
+    pub frames: Vec<ProcessedFrame>,
+    /// The file's animations. The first animation spans all frames;
+    /// it is followed by one animation per tag, each followed by its mirrored variants.
+    /// Tags which are [external aliases](ExternalAlias) have no animation.
+    pub animations: Vec<ProcessedAnimation>,
+    /// Tags standing for another file's animation.
+    pub external_aliases: Vec<ExternalAlias>,
+    /// The file's tilesets.
+    pub tilesets: Vec<ProcessedTileset>,
+    /// The file's slices.
+    pub slices: Vec<Slice>,
+    /// The file's layers, from bottom to top.
+    pub layers: Vec<ProcessedLayer>,
+    /// A texture atlas of all frame images.
+    pub atlas: Option<ProcessedAtlas>,
+    /// A texture atlas of the shadow split off with [ProcessSettings::shadow_layer].
+    ///
+    /// Holds a single image if the shadow is the same in every frame, and otherwise
+    /// one image per frame, laid out like [ProcessedFile::atlas].
+    pub shadow: Option<ProcessedAtlas>,
+    /// Downscaled copies of [ProcessedFile::atlas], generated with [ProcessSettings::lod_levels].
+    ///
+    /// The first atlas is at 1/2 size, and each following one at half the size of the previous.
+    /// Frames are laid out like in the full-size atlas, each at half the size, rounded up.
+    pub lod_atlases: Vec<ProcessedAtlas>,
+    /// An atlas of each frame's outline or silhouette, generated with [ProcessSettings::outline].
+    ///
+    /// Frames are laid out like in [ProcessedFile::atlas], so the outline can be shown by a
+    /// second sprite on the same atlas index.
+    pub outline: Option<ProcessedAtlas>,
+    /// One atlas per tag, in tag order, packed with [ProcessSettings::atlas_per_tag].
+    pub tag_atlases: Vec<ProcessedTagAtlas>,
+    /// A small preview image, generated with [ProcessSettings::thumbnail].
+    pub thumbnail: Option<RawImage>,
+    /// Text regions of UI mockups, split off from the slices with [ProcessSettings::text_slices].
+    pub text_placeholders: Vec<UiTextPlaceholder>,
+    /// How many pixels make up one world unit, from the file's layers or
+    /// [ProcessSettings::pixels_per_unit]. Always positive.
+    pub pixels_per_unit: Option<f32>,
+    /// Descriptions of the file's contents which could not be processed.
+    ///
+    /// Unsupported contents, such as tilemap data written by a newer Aseprite version,
+    /// are skipped while the rest of the file is still processed.
+    pub unsupported: Vec<String>,
+}
+
+impl ProcessedFile {
+    /// Returns true if some of the file's contents could not be processed.
+    pub fn is_partial(&self) -> bool {
+        !self.unsupported.is_empty()
+    }
+}
+
+/// Processes an Aseprite file into plain data.
+///
+/// This is the same processing the [Loader](crate::loader::Loader) performs before
+/// moving data into Bevy's [Assets]. External tools can use it to bake or inspect
+/// files exactly as the runtime loader would see them.
+///
+/// Data which asefile does not parse, such as the pixel ratio, keeps its default values.
+/// Use [process_file_with_header] to include it.
+///
+/// # Examples
+///
+/// ```no_run
+/// use asefile::AsepriteFile;
+/// use bevy_ase::process::{process_file, ProcessSettings};
+/// use std::path::Path;
+///
+/// let file = AsepriteFile::read_file(Path::new("assets/sprites/hello.aseprite")).unwrap();
+/// let processed = process_file(&file, &ProcessSettings::default());
+/// for animation in &processed.animations {
+///     println!("{:?}: {} frames", animation.tag_name, animation.frames.len());
+/// }
+/// ```
+pub fn process_file(file: &AsepriteFile, settings: &ProcessSettings) -> ProcessedFile {
+    process_file_with_header(file, &FileHeader::default(), settings)
+}
+
+/// Processes an Aseprite file into plain data, like [process_file], including the
+/// data read from the file's raw bytes into a [FileHeader].
+pub fn process_file_with_header(
+    file: &AsepriteFile,
+    header: &FileHeader,
+    settings: &ProcessSettings,
+) -> ProcessedFile {
+    let mut frames = process_frames(file, header, settings);
+    set_changed_bounds(&mut frames, file.width() as u32, file.height() as u32);
+    let frame_events = cel_events(file);
+    let external_aliases: Vec<ExternalAlias> = (0..file.num_tags())
+        .filter_map(|tag_id| ExternalAlias::from_tag(file.tag(tag_id)))
+        .collect();
+    let tag_animations = (0..file.num_tags())
+        .map(|tag_id| (tag_id, file.tag(tag_id)))
+        .filter(|(_, tag)| ExternalAlias::from_tag(tag).is_none())
+        .flat_map(|(tag_id, tag)| {
+            let repeat = header.tag_repeats.get(tag_id as usize).copied();
+            let animation = ProcessedAnimation::from_tag(tag, repeat, &frame_events);
+            let mirrored = animation.mirrored(tag);
+            std::iter::once(animation).chain(mirrored)
+        });
+    let mut animations = vec![ProcessedAnimation::from_file(file, &frame_events)];
+    animations.extend(tag_animations);
+    let mut unsupported = Vec::new();
+    let mut tilesets = Vec::new();
+    for tileset in file.tilesets().iter() {
+        match ProcessedTileset::new(file, tileset, settings) {
+            Ok(tileset) => tilesets.push(tileset),
+            Err(error) => unsupported.push(error.to_string()),
+        }
+    }
+    let mut slices: Vec<Slice> = file.slices().iter().map(Slice::from_ase).collect();
+    let mut text_placeholders = Vec::new();
+    if settings.text_slices {
+        slices.retain(|slice| match text_placeholder(slice) {
+            Some(placeholder) => {
+                text_placeholders.push(placeholder);
+                false
+            }
+            None => true,
+        });
+    }
+    let layers = (0..file.num_layers())
+        .map(|id| ProcessedLayer::new(file.layer(id)))
+        .collect();
+    // asefile does not read file-level user data, so files set their value on a layer.
+    let pixels_per_unit = (0..file.num_layers())
+        .find_map(|id| {
+            user_data_value(file.layer(id).user_data(), "ppu")?
+                .parse()
+                .ok()
+        })
+        .or(settings.pixels_per_unit)
+        .filter(|pixels_per_unit: &f32| *pixels_per_unit > 0.0);
+    let atlas = if settings.atlas && !settings.atlas_per_tag {
+        build_atlas(&frames)
+    } else {
+        None
+    };
+    let tag_atlases = if settings.atlas && settings.atlas_per_tag {
+        build_tag_atlases(file, &frames)
+    } else {
+        Vec::new()
+    };
+    let lod_atlases = match atlas {
+        Some(_) => build_lod_atlases(&frames, settings.lod_levels),
+        None => Vec::new(),
+    };
+    let outline = settings
+        .outline
+        .filter(|_| atlas.is_some())
+        .and_then(|outline| build_outline_atlas(&frames, outline));
+    let shadow = shadow_layer_id(file, settings)
+        .filter(|_| settings.frame_images)
+        .and_then(|shadow_id| build_shadow_atlas(file, header, shadow_id, settings));
+    let thumbnail = settings
+        .thumbnail
+        .as_ref()
+        .and_then(|thumbnail| build_thumbnail(file, &frames, thumbnail));
+    if atlas.is_some() && !settings.keep_frame_images {
+        frames.iter_mut().for_each(|frame| frame.image = None);
+    }
+    ProcessedFile {
+        width: file.width() as u32,
+        height: file.height() as u32,
+        pixel_ratio: header.pixel_ratio,
+        color_profile: header.color_profile,
+        frames,
+        animations,
+        tilesets,
+        slices,
+        layers,
+        atlas,
+        shadow,
+        lod_atlases,
+        outline,
+        tag_atlases,
+        thumbnail,
+        text_placeholders,
+        pixels_per_unit,
+        external_aliases,
+        unsupported,
+    }
+}
+
+// Makes a placeholder from a slice whose user data text starts with a `text:{key}` entry.
+pub(crate) fn text_placeholder(slice: &Slice) -> Option<UiTextPlaceholder> {
+    let text = slice.user_data.as_ref()?.text.as_deref()?;
+    let key = text.trim_start().strip_prefix("text:")?;
+    let key = key.split(|c: char| c.is_whitespace() || c == ',').next()?;
+    if key.is_empty() {
+        return None;
+    }
+    Some(UiTextPlaceholder {
+        name: slice.name.clone(),
+        key: key.to_string(),
+        rect: slice::rect(slice.keys.first()?),
+    })
+}
+
+// Downscales the first frame of the thumbnail's tag, or the file's first frame.
+fn build_thumbnail(
+    file: &AsepriteFile,
+    frames: &[ProcessedFrame],
+    thumbnail: &ThumbnailSettings,
+) -> Option<RawImage> {
+    let tag_frame = thumbnail.tag.as_deref().and_then(|tag_name| {
+        (0..file.num_tags())
+            .map(|tag_id| file.tag(tag_id))
+            .find(|tag| tag.name() == tag_name)
+            .map(|tag| tag.from_frame())
+    });
+    let frame = frames.get(tag_frame.unwrap_or(0) as usize)?;
+    Some(frame.image.as_ref()?.downscaled(thumbnail.max_size))
+}
+
+// Composites the shadow layer of each frame, keeping only one image if they are all equal.
+fn build_shadow_atlas(
+    file: &AsepriteFile,
+    header: &FileHeader,
+    shadow_id: u32,
+    settings: &ProcessSettings,
+) -> Option<ProcessedAtlas> {
+    let mut images: Vec<RawImage> = (0..file.num_frames())
+        .map(|index| {
+            composite_layers(file, header, index, |layer| {
+                includes_layer(layer, settings) && top_level_id(file, layer) == shadow_id
+            })
+        })
+        .collect();
+    if images.windows(2).all(|pair| pair[0].data == pair[1].data) {
+        images.truncate(1);
+    }
+    pack_atlas(&images.iter().collect::<Vec<_>>())
+}
+
+fn build_outline_atlas(
+    frames: &[ProcessedFrame],
+    outline: OutlineSettings,
+) -> Option<ProcessedAtlas> {
+    let images: Vec<RawImage> = frames
+        .iter()
+        .map(|frame| {
+            let image = frame.image.as_ref()?;
+            Some(match outline.style {
+                OutlineStyle::Outline => image.outline(outline.color),
+                OutlineStyle::Silhouette => image.silhouette(outline.color),
+            })
+        })
+        .collect::<Option<_>>()?;
+    pack_atlas(&images.iter().collect::<Vec<_>>())
+}
+
+// Halves each frame image once per level, packing an atlas at every level.
+fn build_lod_atlases(frames: &[ProcessedFrame], levels: u32) -> Vec<ProcessedAtlas> {
+    let mut images: Vec<RawImage> = Vec::new();
+    let mut atlases = Vec::new();
+    for level in 0..levels {
+        images = match level {
+            0 => frames
+                .iter()
+                .filter_map(|frame| Some(frame.image.as_ref()?.halved()))
+                .collect(),
+            _ => images.iter().map(RawImage::halved).collect(),
+        };
+        atlases.extend(pack_atlas(&images.iter().collect::<Vec<_>>()));
+    }
+    atlases
+}
+
+// Packs the frames of each tag into their own atlas.
+fn build_tag_atlases(file: &AsepriteFile, frames: &[ProcessedFrame]) -> Vec<ProcessedTagAtlas> {
+    (0..file.num_tags())
+        .filter_map(|tag_id| {
+            let tag = file.tag(tag_id);
+            if ExternalAlias::from_tag(tag).is_some() {
+                return None;
+            }
+            let range = tag.from_frame() as usize..tag.to_frame() as usize + 1;
+            let atlas = build_atlas(frames.get(range)?)?;
+            Some(ProcessedTagAtlas {
+                tag_name: tag.name().to_owned(),
+                first_frame: tag.from_frame(),
+                atlas,
+            })
+        })
+        .collect()
+}
+
+fn build_atlas(frames: &[ProcessedFrame]) -> Option<ProcessedAtlas> {
+    let images: Vec<&RawImage> = frames
+        .iter()
+        .map(|frame| frame.image.as_ref())
+        .collect::<Option<_>>()?;
+    pack_atlas(&images)
+}
+
+/// Packs images of equal size into a grid-shaped texture atlas, in order.
+///
+/// Returns `None` if `images` is empty or the images differ in size.
+///
+/// The atlas is filled one destination row at a time with contiguous copies.
+/// With the "rayon" feature enabled, rows are filled in parallel.
+pub fn pack_atlas(images: &[&RawImage]) -> Option<ProcessedAtlas> {
+    let first = images.first()?;
+    let (cell_width, cell_height) = (first.width, first.height);
+    if images
+        .iter()
+        .any(|image| image.width != cell_width || image.height != cell_height)
+    {
+        return None;
+    }
+    let count = images.len() as u32;
+    let (columns, rows) = atlas_grid(count);
+    let mut atlas = RawImage {
+        width: columns * cell_width,
+        height: rows * cell_height,
+        data: vec![0; (columns * cell_width) as usize * (rows * cell_height) as usize * 4],
+    };
+    fill_atlas_rows(&mut atlas, images, columns as usize, cell_height as usize);
+    let rects = (0..count)
+        .map(|index| PixelRect {
+            x: (index % columns) * cell_width,
+            y: (index / columns) * cell_height,
+            width: cell_width,
+            height: cell_height,
+        })
+        .collect();
+    Some(ProcessedAtlas {
+        image: atlas,
+        frames: rects,
+    })
+}
+
+// Number of columns and rows of a grid atlas with `count` cells.
+fn atlas_grid(count: u32) -> (u32, u32) {
+    let columns = ((count as f64).sqrt().ceil() as u32).max(1);
+    let rows = (count + columns - 1) / columns;
+    (columns, rows)
+}
+
+// Fills each row of a grid atlas with the matching row of every image in that grid row.
+fn fill_atlas_rows(atlas: &mut RawImage, images: &[&RawImage], columns: usize, cell_height: usize) {
+    let stride = atlas.width as usize * 4;
+    let row_len = stride / columns;
+    let fill_row = |(y, dst_row): (usize, &mut [u8])| {
+        let (grid_row, cell_y) = (y / cell_height, y % cell_height);
+        let cells = images.iter().skip(grid_row * columns).take(columns);
+        for (dst, image) in dst_row.chunks_exact_mut(row_len).zip(cells) {
+            dst.copy_from_slice(&image.data[cell_y * row_len..(cell_y + 1) * row_len]);
+        }
+    };
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        atlas
+            .data
+            .par_chunks_exact_mut(stride)
+            .enumerate()
+            .for_each(fill_row);
+    }
+    #[cfg(not(feature = "rayon"))]
+    atlas
+        .data
+        .chunks_exact_mut(stride)
+        .enumerate()
+        .for_each(fill_row);
+}
+
+fn process_frames(
+    file: &AsepriteFile,
+    header: &FileHeader,
+    settings: &ProcessSettings,
+) -> Vec<ProcessedFrame> {
+    let num_frames = file.num_frames();
+    let threads = std::thread::available_parallelism().map_or(1, |threads| threads.get());
+    if !decodes_frames(settings)
+        || threads < 2
+        || (num_frames as usize) < settings.parallel_frames_threshold
+    {
+        return (0..num_frames)
+            .map(|index| process_frame(file, header, index, settings))
+            .collect();
+    }
+    // Split the frame range into one chunk per thread. Scoped threads are used instead of a
+    // task pool, as this may already run on the pool and would block one of its threads.
+    let chunk_size = (num_frames as usize / threads).max(1);
+    std::thread::scope(|scope| {
+        let chunks: Vec<_> = (0..num_frames)
+            .step_by(chunk_size)
+            .map(|start| {
+                let end = (start + chunk_size as u32).min(num_frames);
+                scope.spawn(move || {
+                    (start..end)
+                        .map(|index| process_frame(file, header, index, settings))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        // Panics on frame threads are passed on to the caller.
+        chunks
+            .into_iter()
+            .flat_map(|chunk| {
+                chunk
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect()
+    })
+}
+
+// Compares each frame with the previous one, and the first frame with the last.
+fn set_changed_bounds(frames: &mut [ProcessedFrame], width: u32, height: u32) {
+    let canvas = PixelRect {
+        x: 0,
+        y: 0,
+        width,
+        height,
+    };
+    let changes: Vec<Option<PixelRect>> = (0..frames.len())
+        .map(|index| {
+            let previous = (index + frames.len() - 1) % frames.len();
+            match (&frames[previous].image, &frames[index].image) {
+                (Some(previous), Some(image)) => changed_bounds(previous, image),
+                _ => Some(canvas),
+            }
+        })
+        .collect();
+    for (frame, changed) in frames.iter_mut().zip(changes) {
+        frame.changed_bounds = changed;
+    }
+}
+
+// Frame images are also decoded to generate collision shapes.
+fn decodes_frames(settings: &ProcessSettings) -> bool {
+    settings.frame_images || settings.collision.is_some()
+}
+
+fn process_frame(
+    file: &AsepriteFile,
+    header: &FileHeader,
+    index: u32,
+    settings: &ProcessSettings,
+) -> ProcessedFrame {
+    let frame = file.frame(index);
+    let image = decodes_frames(settings).then(|| {
+        let shadow_id = shadow_layer_id(file, settings);
+        let excludes_references = !settings.reference_layers && has_visible_reference_layer(file);
+        if shadow_id.is_some() || excludes_references {
+            return composite_layers(file, header, index, |layer| {
+                includes_layer(layer, settings) && Some(top_level_id(file, layer)) != shadow_id
+            });
+        }
+        let image = frame.image();
+        RawImage {
+            width: image.width(),
+            height: image.height(),
+            data: image.into_raw(),
+        }
+    });
+    let collision_hull = settings
+        .collision
+        .zip(image.as_ref())
+        .map(|(collision, image)| convex_hull(image, collision.alpha_threshold));
+    let layer_images = match settings.flattening {
+        LayerFlattening::TopLevelGroups if settings.frame_images => {
+            top_level_layer_images(file, header, index, settings)
+        }
+        _ => Vec::new(),
+    };
+    ProcessedFrame {
+        index,
+        duration_ms: frame.duration(),
+        opaque_bounds: image.as_ref().and_then(opaque_bounds),
+        // Set once all frames are decoded, see set_changed_bounds.
+        changed_bounds: None,
+        image: image.filter(|_| settings.frame_images),
+        collision_hull,
+        layer_images,
+    }
+}
+
+fn is_reference_layer(layer: &Layer) -> bool {
+    layer.flags().contains(LayerFlags::REFERENCE)
+}
+
+// Returns true if the layer and all of its parent groups are visible.
+fn is_shown(layer: &Layer) -> bool {
+    layer.is_visible() && layer.parent().map_or(true, |parent| is_shown(&parent))
+}
+
+fn has_visible_reference_layer(file: &AsepriteFile) -> bool {
+    (0..file.num_layers())
+        .map(|id| file.layer(id))
+        .any(|layer| is_shown(&layer) && is_reference_layer(&layer))
+}
+
+fn top_level_layer(layer: Layer) -> Layer {
+    match layer.parent() {
+        Some(parent) => top_level_layer(parent),
+        None => layer,
+    }
+}
+
+fn top_level_id(file: &AsepriteFile, layer: &Layer) -> u32 {
+    top_level_layer(file.layer(layer.id())).id()
+}
+
+// Returns false for reference layers, unless they are included by the settings.
+fn includes_layer(layer: &Layer, settings: &ProcessSettings) -> bool {
+    settings.reference_layers || !is_reference_layer(layer)
+}
+
+// Returns the id of the top-level layer named by ProcessSettings::shadow_layer.
+fn shadow_layer_id(file: &AsepriteFile, settings: &ProcessSettings) -> Option<u32> {
+    let name = settings.shadow_layer.as_deref()?;
+    (0..file.num_layers())
+        .map(|id| file.layer(id))
+        .find(|layer| layer.parent().is_none() && layer.name() == name)
+        .map(|layer| layer.id())
+}
+
+// Composites each top-level layer or group of a frame into its own image.
+fn top_level_layer_images(
+    file: &AsepriteFile,
+    header: &FileHeader,
+    index: u32,
+    settings: &ProcessSettings,
+) -> Vec<(String, RawImage)> {
+    (0..file.num_layers())
+        .map(|id| file.layer(id))
+        .filter(|layer| layer.parent().is_none())
+        .map(|group| {
+            let image = composite_layers(file, header, index, |layer| {
+                includes_layer(layer, settings) && top_level_id(file, layer) == group.id()
+            });
+            (group.name().to_owned(), image)
+        })
+        .collect()
+}
+
+// Composites a frame from the visible layers accepted by `include`.
+// asefile composites every visible layer, so this blends the layers itself, with their
+// blend mode and the layer and cel opacity.
+pub(crate) fn composite_layers(
+    file: &AsepriteFile,
+    header: &FileHeader,
+    index: u32,
+    include: impl Fn(&Layer) -> bool,
+) -> RawImage {
+    let (width, height) = (file.width() as u32, file.height() as u32);
+    let mut data = vec![0; width as usize * height as usize * 4];
+    for id in 0..file.num_layers() {
+        let layer = file.layer(id);
+        if !is_shown(&layer) || !include(&layer) {
+            continue;
+        }
+        let cel_opacity = header.cel_opacities.get(&(index, id)).copied();
+        let opacity = mul_un8(layer.opacity(), cel_opacity.unwrap_or(u8::MAX));
+        let mode = layer.blend_mode();
+        let cel = layer.frame(index).image();
+        for (dst, src) in data.chunks_exact_mut(4).zip(cel.pixels()) {
+            blend(mode, dst, src.0, opacity);
+        }
+    }
+    RawImage {
+        width,
+        height,
+        data,
+    }
+}
+
+// Multiplies two values as fractions of 255, rounding like Aseprite.
+fn mul_un8(a: u8, b: u8) -> u8 {
+    let t = a as u32 * b as u32 + 0x80;
+    (((t >> 8) + t) >> 8) as u8
+}
+
+// Divides two values as fractions of 255, rounding like Aseprite. `b` must not be 0.
+fn div_un8(a: u8, b: u8) -> u8 {
+    ((a as u32 * 255 + b as u32 / 2) / b as u32) as u8
+}
+
+// Blends a straight-alpha sRGBA pixel over another in normal mode, like Aseprite.
+fn blend_normal(dst: &mut [u8], src: [u8; 4], opacity: u8) {
+    if dst[3] == 0 {
+        dst[..3].copy_from_slice(&src[..3]);
+        dst[3] = mul_un8(src[3], opacity);
+        return;
+    }
+    if src[3] == 0 {
+        return;
+    }
+    let src_alpha = mul_un8(src[3], opacity) as i32;
+    let dst_alpha = dst[3] as i32;
+    let out_alpha = src_alpha + dst_alpha - mul_un8(dst[3], src_alpha as u8) as i32;
+    for channel in 0..3 {
+        let (src_color, dst_color) = (src[channel] as i32, dst[channel] as i32);
+        dst[channel] = (dst_color + (src_color - dst_color) * src_alpha / out_alpha) as u8;
+    }
+    dst[3] = out_alpha as u8;
+}
+
+// Blends a straight-alpha sRGBA pixel over another with a layer's blend mode, like Aseprite.
+// Aseprite combines the colors with the blend mode, then blends the result in normal mode,
+// keeping the source alpha.
+fn blend(mode: BlendMode, dst: &mut [u8], src: [u8; 4], opacity: u8) {
+    let backdrop = [dst[0], dst[1], dst[2]];
+    let color = [src[0], src[1], src[2]];
+    let blended = match mode {
+        BlendMode::Normal => color,
+        BlendMode::Hue => {
+            let saturated = set_sat(to_unit(color), sat(to_unit(backdrop)));
+            from_unit(set_lum(saturated, lum(to_unit(backdrop))))
+        }
+        BlendMode::Saturation => {
+            let saturated = set_sat(to_unit(backdrop), sat(to_unit(color)));
+            from_unit(set_lum(saturated, lum(to_unit(backdrop))))
+        }
+        BlendMode::Color => from_unit(set_lum(to_unit(color), lum(to_unit(backdrop)))),
+        BlendMode::Luminosity => from_unit(set_lum(to_unit(backdrop), lum(to_unit(color)))),
+        mode => [0, 1, 2].map(|channel| blend_channel(mode, backdrop[channel], color[channel])),
+    };
+    blend_normal(dst, [blended[0], blended[1], blended[2], src[3]], opacity);
+}
+
+// Combines one color channel of a backdrop and source pixel with a separable blend mode.
+fn blend_channel(mode: BlendMode, b: u8, s: u8) -> u8 {
+    match mode {
+        BlendMode::Multiply => mul_un8(b, s),
+        BlendMode::Screen => screen(b, s),
+        BlendMode::Overlay => hard_light(s, b),
+        BlendMode::Darken => b.min(s),
+        BlendMode::Lighten => b.max(s),
+        BlendMode::ColorDodge => match (b, u8::MAX - s) {
+            (0, _) => 0,
+            (b, s) if b >= s => u8::MAX,
+            (b, s) => div_un8(b, s),
+        },
+        BlendMode::ColorBurn => match (u8::MAX - b, s) {
+            (0, _) => u8::MAX,
+            (b, s) if b >= s => 0,
+            (b, s) => u8::MAX - div_un8(b, s),
+        },
+        BlendMode::HardLight => hard_light(b, s),
+        BlendMode::SoftLight => soft_light(b, s),
+        BlendMode::Difference => b.abs_diff(s),
+        BlendMode::Exclusion => (b as i32 + s as i32 - 2 * mul_un8(b, s) as i32) as u8,
+        BlendMode::Addition => b.saturating_add(s),
+        BlendMode::Subtract => b.saturating_sub(s),
+        BlendMode::Divide => match (b, s) {
+            (0, _) => 0,
+            (b, s) if b >= s => u8::MAX,
+            (b, s) => div_un8(b, s),
+        },
+        _ => s,
+    }
+}
+
+fn screen(b: u8, s: u8) -> u8 {
+    (b as i32 + s as i32 - mul_un8(b, s) as i32) as u8
+}
+
+fn hard_light(b: u8, s: u8) -> u8 {
+    if s < 128 {
+        mul_un8(b, s << 1)
+    } else {
+        screen(b, ((s as i32) * 2 - 255) as u8)
+    }
+}
+
+fn soft_light(b: u8, s: u8) -> u8 {
+    let (b, s) = (b as f64 / 255.0, s as f64 / 255.0);
+    let d = if b <= 0.25 {
+        ((16.0 * b - 12.0) * b + 4.0) * b
+    } else {
+        b.sqrt()
+    };
+    let r = if s <= 0.5 {
+        b - (1.0 - 2.0 * s) * b * (1.0 - b)
+    } else {
+        b + (2.0 * s - 1.0) * (d - b)
+    };
+    (r * 255.0 + 0.5) as u8
+}
+
+// The non-separable blend modes work on colors with channels from 0 to 1.
+fn to_unit(color: [u8; 3]) -> [f64; 3] {
+    color.map(|channel| channel as f64 / 255.0)
+}
+
+fn from_unit(color: [f64; 3]) -> [u8; 3] {
+    color.map(|channel| (255.0 * channel) as u8)
+}
+
+fn lum([r, g, b]: [f64; 3]) -> f64 {
+    0.3 * r + 0.59 * g + 0.11 * b
+}
+
+fn sat([r, g, b]: [f64; 3]) -> f64 {
+    r.max(g).max(b) - r.min(g).min(b)
+}
+
+// Brings a color's channels back into range, keeping its luminosity.
+fn clip_color(color: [f64; 3]) -> [f64; 3] {
+    let l = lum(color);
+    let n = color[0].min(color[1]).min(color[2]);
+    let x = color[0].max(color[1]).max(color[2]);
+    color.map(|c| {
+        let c = if n < 0.0 {
+            l + (c - l) * l / (l - n)
+        } else {
+            c
+        };
+        if x > 1.0 {
+            l + (c - l) * (1.0 - l) / (x - l)
+        } else {
+            c
+        }
+    })
+}
+
+fn set_lum(color: [f64; 3], l: f64) -> [f64; 3] {
+    let d = l - lum(color);
+    clip_color(color.map(|c| c + d))
+}
+
+// Scales a color's channels to the given saturation, keeping their order.
+fn set_sat(color: [f64; 3], s: f64) -> [f64; 3] {
+    let max = color[0].max(color[1]).max(color[2]);
+    let min = color[0].min(color[1]).min(color[2]);
+    color.map(|c| {
+        if max <= min {
+            0.0
+        } else if c == max {
+            s
+        } else if c == min {
+            0.0
+        } else {
+            (c - min) * s / (max - min)
+        }
+    })
+}
@@ -1,25 +1,37 @@
-use crate::loader::AseAssetResources;
+use crate::loader::{AseAssetResources, AseTileIndexWarning, ImportError, ImportResult};
 use crate::{
     asset::{
         animation::{self, Animation, AnimationData, Frame, SpriteData},
-        slice::Slice,
-        tileset::{TilesetData, TilesetResult},
-        AseAssetMap, Tileset,
+        collision::FrameCollision,
+        events::AnimationEvents,
+        slice::{self, Slice},
+        tileset::{tile_index_changes, TilesetData},
+        AseAssetMap, Tileset, UiTextPlaceholder,
+    },
+    handle_id::{self, label},
+    process::{
+        bake_palette_atlas, check_limits, process_file_with_header, AtlasUsage, ColorProfile,
+        ExternalAlias, FileHeader, ImageFormats, Palette, PixelRect, ProcessSettings,
+        ProcessedAtlas, ProcessedFile, ProcessedTagAtlas, RawImage, UnsupportedFormat,
     },
-    handle_id,
 };
 use asefile::AsepriteFile;
-use bevy::sprite::TextureAtlasBuilder;
-use bevy::{prelude::*, utils::HashMap};
-use std::path::{Path, PathBuf};
-
-fn tilesets_from(ase: &AsepriteFile) -> TilesetResult<Vec<TilesetData<Image>>> {
-    let f = |t| TilesetData::<Image>::from_ase_with_texture(ase, t);
-    ase.tilesets().iter().map(f).collect()
-}
+use bevy::{
+    asset::HandleId,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
+    utils::HashMap,
+};
+use std::{
+    any::Any,
+    collections::BTreeSet,
+    panic::{self, AssertUnwindSafe},
+    path::{Component, Path, PathBuf},
+    sync::{Mutex, PoisonError},
+};
 
 fn move_slices(
-    path: &str,
+    path: &Path,
     slice_vec: Vec<Slice>,
     slices: &mut Assets<Slice>,
     file_assets: &mut AseAssetMap,
@@ -38,8 +50,8 @@ struct TilesetImportResources<'a> {
 }
 
 fn move_tilesets(
-    path: &str,
-    tileset_data: Vec<TilesetData<Image>>,
+    path: &Path,
+    tileset_data: Vec<TilesetData<Option<Image>>>,
     resources: TilesetImportResources,
     file_assets: &mut AseAssetMap,
 ) {
@@ -53,7 +65,11 @@ fn move_tilesets(
             texture,
         } = ts;
         let image_handle_id = handle_id::tileset_image(path, id);
-        let tex_handle = textures.set(image_handle_id, texture);
+        // Tilesets processed without image data refer to their image's handle id.
+        let tex_handle = match texture {
+            Some(texture) => textures.set(image_handle_id, texture),
+            None => Handle::weak(image_handle_id),
+        };
         let tileset = Tileset {
             id,
             name,
@@ -68,46 +84,88 @@ fn move_tilesets(
 }
 
 // Data used to move animations into Bevy.
-struct AnimationImportData<'a> {
+struct AnimationImportData {
     animation_data: Vec<AnimationData>,
-    sprite_data: Vec<SpriteData<Handle<Image>>>,
-    atlas: &'a TextureAtlas,
+    // Atlas index and duration of each sprite, indexed by sprite id.
+    sprite_frames: Vec<SpriteFrame>,
     atlas_handle: Handle<TextureAtlas>,
+    lod_atlas_handles: Vec<Handle<TextureAtlas>>,
+    palette_atlas_handles: Vec<Handle<TextureAtlas>>,
+    // Handle and first frame of each tag atlas.
+    tag_atlas_handles: Vec<(Handle<TextureAtlas>, u32)>,
+}
+
+struct SpriteFrame {
+    atlas_index: usize,
+    duration: u32,
+    opaque_bounds: Option<Rect>,
+    changed_bounds: Option<Rect>,
 }
 
 fn move_animations(
-    path: &str,
+    path: &Path,
     data: AnimationImportData,
     animations: &mut Assets<Animation>,
+    mut events: Option<&mut Assets<AnimationEvents>>,
     file_assets: &mut AseAssetMap,
 ) {
     let AnimationImportData {
         animation_data,
-        sprite_data,
-        atlas,
+        sprite_frames,
         atlas_handle,
+        lod_atlas_handles,
+        palette_atlas_handles,
+        tag_atlas_handles,
     } = data;
 
     for anim_data in animation_data.into_iter() {
         if let Some(tag_name) = anim_data.tag_name {
-            let mut frames = Vec::with_capacity(anim_data.sprites.len());
-            for sprite_id in &anim_data.sprites {
-                let sprite_id = *sprite_id;
-                let tmp_sprite = &sprite_data[sprite_id];
-                let atlas_index = atlas
-                    .get_texture_index(&tmp_sprite.texture)
-                    .expect("Failed to get texture from atlas");
-                frames.push(Frame {
-                    sprite: animation::Sprite {
-                        atlas_index: atlas_index as u32,
-                    },
-                    duration_ms: tmp_sprite.duration,
-                });
-            }
+            // Tag atlases start at their tag's first frame.
+            let (atlas_handle, first_frame) = match anim_data.tag_atlas {
+                Some(index) => tag_atlas_handles[index].clone(),
+                None => (atlas_handle.clone(), 0),
+            };
+            let frame_indices = anim_data
+                .sprites
+                .iter()
+                .map(|sprite_id| {
+                    let atlas_index = sprite_frames[*sprite_id].atlas_index as u32;
+                    (*sprite_id as u32, atlas_index - first_frame)
+                })
+                .collect();
+            let frames = anim_data
+                .sprites
+                .iter()
+                .map(|sprite_id| {
+                    let sprite_frame = &sprite_frames[*sprite_id];
+                    Frame {
+                        sprite: animation::Sprite {
+                            atlas_index: sprite_frame.atlas_index as u32 - first_frame,
+                        },
+                        duration_ms: sprite_frame.duration,
+                        opaque_bounds: sprite_frame.opaque_bounds,
+                        changed_bounds: sprite_frame.changed_bounds,
+                    }
+                })
+                .collect();
             let anim_id = handle_id::animation(path, &tag_name);
-            let asset = Animation::new(frames, atlas_handle.clone());
+            let mut asset = Animation::new(frames, atlas_handle)
+                .with_lod_atlases(lod_atlas_handles.clone())
+                .with_palette_atlases(palette_atlas_handles.clone())
+                .with_repeat(anim_data.repeat)
+                .with_speed(anim_data.speed)
+                .with_color(anim_data.color)
+                .with_flip(anim_data.flip_x, anim_data.flip_y);
+            if let Some(events) = events.as_deref_mut() {
+                if !anim_data.events.is_empty() {
+                    let events_id = handle_id::animation_events(path, &tag_name);
+                    let handle = events.set(events_id, AnimationEvents::new(anim_data.events));
+                    file_assets.insert_animation_events(tag_name.clone(), handle.clone());
+                    asset = asset.with_events(handle);
+                }
+            }
             let handle = animations.set(anim_id, asset);
-            file_assets.insert_animation(tag_name, handle);
+            file_assets.insert_animation(tag_name, handle, frame_indices);
         }
     }
 }
@@ -117,141 +175,749 @@ struct SpriteImportResources<'a> {
     atlases: &'a mut Assets<TextureAtlas>,
 }
 
+// Texture atlas data, packed during processing.
+pub(crate) struct AtlasData {
+    pub(crate) image: Image,
+    pub(crate) size: Vec2,
+    // Region of each frame in the atlas image, indexed by frame index.
+    pub(crate) rects: Vec<Rect>,
+}
+impl AtlasData {
+    fn new(atlas: ProcessedAtlas, format: TextureFormat) -> Result<Self, UnsupportedFormat> {
+        let size = Vec2::new(atlas.image.width as f32, atlas.image.height as f32);
+        let rects = atlas.frames.iter().copied().map(pixel_rect).collect();
+        Ok(Self {
+            image: atlas.image.into_image(format)?,
+            size,
+            rects,
+        })
+    }
+}
+
 fn move_sprites(
-    path: &str,
-    sprites: Vec<SpriteData<Image>>,
+    path: &Path,
+    sprites: Vec<SpriteData<Option<Image>>>,
+    atlas: AtlasData,
     resources: SpriteImportResources,
     file_assets: &mut AseAssetMap,
-) -> (Vec<SpriteData<Handle<Image>>>, Handle<TextureAtlas>) {
+) -> Handle<TextureAtlas> {
     let SpriteImportResources { images, atlases } = resources;
-    let mut texture_atlas_builder = TextureAtlasBuilder::default();
-    let sprite_handles: Vec<SpriteData<Handle<Image>>> = sprites
-        .into_iter()
-        .map(
-            |SpriteData {
-                 frame,
-                 texture: image,
-                 duration,
-             }| {
-                let image_handle_id = handle_id::frame_image(path, frame);
-                let image_handle = images.set(image_handle_id, image);
-                file_assets.insert_texture(frame, image_handle.clone());
-                // Expect: We just inserted this image above
-                let image = images.get(&image_handle).expect("Image missing");
-                texture_atlas_builder.add_texture(image_handle.clone_weak(), image);
-                SpriteData {
-                    texture: image_handle,
-                    frame,
-                    duration,
-                }
-            },
-        )
-        .collect();
-    let atlas = texture_atlas_builder
-        .finish(images)
-        .expect("Creating texture atlas failed");
+    let AtlasData { image, size, rects } = atlas;
+    let mut texture_atlas = TextureAtlas::new_empty(images.add(image), size);
+    let mut texture_handles = HashMap::default();
+    for (sprite, rect) in sprites.into_iter().zip(rects) {
+        let SpriteData { frame, texture, .. } = sprite;
+        let image_handle_id = handle_id::frame_image(path, frame);
+        let atlas_index = texture_atlas.add_texture(rect);
+        let Some(texture) = texture else {
+            // Frame images without data are created from the atlas on first access.
+            file_assets.insert_lazy_texture(frame, Handle::weak(image_handle_id));
+            continue;
+        };
+        let image_handle = images.set(image_handle_id, texture);
+        file_assets.insert_texture(frame, image_handle.clone());
+        texture_handles.insert(image_handle.clone_weak(), atlas_index);
+    }
+    texture_atlas.texture_handles = Some(texture_handles);
     let atlas_handle_id = handle_id::atlas(path);
-    let atlas_handle = atlases.set(atlas_handle_id, atlas);
+    let atlas_handle = atlases.set(atlas_handle_id, texture_atlas);
     file_assets.insert_atlas(atlas_handle.clone());
-    (sprite_handles, atlas_handle)
+    atlas_handle
+}
+
+// Returns the number of bytes per pixel of an image.
+// Images may use any supported format, so this is derived from their data.
+pub(crate) fn pixel_bytes(image: &Image) -> Option<usize> {
+    let size = image.texture_descriptor.size;
+    image
+        .data
+        .len()
+        .checked_div((size.width * size.height) as usize)
+}
+
+// Joins a path relative to a file's folder, resolving `.` and `..` so the result matches
+// the path the other file is imported with.
+fn relative_to_file(file_path: &Path, relative: &Path) -> PathBuf {
+    let mut joined = file_path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    for component in relative.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                joined.pop();
+            }
+            component => joined.push(component),
+        }
+    }
+    joined
 }
 
-pub(crate) struct ResourceDataByFile(HashMap<PathBuf, ResourceData>);
+// Returns the index of the tag atlas for an animation: its own tag's atlas, or for
+// mirrored animations, the first atlas holding all of its frames.
+fn tag_atlas_index(
+    tag_atlases: &[ProcessedTagAtlas],
+    tag_name: Option<&str>,
+    frames: &[u32],
+) -> Option<usize> {
+    let by_name = tag_atlases
+        .iter()
+        .position(|tag_atlas| Some(tag_atlas.tag_name.as_str()) == tag_name);
+    by_name.or_else(|| {
+        tag_atlases
+            .iter()
+            .position(|tag_atlas| frames.iter().all(|frame| tag_atlas.contains(*frame)))
+    })
+}
+
+// Adds an atlas which is not indexed by frame image, such as a downscaled or shadow atlas.
+fn move_extra_atlas(
+    handle_id: HandleId,
+    atlas: AtlasData,
+    resources: SpriteImportResources,
+) -> Handle<TextureAtlas> {
+    let SpriteImportResources { images, atlases } = resources;
+    let AtlasData { image, size, rects } = atlas;
+    let mut texture_atlas = TextureAtlas::new_empty(images.add(image), size);
+    for rect in rects {
+        texture_atlas.add_texture(rect);
+    }
+    atlases.set(handle_id, texture_atlas)
+}
+
+// Copies a frame's region out of its atlas image.
+pub(crate) fn frame_image_from_atlas(
+    atlas: &TextureAtlas,
+    images: &Assets<Image>,
+    frame: u32,
+) -> Option<Image> {
+    let rect = atlas.textures.get(frame as usize)?;
+    let atlas_image = images.get(&atlas.texture)?;
+    let pixel_bytes = pixel_bytes(atlas_image)?;
+    let stride = atlas_image.texture_descriptor.size.width as usize * pixel_bytes;
+    let (x, y) = (rect.min.x as usize, rect.min.y as usize);
+    let (width, height) = (rect.width() as usize, rect.height() as usize);
+    let mut data = Vec::with_capacity(width * height * pixel_bytes);
+    for row in y..y + height {
+        let start = row * stride + x * pixel_bytes;
+        data.extend_from_slice(atlas_image.data.get(start..start + width * pixel_bytes)?);
+    }
+    let size = Extent3d {
+        width: width as u32,
+        height: height as u32,
+        depth_or_array_layers: 1,
+    };
+    let format = atlas_image.texture_descriptor.format;
+    Some(Image::new(size, TextureDimension::D2, data, format))
+}
+
+// Processed files of one batch, in the order they were queued.
+pub(crate) struct ResourceDataByFile(Vec<(PathBuf, ImportResult<ResourceData>)>);
 impl ResourceDataByFile {
-    pub(crate) fn new(ases: Vec<(PathBuf, AsepriteFile)>) -> Self {
+    pub(crate) fn new(
+        ases: Vec<(PathBuf, AsepriteFile, FileHeader, Vec<Palette>)>,
+        settings: &ProcessSettings,
+    ) -> Self {
         let inner = ases
             .into_iter()
-            .map(|(path, ase)| {
-                let data = ResourceData::new(&path, &ase);
+            .map(|(path, ase, header, palettes)| {
+                // A panic while processing one file fails only that file.
+                let data = panic::catch_unwind(AssertUnwindSafe(|| {
+                    ResourceData::new(&path, &ase, &header, &palettes, settings)
+                }));
+                let data = data.unwrap_or_else(|payload| {
+                    Err(ImportError::ProcessingPanicked(panic_message(&*payload)))
+                });
                 (path, data)
             })
             .collect();
         Self(inner)
     }
-    pub(crate) fn move_into_resources(self, resources: &mut AseAssetResources) {
-        for (path, data) in self.0.into_iter() {
-            data.move_into_resources(path, resources);
-        }
+    pub(crate) fn into_files(self) -> impl Iterator<Item = (PathBuf, ImportResult<ResourceData>)> {
+        self.0.into_iter()
     }
 }
 
+// Debug labels of textures, which must be 'static. Each distinct label is leaked once,
+// and reused when files are imported again.
+static DEBUG_LABELS: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+
+fn debug_label(name: String) -> &'static str {
+    let mut labels = DEBUG_LABELS.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(label) = labels.get(name.as_str()) {
+        return *label;
+    }
+    let label: &'static str = Box::leak(name.into_boxed_str());
+    labels.insert(label);
+    label
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+fn pixel_rect(rect: PixelRect) -> Rect {
+    let min = Vec2::new(rect.x as f32, rect.y as f32);
+    Rect::from_corners(min, min + Vec2::new(rect.width as f32, rect.height as f32))
+}
+
+fn into_image(image: Option<RawImage>, format: TextureFormat) -> ImportResult<Option<Image>> {
+    Ok(image.map(|image| image.into_image(format)).transpose()?)
+}
+
+// Aseprite colors are sRGB.
+fn color_from_srgba([r, g, b, a]: [u8; 4]) -> Color {
+    Color::rgba_u8(r, g, b, a)
+}
+
 pub(crate) struct ResourceData {
-    pub(crate) sprites: Vec<SpriteData<Image>>,
+    pub(crate) sprites: Vec<SpriteData<Option<Image>>>,
     pub(crate) anims: Vec<AnimationData>,
-    pub(crate) tilesets: Vec<TilesetData<Image>>,
+    pub(crate) tilesets: Vec<TilesetData<Option<Image>>>,
     pub(crate) slices: Vec<Slice>,
+    pub(crate) text_placeholders: Vec<UiTextPlaceholder>,
+    pub(crate) atlas: Option<AtlasData>,
+    pub(crate) lod_atlases: Vec<AtlasData>,
+    pub(crate) palette_atlases: Vec<AtlasData>,
+    pub(crate) outline: Option<AtlasData>,
+    // Tag name, first frame and atlas of each tag atlas.
+    pub(crate) tag_atlases: Vec<(String, u32, AtlasData)>,
+    pub(crate) external_aliases: Vec<ExternalAlias>,
+    pub(crate) atlas_usage: Option<AtlasUsage>,
+    pub(crate) shadow: Option<AtlasData>,
+    pub(crate) thumbnail: Option<Image>,
+    pub(crate) canvas_size: Vec2,
+    pub(crate) pixel_ratio: (u32, u32),
+    pub(crate) pixels_per_unit: Option<f32>,
+    pub(crate) layer_colors: Vec<(String, Color)>,
+    pub(crate) layer_z_orders: Vec<(String, f32)>,
+    pub(crate) collisions: Vec<(u32, FrameCollision)>,
+    pub(crate) layer_images: Vec<(u32, String, Image)>,
+    pub(crate) color_profile_warning: Option<ColorProfile>,
+    pub(crate) unsupported: Vec<String>,
 }
 impl ResourceData {
-    pub(crate) fn new(path: &Path, file: &AsepriteFile) -> Self {
-        let mut tmp_sprites: Vec<SpriteData<Image>> = Vec::new();
-        let mut tmp_anim_info: Vec<AnimationData> = Vec::new();
-        let mut slices: Vec<Slice> = Vec::new();
-        let mut tilesets: Vec<TilesetData<Image>> = Vec::new();
+    pub(crate) fn new(
+        path: &Path,
+        file: &AsepriteFile,
+        header: &FileHeader,
+        palettes: &[Palette],
+        settings: &ProcessSettings,
+    ) -> ImportResult<Self> {
         debug!("Processing Aseprite file: {}", path.display());
-        let sprite_offset = tmp_sprites.len();
-        for frame in 0..file.num_frames() {
-            tmp_sprites.push(SpriteData::<Image>::new(file, frame));
+        check_limits(file, &settings.limits)?;
+        let processed = process_file_with_header(file, header, settings);
+        let profile = processed.color_profile;
+        let palette_atlases: Vec<AtlasData> = match (&processed.atlas, Palette::from_file(file)) {
+            (Some(atlas), Some(base)) => palettes
+                .iter()
+                .map(|palette| bake_palette_atlas(atlas, &base, palette))
+                .map(|atlas| AtlasData::new(atlas, settings.formats.atlas))
+                .collect::<Result<_, _>>()?,
+            _ => Vec::new(),
+        };
+        let mut data = Self::from_processed(processed, &settings.formats)?;
+        data.palette_atlases = palette_atlases;
+        if settings.color_profile.warns(profile) {
+            data.color_profile_warning = Some(profile);
         }
-        tmp_anim_info.push(AnimationData::new(file, sprite_offset));
-        for tag_id in 0..file.num_tags() {
-            let tag = file.tag(tag_id);
-            tmp_anim_info.push(AnimationData::from_tag(sprite_offset, tag));
+        if settings.debug_labels {
+            data.set_debug_labels(path);
         }
-        let mut ase_tilesets =
-            tilesets_from(file).expect("Internal error: Failed to add tilesets from Ase file");
-        tilesets.append(&mut ase_tilesets);
-        for ase_slice in file.slices().iter() {
-            // let slice_id = SliceId::new(idx as u32);
-            let slice = crate::asset::slice::Slice::from_ase(ase_slice);
-            slices.push(slice);
+        if settings.editable_tilesets {
+            for texture in data
+                .tilesets
+                .iter_mut()
+                .filter_map(|tileset| tileset.texture.as_mut())
+            {
+                texture.texture_descriptor.usage |=
+                    TextureUsages::COPY_SRC | TextureUsages::COPY_DST;
+            }
         }
-        Self {
-            sprites: tmp_sprites,
-            anims: tmp_anim_info,
-            tilesets,
-            slices,
+        Ok(data)
+    }
+    // Compares each tileset with its previous import, see AseTileIndexWarning.
+    pub(crate) fn tile_index_warnings(
+        &self,
+        path: &Path,
+        images: &Assets<Image>,
+        tilesets: &Assets<Tileset>,
+    ) -> Vec<AseTileIndexWarning> {
+        self.tilesets
+            .iter()
+            .filter_map(|tileset| {
+                let texture = tileset.texture.as_ref()?;
+                let previous = tilesets.get(handle_id::tileset(path, tileset.id))?;
+                let previous_texture = images.get(&previous.texture)?;
+                // Tiles of another size or format can't be compared.
+                if previous.tile_size != tileset.tile_size
+                    || previous_texture.texture_descriptor.format
+                        != texture.texture_descriptor.format
+                    || tileset.tile_count == 0
+                {
+                    return None;
+                }
+                let tile_bytes = texture.data.len() / tileset.tile_count as usize;
+                let (moved, removed) =
+                    tile_index_changes(&previous_texture.data, &texture.data, tile_bytes);
+                if moved.is_empty() && removed.is_empty() {
+                    return None;
+                }
+                Some(AseTileIndexWarning {
+                    path: path.to_path_buf(),
+                    tileset_id: tileset.id,
+                    moved,
+                    removed,
+                })
+            })
+            .collect()
+    }
+    // Names each image after its source, see ProcessSettings::debug_labels.
+    fn set_debug_labels(&mut self, path: &Path) {
+        let labeled = |label: String| debug_label(format!("{}#{}", path.display(), label));
+        for sprite in &mut self.sprites {
+            let Some(texture) = sprite.texture.as_mut() else {
+                continue;
+            };
+            let frame = sprite.frame as usize;
+            let tag_frame = self.anims.iter().find_map(|anim| {
+                let index = anim.sprites.iter().position(|id| *id == frame)?;
+                Some(format!(
+                    "{}/{}/{}",
+                    path.display(),
+                    anim.tag_name.as_ref()?,
+                    index
+                ))
+            });
+            texture.texture_descriptor.label = Some(match tag_frame {
+                Some(name) => debug_label(name),
+                None => labeled(label::frame_image(sprite.frame)),
+            });
+        }
+        let atlases = self
+            .atlas
+            .iter_mut()
+            .map(|atlas| (label::atlas(), atlas))
+            .chain(
+                (1..)
+                    .zip(&mut self.lod_atlases)
+                    .map(|(level, atlas)| (label::lod_atlas(level), atlas)),
+            )
+            .chain(
+                (1..)
+                    .zip(&mut self.palette_atlases)
+                    .map(|(number, atlas)| (label::palette_atlas(number), atlas)),
+            )
+            .chain(
+                self.outline
+                    .iter_mut()
+                    .map(|atlas| (label::outline_atlas(), atlas)),
+            )
+            .chain(
+                self.tag_atlases
+                    .iter_mut()
+                    .map(|(tag_name, _, atlas)| (label::tag_atlas(tag_name), atlas)),
+            )
+            .chain(
+                self.shadow
+                    .iter_mut()
+                    .map(|atlas| (label::shadow_atlas(), atlas)),
+            );
+        for (atlas_label, atlas) in atlases {
+            atlas.image.texture_descriptor.label = Some(labeled(atlas_label));
+        }
+        if let Some(thumbnail) = self.thumbnail.as_mut() {
+            thumbnail.texture_descriptor.label = Some(labeled(label::thumbnail()));
+        }
+        for (frame, layer_name, image) in &mut self.layer_images {
+            image.texture_descriptor.label = Some(labeled(label::layer_image(*frame, layer_name)));
         }
+        for tileset in &mut self.tilesets {
+            if let Some(texture) = tileset.texture.as_mut() {
+                texture.texture_descriptor.label = Some(labeled(label::tileset_image(tileset.id)));
+            }
+        }
+    }
+    fn from_processed(mut processed: ProcessedFile, formats: &ImageFormats) -> ImportResult<Self> {
+        let canvas_size = Vec2::new(processed.width as f32, processed.height as f32);
+        let collisions = processed
+            .frames
+            .iter()
+            .filter_map(|frame| {
+                let hull = frame.collision_hull.as_ref()?;
+                let hull = hull
+                    .iter()
+                    .map(|&(x, y)| {
+                        let pixel = Vec2::new(x as f32, y as f32);
+                        slice::to_sprite_point(pixel, canvas_size)
+                    })
+                    .collect();
+                Some((frame.index, FrameCollision::new(hull)))
+            })
+            .collect();
+        let layer_images = processed
+            .frames
+            .iter_mut()
+            .flat_map(|frame| {
+                let index = frame.index;
+                let images = std::mem::take(&mut frame.layer_images);
+                images
+                    .into_iter()
+                    .map(move |(name, image)| (index, name, image))
+            })
+            .map(|(frame, name, image)| Ok((frame, name, image.into_image(formats.frames)?)))
+            .collect::<ImportResult<_>>()?;
+        let sprites = processed
+            .frames
+            .into_iter()
+            .map(|frame| {
+                Ok(SpriteData {
+                    frame: frame.index,
+                    texture: into_image(frame.image, formats.frames)?,
+                    duration: frame.duration_ms,
+                    opaque_bounds: frame.opaque_bounds.map(pixel_rect),
+                    changed_bounds: frame.changed_bounds.map(pixel_rect),
+                })
+            })
+            .collect::<ImportResult<_>>()?;
+        let tag_atlases = &processed.tag_atlases;
+        let anims = processed
+            .animations
+            .into_iter()
+            .map(|anim| AnimationData {
+                tag_atlas: tag_atlas_index(tag_atlases, anim.tag_name.as_deref(), &anim.frames),
+                tag_name: anim.tag_name,
+                sprites: anim.frames.into_iter().map(|f| f as usize).collect(),
+                repeat: anim.repeat,
+                speed: anim.speed,
+                events: anim.events,
+                color: anim.color.map(color_from_srgba),
+                flip_x: anim.flip_x,
+                flip_y: anim.flip_y,
+            })
+            .collect();
+        let tilesets = processed
+            .tilesets
+            .into_iter()
+            .map(|tileset| {
+                Ok(TilesetData {
+                    id: tileset.id,
+                    tile_count: tileset.tile_count,
+                    tile_size: tileset.tile_size,
+                    name: tileset.name,
+                    texture: into_image(tileset.image, formats.tilesets)?,
+                })
+            })
+            .collect::<ImportResult<_>>()?;
+        let tag_atlases: Vec<(String, u32, AtlasData)> = processed
+            .tag_atlases
+            .into_iter()
+            .map(|tag_atlas| {
+                let atlas = AtlasData::new(tag_atlas.atlas, formats.atlas)?;
+                Ok((tag_atlas.tag_name, tag_atlas.first_frame, atlas))
+            })
+            .collect::<ImportResult<_>>()?;
+        let atlas_usage = processed.atlas.as_ref().map(ProcessedAtlas::usage);
+        let atlas = processed
+            .atlas
+            .map(|atlas| AtlasData::new(atlas, formats.atlas))
+            .transpose()?;
+        let lod_atlases: Vec<AtlasData> = processed
+            .lod_atlases
+            .into_iter()
+            .map(|atlas| AtlasData::new(atlas, formats.atlas))
+            .collect::<Result<_, _>>()?;
+        let outline = processed
+            .outline
+            .map(|atlas| AtlasData::new(atlas, formats.atlas))
+            .transpose()?;
+        let shadow = processed
+            .shadow
+            .map(|atlas| AtlasData::new(atlas, formats.atlas))
+            .transpose()?;
+        let thumbnail = into_image(processed.thumbnail, formats.frames)?;
+        Ok(Self {
+            sprites,
+            anims,
+            tilesets,
+            slices: processed.slices,
+            text_placeholders: processed.text_placeholders,
+            atlas,
+            lod_atlases,
+            palette_atlases: Vec::new(),
+            outline,
+            tag_atlases,
+            external_aliases: processed.external_aliases,
+            atlas_usage,
+            shadow,
+            thumbnail,
+            canvas_size,
+            pixel_ratio: processed.pixel_ratio,
+            pixels_per_unit: processed.pixels_per_unit,
+            layer_colors: processed
+                .layers
+                .iter()
+                .filter_map(|layer| Some((layer.name.clone(), color_from_srgba(layer.color?))))
+                .collect(),
+            layer_z_orders: processed
+                .layers
+                .into_iter()
+                .filter_map(|layer| Some((layer.name, layer.z_order?)))
+                .collect(),
+            collisions,
+            layer_images,
+            color_profile_warning: None,
+            unsupported: processed.unsupported,
+        })
     }
     pub(crate) fn move_into_resources(self, path_buf: PathBuf, resources: &mut AseAssetResources) {
         let data = self;
-        let path_str = path_buf.to_str().expect("Expected valid Unicode path!");
-        let (textures, animations, atlases, tilesets, slices, index) = resources;
+        let path = path_buf.as_path();
+        let (
+            textures,
+            animations,
+            atlases,
+            tilesets,
+            slices,
+            events,
+            collisions,
+            placeholders,
+            index,
+        ) = resources;
 
-        let file_assets = index
-            .as_deref_mut()
-            .map(|ase_file_map| ase_file_map.get_mut(&path_buf))
-            .expect("Expected a file map!");
+        // Without a file map, assets are still created under their handle ids,
+        // but not indexed.
+        let mut unindexed = AseAssetMap::default();
+        let file_assets = match index.as_deref_mut() {
+            Some(ase_file_map) => ase_file_map.get_mut(path),
+            None => &mut unindexed,
+        };
+
+        file_assets.next_generation();
+        file_assets.set_canvas_size(data.canvas_size);
+        file_assets.set_partial(!data.unsupported.is_empty());
+        file_assets.set_pixel_ratio(data.pixel_ratio);
+        file_assets.set_pixels_per_unit(data.pixels_per_unit);
+        file_assets.set_atlas_usage(data.atlas_usage);
+        for (layer_name, color) in data.layer_colors {
+            file_assets.insert_layer_color(layer_name, color);
+        }
+        for (layer_name, z_order) in data.layer_z_orders {
+            file_assets.insert_layer_z_order(layer_name, z_order);
+        }
+        for alias in data.external_aliases {
+            let target_path = relative_to_file(path, &alias.path);
+            file_assets.add_external_alias(alias.tag_name, target_path, alias.target_tag);
+        }
 
         if let Some(slices) = slices {
-            move_slices(path_str, data.slices, slices, file_assets);
+            move_slices(path, data.slices, slices, file_assets);
+        }
+
+        if let Some(placeholders) = placeholders {
+            for placeholder in data.text_placeholders {
+                let slice_name = placeholder.name.clone();
+                let handle_id = handle_id::text_placeholder(path, &slice_name);
+                let handle = placeholders.set(handle_id, placeholder);
+                file_assets.insert_text_placeholder(slice_name, handle);
+            }
         }
 
-        if let Some(tilesets) = tilesets {
+        if let Some(collisions) = collisions {
+            for (frame, collision) in data.collisions {
+                let handle = collisions.set(handle_id::frame_collision(path, frame), collision);
+                file_assets.insert_frame_collision(frame, handle);
+            }
+        }
+
+        if let Some(images) = textures.as_deref_mut() {
+            if let Some(thumbnail) = data.thumbnail {
+                let handle = images.set(handle_id::thumbnail(path), thumbnail);
+                file_assets.insert_thumbnail(handle);
+            }
+            for (frame, layer_name, image) in data.layer_images {
+                let handle = images.set(handle_id::layer_image(path, frame, &layer_name), image);
+                file_assets.insert_layer_image(frame, layer_name, handle);
+            }
+        }
+
+        if let (Some(textures), Some(tilesets)) = (textures.as_deref_mut(), tilesets) {
             let resources = TilesetImportResources { textures, tilesets };
-            move_tilesets(path_str, data.tilesets, resources, file_assets);
+            move_tilesets(path, data.tilesets, resources, file_assets);
         }
 
-        // Move sprites
-        if let Some(atlases) = atlases {
-            let resources = SpriteImportResources {
-                images: textures,
-                atlases,
-            };
+        if let (Some(images), Some(atlases), Some(shadow)) =
+            (textures.as_deref_mut(), atlases.as_deref_mut(), data.shadow)
+        {
+            let resources = SpriteImportResources { images, atlases };
+            let handle = move_extra_atlas(handle_id::shadow_atlas(path), shadow, resources);
+            file_assets.insert_shadow_atlas(handle);
+        }
 
-            let (sprites, atlas_handle) =
-                move_sprites(path_str, data.sprites, resources, file_assets);
-            let atlas = atlases.get(&atlas_handle).unwrap();
-            // Move animations
-            if let Some(animations) = animations {
-                let data = AnimationImportData {
-                    animation_data: data.anims,
-                    sprite_data: sprites,
-                    atlas,
-                    atlas_handle,
+        let mut lod_atlas_handles = Vec::new();
+        if let (Some(images), Some(atlases)) = (textures.as_deref_mut(), atlases.as_deref_mut()) {
+            for (level, lod_atlas) in (1..).zip(data.lod_atlases) {
+                let resources = SpriteImportResources {
+                    images: &mut *images,
+                    atlases: &mut *atlases,
                 };
+                let handle_id = handle_id::lod_atlas(path, level);
+                lod_atlas_handles.push(move_extra_atlas(handle_id, lod_atlas, resources));
+            }
+        }
+        file_assets.set_lod_atlases(lod_atlas_handles.clone());
 
-                move_animations(path_str, data, animations, file_assets);
+        let mut palette_atlas_handles = Vec::new();
+        if let (Some(images), Some(atlases)) = (textures.as_deref_mut(), atlases.as_deref_mut()) {
+            for (number, palette_atlas) in (1..).zip(data.palette_atlases) {
+                let resources = SpriteImportResources {
+                    images: &mut *images,
+                    atlases: &mut *atlases,
+                };
+                let handle_id = handle_id::palette_atlas(path, number);
+                palette_atlas_handles.push(move_extra_atlas(handle_id, palette_atlas, resources));
             }
         }
+        file_assets.set_palette_atlases(palette_atlas_handles.clone());
+
+        let outline_atlas_handle = match (textures.as_deref_mut(), atlases.as_deref_mut()) {
+            (Some(images), Some(atlases)) => data.outline.map(|outline| {
+                let resources = SpriteImportResources { images, atlases };
+                move_extra_atlas(handle_id::outline_atlas(path), outline, resources)
+            }),
+            _ => None,
+        };
+        file_assets.set_outline_atlas(outline_atlas_handle);
+
+        let mut tag_atlas_handles = Vec::new();
+        if let (Some(images), Some(atlases)) = (textures.as_deref_mut(), atlases.as_deref_mut()) {
+            for (tag_name, first_frame, tag_atlas) in data.tag_atlases {
+                let resources = SpriteImportResources {
+                    images: &mut *images,
+                    atlases: &mut *atlases,
+                };
+                let handle_id = handle_id::tag_atlas(path, &tag_name);
+                let handle = move_extra_atlas(handle_id, tag_atlas, resources);
+                file_assets.insert_tag_atlas(tag_name, handle.clone());
+                tag_atlas_handles.push((handle, first_frame));
+            }
+        }
+
+        // Move sprites. Each frame's atlas index is its frame index.
+        let sprite_frames = data
+            .sprites
+            .iter()
+            .map(|sprite| SpriteFrame {
+                atlas_index: sprite.frame as usize,
+                duration: sprite.duration,
+                opaque_bounds: sprite.opaque_bounds,
+                changed_bounds: sprite.changed_bounds,
+            })
+            .collect();
+        let atlas_handle = match (textures.as_deref_mut(), atlases, data.atlas) {
+            (Some(images), Some(atlases), Some(atlas)) => {
+                let resources = SpriteImportResources { images, atlases };
+                move_sprites(path, data.sprites, atlas, resources, file_assets)
+            }
+            // Without a file atlas, frame images are added on their own.
+            (Some(images), _, None) => {
+                for sprite in data.sprites {
+                    let Some(texture) = sprite.texture else {
+                        continue;
+                    };
+                    let handle = images.set(handle_id::frame_image(path, sprite.frame), texture);
+                    file_assets.insert_texture(sprite.frame, handle);
+                }
+                Handle::weak(handle_id::atlas(path))
+            }
+            // Without images there is no atlas. Animations still refer to the
+            // atlas' handle id.
+            _ => Handle::weak(handle_id::atlas(path)),
+        };
+
+        // Move animations
+        if let Some(animations) = animations {
+            let data = AnimationImportData {
+                animation_data: data.anims,
+                sprite_frames,
+                atlas_handle,
+                lod_atlas_handles,
+                palette_atlas_handles,
+                tag_atlas_handles,
+            };
+            move_animations(path, data, animations, events.as_deref_mut(), file_assets);
+        }
+    }
+}
+
+// Removes an imported file's assets from their resources, along with its file map entry.
+// Only assets indexed by the file map can be found, so nothing is removed without one.
+pub(crate) fn remove_from_resources(path: &Path, resources: &mut AseAssetResources) {
+    let (textures, animations, atlases, tilesets, slices, events, collisions, placeholders, index) =
+        resources;
+    let Some(file_assets) = index.as_deref_mut().and_then(|index| index.0.remove(path)) else {
+        return;
+    };
+    if let Some(animations) = animations {
+        for handle in file_assets.animations.values() {
+            animations.remove(handle);
+        }
+    }
+    if let Some(events) = events {
+        for handle in file_assets.animation_events.values() {
+            events.remove(handle);
+        }
+    }
+    if let Some(slices) = slices {
+        for handle in file_assets.slices.values() {
+            slices.remove(handle);
+        }
+    }
+    if let Some(placeholders) = placeholders {
+        for handle in file_assets.text_placeholders.values() {
+            placeholders.remove(handle);
+        }
+    }
+    if let Some(collisions) = collisions {
+        for handle in file_assets.frame_collisions.values() {
+            collisions.remove(handle);
+        }
+    }
+    let mut images = file_assets.textures.into_values().collect::<Vec<_>>();
+    images.extend(file_assets.layer_images.into_values());
+    images.extend(file_assets.thumbnail);
+    if let Some(tilesets) = tilesets {
+        for handle in file_assets.tilesets.values() {
+            images.extend(tilesets.remove(handle).map(|tileset| tileset.texture));
+        }
+    }
+    if let Some(atlases) = atlases {
+        images.extend(
+            atlases
+                .remove(&file_assets.atlas)
+                .map(|atlas| atlas.texture),
+        );
+        let extra_atlases = file_assets
+            .lod_atlases
+            .iter()
+            .chain(&file_assets.palette_atlases)
+            .chain(&file_assets.outline_atlas)
+            .chain(file_assets.tag_atlases.values())
+            .chain(&file_assets.shadow_atlas);
+        for handle in extra_atlases {
+            images.extend(atlases.remove(handle).map(|atlas| atlas.texture));
+        }
+    }
+    if let Some(textures) = textures {
+        for handle in &images {
+            textures.remove(handle);
+        }
     }
 }
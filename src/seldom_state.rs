@@ -0,0 +1,69 @@
+use crate::asset::Animation;
+use crate::player::{AnimationPlayer, PlayMode};
+use bevy::prelude::*;
+use seldom_state::prelude::*;
+
+/// Registers [DoneTrigger] and the system playing [PlayAnimation] states.
+///
+/// Add this plugin alongside seldom_state's `StateMachinePlugin`
+/// and the [AnimationPlayerPlugin](crate::player::AnimationPlayerPlugin).
+pub struct AseSeldomStatePlugin;
+
+impl Plugin for AseSeldomStatePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(TriggerPlugin::<DoneTrigger>::default())
+            .add_system(play_animation_states);
+    }
+}
+
+/// Trigger which fires when the entity's [AnimationPlayer] finished its animation.
+#[derive(Debug, Clone, Copy, Default, FromReflect, Reflect)]
+pub struct DoneTrigger;
+
+impl BoolTrigger for DoneTrigger {
+    type Param<'w, 's> = Query<'w, 's, &'static AnimationPlayer>;
+
+    fn trigger(&self, entity: Entity, players: &Self::Param<'_, '_>) -> bool {
+        players.get(entity).is_ok_and(|player| player.is_finished())
+    }
+}
+
+/// State which plays an [Animation] on the entity's [AnimationPlayer] when entered.
+#[derive(Debug, Clone, Component, Reflect)]
+#[component(storage = "SparseSet")]
+pub struct PlayAnimation {
+    /// The animation to play.
+    pub animation: Handle<Animation>,
+    /// How the animation is played.
+    #[reflect(ignore)]
+    pub mode: PlayMode,
+}
+
+impl PlayAnimation {
+    /// Creates a state playing the animation with [PlayMode::Auto].
+    pub fn new(animation: Handle<Animation>) -> Self {
+        Self {
+            animation,
+            mode: PlayMode::Auto,
+        }
+    }
+
+    /// Creates a state playing the animation once.
+    pub fn once(animation: Handle<Animation>) -> Self {
+        Self {
+            animation,
+            mode: PlayMode::Once,
+        }
+    }
+}
+
+/// System function which starts the animation of each entered [PlayAnimation] state.
+///
+/// This system is already added by [AseSeldomStatePlugin].
+pub fn play_animation_states(
+    mut players: Query<(&PlayAnimation, &mut AnimationPlayer), Added<PlayAnimation>>,
+) {
+    for (state, mut player) in &mut players {
+        *player = AnimationPlayer::new(state.animation.clone()).with_mode(state.mode);
+    }
+}
@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+
+/// Marker component for a shadow sprite spawned with [spawn_shadow].
+///
+/// The [sync_shadows] system keeps each shadow on the frame, flip and custom size of its
+/// parent's [TextureAtlasSprite]. It is added by [AnimationPlayerPlugin](crate::player::AnimationPlayerPlugin).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Component)]
+pub struct AseShadow;
+
+/// Spawns a shadow sprite as a child of `parent`, and returns its entity.
+///
+/// `atlas` is a file's [shadow atlas](crate::asset::AseAssetMap::shadow_atlas).
+/// The shadow is offset along z by `z_offset`, which is usually negative so that the
+/// shadow renders below its parent. Shadows which are the same in every frame have a
+/// single image, and are shown unchanged while the parent animates.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::asset::AseFileMap;
+/// use bevy_ase::shadow::spawn_shadow;
+///
+/// fn spawn_hero(mut commands: Commands, file_map: Res<AseFileMap>) {
+///     let Some(asset_map) = file_map.get("sprites/hero.aseprite") else {
+///         return;
+///     };
+///     let hero = commands
+///         .spawn(asset_map.sprite_sheet_bundle())
+///         .id();
+///     if let Some(shadow_atlas) = asset_map.shadow_atlas() {
+///         spawn_shadow(&mut commands, hero, shadow_atlas.clone(), -0.1);
+///     }
+/// }
+/// ```
+pub fn spawn_shadow(
+    commands: &mut Commands,
+    parent: Entity,
+    atlas: Handle<TextureAtlas>,
+    z_offset: f32,
+) -> Entity {
+    let shadow = commands
+        .spawn((
+            SpriteSheetBundle {
+                texture_atlas: atlas,
+                transform: Transform::from_xyz(0.0, 0.0, z_offset),
+                ..default()
+            },
+            AseShadow,
+        ))
+        .id();
+    commands.entity(parent).add_child(shadow);
+    shadow
+}
+
+/// System function which keeps each [AseShadow] on its parent's frame, flip and custom size.
+///
+/// This system is already added by [AnimationPlayerPlugin](crate::player::AnimationPlayerPlugin).
+pub fn sync_shadows(
+    atlases: Res<Assets<TextureAtlas>>,
+    parents: Query<&TextureAtlasSprite, Without<AseShadow>>,
+    mut shadows: Query<(&Parent, &Handle<TextureAtlas>, &mut TextureAtlasSprite), With<AseShadow>>,
+) {
+    for (parent, atlas, mut sprite) in &mut shadows {
+        let Ok(parent_sprite) = parents.get(parent.get()) else {
+            continue;
+        };
+        let Some(atlas) = atlases.get(atlas) else {
+            continue;
+        };
+        let index = match atlas.len() {
+            0 | 1 => 0,
+            len => parent_sprite.index.min(len - 1),
+        };
+        if sprite.index != index {
+            sprite.index = index;
+        }
+        if sprite.flip_x != parent_sprite.flip_x || sprite.flip_y != parent_sprite.flip_y {
+            sprite.flip_x = parent_sprite.flip_x;
+            sprite.flip_y = parent_sprite.flip_y;
+        }
+        if sprite.custom_size != parent_sprite.custom_size {
+            sprite.custom_size = parent_sprite.custom_size;
+        }
+    }
+}
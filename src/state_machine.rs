@@ -0,0 +1,214 @@
+use crate::asset::Animation;
+use crate::player::{AnimationPlayer, PlayMode};
+use bevy::{prelude::*, utils::HashMap};
+
+/// Condition for a transition between two states of an [AseStateMachine].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Condition {
+    /// The current state's animation finished playing.
+    /// Only animations played once finish; see [PlayMode].
+    Finished,
+    /// The named event was triggered with [AseStateMachine::trigger].
+    Event(String),
+    /// The named flag has the given value. Unset flags are false.
+    Flag(String, bool),
+}
+
+#[derive(Debug, Clone)]
+struct State {
+    name: String,
+    animation: Handle<Animation>,
+    mode: PlayMode,
+}
+
+#[derive(Debug, Clone)]
+struct Transition {
+    from: usize,
+    to: usize,
+    condition: Condition,
+}
+
+/// Component which switches an entity's [AnimationPlayer] between named animation states.
+///
+/// Each state plays one [Animation]. Transitions move from one state to another
+/// when their [Condition] holds, checked in the order they were added.
+/// The [drive_state_machines] system runs the state machine; it is added by
+/// [AnimationPlayerPlugin](crate::player::AnimationPlayerPlugin).
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::asset::Animation;
+/// use bevy_ase::player::PlayMode;
+/// use bevy_ase::state_machine::{AseStateMachine, Condition};
+///
+/// fn spawn_hero(
+///     mut commands: Commands,
+///     idle: Handle<Animation>,
+///     walk: Handle<Animation>,
+///     attack: Handle<Animation>,
+/// ) {
+///     let machine = AseStateMachine::new("idle", idle)
+///         .with_state("walk", walk)
+///         .with_state_mode("attack", attack, PlayMode::Once)
+///         .with_transition("idle", "walk", Condition::Flag("moving".into(), true))
+///         .with_transition("walk", "idle", Condition::Flag("moving".into(), false))
+///         .with_transition("idle", "attack", Condition::Event("attack".into()))
+///         .with_transition("attack", "idle", Condition::Finished);
+///     commands.spawn((SpriteSheetBundle::default(), machine.player(), machine));
+/// }
+///
+/// fn move_hero(mut machines: Query<&mut AseStateMachine>, keys: Res<Input<KeyCode>>) {
+///     for mut machine in &mut machines {
+///         machine.set_flag("moving", keys.pressed(KeyCode::Right));
+///         if keys.just_pressed(KeyCode::Space) {
+///             machine.trigger("attack");
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Component)]
+pub struct AseStateMachine {
+    states: Vec<State>,
+    transitions: Vec<Transition>,
+    current: usize,
+    flags: HashMap<String, bool>,
+    events: Vec<String>,
+}
+
+impl AseStateMachine {
+    /// Creates a state machine starting in the given state, playing its animation with [PlayMode::Auto].
+    pub fn new(initial_state: impl Into<String>, animation: Handle<Animation>) -> Self {
+        Self {
+            states: vec![State {
+                name: initial_state.into(),
+                animation,
+                mode: PlayMode::Auto,
+            }],
+            transitions: Vec::new(),
+            current: 0,
+            flags: HashMap::default(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Adds a state playing the given animation with [PlayMode::Auto].
+    pub fn with_state(self, name: impl Into<String>, animation: Handle<Animation>) -> Self {
+        self.with_state_mode(name, animation, PlayMode::Auto)
+    }
+
+    /// Adds a state playing the given animation with the given [PlayMode].
+    ///
+    /// Adding a state with an existing name replaces that state.
+    pub fn with_state_mode(
+        mut self,
+        name: impl Into<String>,
+        animation: Handle<Animation>,
+        mode: PlayMode,
+    ) -> Self {
+        let state = State {
+            name: name.into(),
+            animation,
+            mode,
+        };
+        match self.state_index(&state.name) {
+            Some(index) => self.states[index] = state,
+            None => self.states.push(state),
+        }
+        self
+    }
+
+    /// Adds a transition between two states.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either state has not been added.
+    pub fn with_transition(mut self, from: &str, to: &str, condition: Condition) -> Self {
+        let from = self
+            .state_index(from)
+            .unwrap_or_else(|| panic!("Unknown state: {}", from));
+        let to = self
+            .state_index(to)
+            .unwrap_or_else(|| panic!("Unknown state: {}", to));
+        self.transitions.push(Transition {
+            from,
+            to,
+            condition,
+        });
+        self
+    }
+
+    /// Returns an [AnimationPlayer] playing the current state's animation.
+    pub fn player(&self) -> AnimationPlayer {
+        let state = &self.states[self.current];
+        AnimationPlayer::new(state.animation.clone()).with_mode(state.mode)
+    }
+
+    /// Returns the name of the current state.
+    pub fn current_state(&self) -> &str {
+        &self.states[self.current].name
+    }
+
+    /// Sets a flag used by [Condition::Flag] transitions.
+    pub fn set_flag(&mut self, name: impl Into<String>, value: bool) {
+        self.flags.insert(name.into(), value);
+    }
+
+    /// Returns the value of a flag. Unset flags are false.
+    pub fn flag(&self, name: &str) -> bool {
+        self.flags.get(name).copied().unwrap_or(false)
+    }
+
+    /// Triggers an event used by [Condition::Event] transitions.
+    ///
+    /// Events are consumed the next time [drive_state_machines] runs,
+    /// whether or not a transition used them.
+    pub fn trigger(&mut self, event: impl Into<String>) {
+        self.events.push(event.into());
+    }
+
+    fn state_index(&self, name: &str) -> Option<usize> {
+        self.states.iter().position(|state| state.name == name)
+    }
+
+    fn holds(&self, condition: &Condition, player: &AnimationPlayer) -> bool {
+        match condition {
+            Condition::Finished => player.is_finished(),
+            Condition::Event(event) => self.events.iter().any(|e| e == event),
+            Condition::Flag(name, value) => self.flag(name) == *value,
+        }
+    }
+
+    // Takes the first transition out of the current state whose condition holds.
+    // Returns true if the state changed.
+    pub(crate) fn update(&mut self, player: &AnimationPlayer) -> bool {
+        let next = self
+            .transitions
+            .iter()
+            .filter(|t| t.from == self.current)
+            .find(|t| self.holds(&t.condition, player))
+            .map(|t| t.to);
+        self.events.clear();
+        match next {
+            Some(next) => {
+                self.current = next;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// System function which runs every [AseStateMachine] and updates its entity's [AnimationPlayer].
+///
+/// This system is already added by [AnimationPlayerPlugin](crate::player::AnimationPlayerPlugin).
+pub fn drive_state_machines(mut machines: Query<(&mut AseStateMachine, &mut AnimationPlayer)>) {
+    for (mut machine, mut player) in &mut machines {
+        let changed = machine.update(&player);
+        // Also restarts players which don't play the current state's animation yet.
+        if changed || player.animation() != &machine.states[machine.current].animation {
+            *player = machine.player();
+        }
+    }
+}
@@ -0,0 +1,268 @@
+use crate::asset::slice::Slice;
+use crate::process::{AtlasUsage, FileHeader, ProcessSettings};
+use crate::processing::ResourceData;
+use asefile::{AsepriteFile, AsepriteParseError};
+use bevy::{prelude::*, utils::HashMap};
+use std::{
+    error::Error,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+/// Reads and parses an Aseprite file from disk.
+pub fn load_file(path: impl AsRef<Path>) -> Result<AsepriteFile, AsepriteParseError> {
+    AsepriteFile::read_file(path.as_ref())
+}
+
+/// Reads an Aseprite file from disk and processes it into [ImportedResources].
+///
+/// This runs the same processing as the [Loader](crate::loader::Loader),
+/// but without a running Bevy app and without moving data into [Assets].
+///
+/// # Examples
+///
+/// ```no_run
+/// use bevy_ase::test_support::load_resources;
+///
+/// // Run as a unit test in your game crate.
+/// fn hero_has_walk_cycle() {
+///     let resources = load_resources("assets/sprites/hero.aseprite").unwrap();
+///     assert!(resources.tag_names().any(|tag| tag == "walk"));
+///     assert_eq!(resources.frame_count(), 12);
+/// }
+/// ```
+pub fn load_resources(path: impl AsRef<Path>) -> Result<ImportedResources, AsepriteParseError> {
+    let path = path.as_ref();
+    let ase = load_file(path)?;
+    let header = fs::read(path)
+        .map(|bytes| FileHeader::read(&bytes))
+        .unwrap_or_default();
+    let settings = ProcessSettings::default();
+    // Default settings have no limits, so processing cannot fail.
+    let data =
+        ResourceData::new(path, &ase, &header, &[], &settings).expect("Import limits exceeded");
+    Ok(ImportedResources(data))
+}
+
+/// Processed data from a single Aseprite file, before it is moved into [Assets].
+pub struct ImportedResources(pub(crate) ResourceData);
+impl ImportedResources {
+    /// Returns the number of frames in the file.
+    pub fn frame_count(&self) -> usize {
+        self.0.sprites.len()
+    }
+    /// Returns the image for the given frame index.
+    pub fn frame_image(&self, frame: u32) -> Option<&Image> {
+        self.0.sprites.get(frame as usize)?.texture.as_ref()
+    }
+    /// Returns the duration of the given frame in milliseconds.
+    pub fn frame_duration_ms(&self, frame: u32) -> Option<u32> {
+        self.0.sprites.get(frame as usize).map(|s| s.duration)
+    }
+    /// Returns the names of all tags which generate an animation.
+    pub fn tag_names(&self) -> impl Iterator<Item = &str> {
+        self.0.anims.iter().filter_map(|a| a.tag_name.as_deref())
+    }
+    /// Returns the frame indices of the animation with the given tag name.
+    pub fn tag_frames(&self, tag_name: &str) -> Option<&[usize]> {
+        self.0
+            .anims
+            .iter()
+            .find(|a| a.tag_name.as_deref() == Some(tag_name))
+            .map(|a| a.sprites.as_slice())
+    }
+    /// Returns how much of the file's atlas is covered by visible pixels.
+    ///
+    /// A test can fail for files which waste too much texture memory.
+    pub fn atlas_usage(&self) -> Option<&AtlasUsage> {
+        self.0.atlas_usage.as_ref()
+    }
+    /// Returns the file's slices.
+    pub fn slices(&self) -> &[Slice] {
+        &self.0.slices
+    }
+    /// Returns the ids of the file's tilesets.
+    pub fn tileset_ids(&self) -> impl Iterator<Item = u32> + '_ {
+        self.0.tilesets.iter().map(|t| t.id)
+    }
+    /// Returns the image for the tileset with the given id.
+    pub fn tileset_image(&self, tileset_id: u32) -> Option<&Image> {
+        self.0
+            .tilesets
+            .iter()
+            .find(|t| t.id == tileset_id)?
+            .texture
+            .as_ref()
+    }
+}
+
+/// The kind of sub-asset an [AseRef] points to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AseRefKind {
+    /// An animation, named by its tag.
+    Animation,
+    /// A slice, named by its slice name.
+    Slice,
+}
+
+/// A reference from game code to a named asset inside an Aseprite file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AseRef {
+    /// Path of the Aseprite file, relative to the assets directory.
+    pub path: PathBuf,
+    /// The kind of the referenced asset.
+    pub kind: AseRefKind,
+    /// The tag or slice name.
+    pub name: String,
+}
+impl AseRef {
+    /// Creates a reference to the animation with the given tag name.
+    pub fn animation(path: impl Into<PathBuf>, tag_name: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            kind: AseRefKind::Animation,
+            name: tag_name.into(),
+        }
+    }
+    /// Creates a reference to the slice with the given name.
+    pub fn slice(path: impl Into<PathBuf>, slice_name: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            kind: AseRefKind::Slice,
+            name: slice_name.into(),
+        }
+    }
+}
+impl fmt::Display for AseRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            AseRefKind::Animation => "animation",
+            AseRefKind::Slice => "slice",
+        };
+        write!(f, "{} \"{}\" in {}", kind, self.name, self.path.display())
+    }
+}
+
+/// Describes why a reference could not be resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MissingReason {
+    /// The referenced file could not be read or parsed.
+    FileNotLoaded(String),
+    /// The file was loaded, but has no asset with the referenced name.
+    NotFound,
+}
+
+/// A reference which could not be resolved, and the reason why.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingRef {
+    /// The unresolved reference.
+    pub reference: AseRef,
+    /// Why the reference could not be resolved.
+    pub reason: MissingReason,
+}
+impl fmt::Display for MissingRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.reason {
+            MissingReason::FileNotLoaded(e) => {
+                write!(f, "{}: file could not be loaded ({})", self.reference, e)
+            }
+            MissingReason::NotFound => write!(f, "{}: not found", self.reference),
+        }
+    }
+}
+
+/// Error returned by [validate_refs] when one or more references are missing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// Every reference which could not be resolved, in input order.
+    pub missing: Vec<MissingRef>,
+}
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} missing Aseprite reference(s):", self.missing.len())?;
+        for missing in &self.missing {
+            writeln!(f, "  {}", missing)?;
+        }
+        Ok(())
+    }
+}
+impl Error for ValidationReport {}
+
+// Names of the animations and slices the importer generates for one file.
+struct ImportedNames {
+    animations: Vec<String>,
+    slices: Vec<String>,
+}
+impl ImportedNames {
+    fn new(resources: ImportedResources) -> Self {
+        let data = resources.0;
+        Self {
+            animations: data.anims.into_iter().filter_map(|a| a.tag_name).collect(),
+            slices: data.slices.into_iter().map(|s| s.name).collect(),
+        }
+    }
+    fn contains(&self, kind: AseRefKind, name: &str) -> bool {
+        let names = match kind {
+            AseRefKind::Animation => &self.animations,
+            AseRefKind::Slice => &self.slices,
+        };
+        names.iter().any(|n| n == name)
+    }
+}
+
+/// Checks that every reference resolves to an asset generated by the importer.
+///
+/// Each referenced file is read from `assets_dir` and processed headlessly,
+/// without a running Bevy app. Each file is only processed once,
+/// no matter how many references point to it.
+///
+/// Returns a [ValidationReport] listing every unresolved reference.
+///
+/// # Examples
+///
+/// ```no_run
+/// use bevy_ase::test_support::{validate_refs, AseRef};
+///
+/// // Run as a unit test in your game crate.
+/// fn asset_references_exist() {
+///     let refs = [
+///         AseRef::animation("sprites/hero.aseprite", "walk"),
+///         AseRef::slice("sprites/hero.aseprite", "hitbox"),
+///     ];
+///     // Fails with a list of missing tags and slices if an artist renamed one.
+///     validate_refs("assets", &refs).unwrap();
+/// }
+/// ```
+pub fn validate_refs(
+    assets_dir: impl AsRef<Path>,
+    refs: &[AseRef],
+) -> Result<(), ValidationReport> {
+    let assets_dir = assets_dir.as_ref();
+    let mut files: HashMap<&Path, Result<ImportedNames, String>> = HashMap::default();
+    let mut missing = Vec::new();
+    for reference in refs {
+        let names = files.entry(reference.path.as_path()).or_insert_with(|| {
+            let full_path = assets_dir.join(&reference.path);
+            let resources = load_resources(full_path).map_err(|e| e.to_string())?;
+            Ok(ImportedNames::new(resources))
+        });
+        let reason = match names {
+            Err(e) => Some(MissingReason::FileNotLoaded(e.clone())),
+            Ok(names) if !names.contains(reference.kind, &reference.name) => {
+                Some(MissingReason::NotFound)
+            }
+            Ok(_) => None,
+        };
+        if let Some(reason) = reason {
+            missing.push(MissingRef {
+                reference: reference.clone(),
+                reason,
+            });
+        }
+    }
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(ValidationReport { missing })
+    }
+}
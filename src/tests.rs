@@ -1,8 +1,6 @@
 use std::path::PathBuf;
 
-use asefile::AsepriteFile;
-
-use crate::processing::{self, ResourceData};
+use crate::test_support::{self, ImportedResources};
 
 fn test_path(name: &str) -> PathBuf {
     let mut path = PathBuf::new();
@@ -12,20 +10,2491 @@ fn test_path(name: &str) -> PathBuf {
     path
 }
 
-fn load_test_file(path: &PathBuf) -> AsepriteFile {
+fn load_test_file_as_assets(name: &str) -> ImportedResources {
+    let path = test_path(name);
     println!("Loading file: {}", path.display());
-    AsepriteFile::read_file(path).unwrap()
+    test_support::load_resources(&path).unwrap()
 }
 
-fn load_test_file_as_assets(name: &str) -> ResourceData {
-    let path = test_path(name);
-    let ase = load_test_file(&path);
-    processing::ResourceData::new(&path, &ase)
+// Updates the app until `done` returns true, or for about five seconds.
+fn update_until(app: &mut bevy::app::App, mut done: impl FnMut(&bevy::app::App) -> bool) {
+    for _ in 0..1000 {
+        app.update();
+        if done(app) {
+            return;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+}
+
+// Updates the app until its Loader has imported every queued file.
+fn update_until_loaded(app: &mut bevy::app::App) {
+    update_until(app, |app| {
+        app.world.resource::<crate::loader::Loader>().is_loaded()
+    });
 }
 
 #[test]
 fn tileset_file() {
     let assets = load_test_file_as_assets("tileset");
-    let tilesets = assets.tilesets;
-    assert_eq!(tilesets.len(), 1);
+    assert_eq!(assets.tileset_ids().count(), 1);
+}
+
+#[test]
+fn animation_alias_and_case_lookup() {
+    use crate::asset::{Animation, AseAssetMap, NameMatching};
+    use bevy::asset::{Handle, HandleId};
+
+    let mut map = AseAssetMap::default();
+    let handle: Handle<Animation> = Handle::weak(HandleId::random::<Animation>());
+    map.insert_animation("walk_cycle".to_string(), handle.clone(), Vec::new());
+    map.add_animation_alias("Walk", "walk_cycle");
+
+    assert_eq!(map.animation("walk_cycle"), Some(&handle));
+    assert_eq!(map.animation("Walk"), Some(&handle));
+    assert_eq!(map.animation("WALK"), None);
+
+    map.set_name_matching(NameMatching::CaseInsensitive);
+    assert_eq!(map.animation("WALK"), Some(&handle));
+    assert_eq!(map.animation("Walk_Cycle"), Some(&handle));
+    assert_eq!(map.animation("run"), None);
+}
+
+#[test]
+fn file_map_animation_carries_metadata() {
+    use crate::asset::{Animation, AseFileMap};
+    use bevy::asset::{Handle, HandleId};
+    use std::path::Path;
+
+    let mut file_map = AseFileMap::default();
+    let handle: Handle<Animation> = Handle::weak(HandleId::random::<Animation>());
+    let asset_map = file_map.get_mut(Path::new("sprites/hero.aseprite"));
+    let frames = (0..8).map(|frame| (frame, frame)).collect();
+    asset_map.insert_animation("walk_cycle".to_string(), handle.clone(), frames);
+    asset_map.add_animation_alias("walk", "walk_cycle");
+
+    let walk = file_map.animation("sprites/hero.aseprite", "walk").unwrap();
+    assert_eq!(walk.handle(), &handle);
+    assert_eq!(walk.path(), Path::new("sprites/hero.aseprite"));
+    assert_eq!(walk.tag_name(), "walk_cycle");
+    assert_eq!(walk.frame_count(), 8);
+    assert_eq!(
+        walk.to_string(),
+        "sprites/hero.aseprite#Animation/walk_cycle (8 frames)"
+    );
+    assert_eq!(Handle::from(walk), handle);
+}
+
+#[test]
+fn animation_group_strips_group_prefix() {
+    use crate::asset::{Animation, AseAssetMap};
+    use bevy::asset::{Handle, HandleId};
+
+    let mut map = AseAssetMap::default();
+    for tag_name in ["attack/light", "attack/air/down", "attacked", "idle"] {
+        let handle: Handle<Animation> = Handle::weak(HandleId::random::<Animation>());
+        map.insert_animation(tag_name.to_string(), handle, vec![(0, 0)]);
+    }
+
+    let mut names: Vec<_> = map.animation_group("attack").into_keys().collect();
+    names.sort();
+    assert_eq!(names, ["air/down", "light"]);
+    assert_eq!(
+        map.animation_group("attack/air").get("down"),
+        map.animation("attack/air/down")
+    );
+    assert!(map.animation_group("Attack").is_empty());
+}
+
+#[test]
+fn validate_refs_reports_missing() {
+    use crate::test_support::{validate_refs, AseRef, MissingReason};
+
+    let refs = [
+        AseRef::animation("tileset.aseprite", "no_such_tag"),
+        AseRef::slice("no_such_file.aseprite", "no_such_slice"),
+    ];
+    let report = validate_refs(PathBuf::from("tests").join("data"), &refs).unwrap_err();
+    assert_eq!(report.missing.len(), 2);
+    assert_eq!(report.missing[0].reason, MissingReason::NotFound);
+    assert!(matches!(
+        report.missing[1].reason,
+        MissingReason::FileNotLoaded(_)
+    ));
+}
+
+#[test]
+fn headless_app_imports_file() {
+    use crate::asset::{AseAsset, AseFileMap};
+    use crate::loader::{AseLoaderDefaultPlugin, Loader};
+    use bevy::prelude::*;
+    use std::path::Path;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(AseLoaderDefaultPlugin);
+    let handle: Handle<AseAsset> = app
+        .world
+        .resource::<AssetServer>()
+        .load("sprites/hello.aseprite");
+    app.world.resource_mut::<Loader>().add(handle);
+    let queued = app
+        .world
+        .resource::<Loader>()
+        .queued_paths(app.world.resource::<AssetServer>());
+    assert_eq!(queued, [Path::new("sprites/hello.aseprite")]);
+
+    update_until_loaded(&mut app);
+    let loader = app.world.resource::<Loader>();
+    assert!(loader.is_loaded());
+    assert!(loader.queued_paths(app.world.resource()).is_empty());
+    assert_eq!(loader.processing_paths().count(), 0);
+    let file_map = app.world.resource::<AseFileMap>();
+    assert!(file_map.get(Path::new("sprites/hello.aseprite")).is_some());
+    let labels = file_map.labels("sprites/hello.aseprite");
+    assert!(labels.contains(&"Atlas".to_string()));
+    assert!(labels.contains(&"FrameImage0".to_string()));
+}
+
+#[test]
+fn folder_watch_queues_every_file() {
+    use crate::asset::AseFileMap;
+    use crate::loader::AseLoaderDefaultPlugin;
+    use crate::watch::{AseFolderEvent, AseFolderWatchPlugin};
+    use bevy::prelude::*;
+    use std::path::Path;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(AseLoaderDefaultPlugin)
+        .add_plugin(AseFolderWatchPlugin::new("sprites"));
+    app.update();
+    let added = app.world.resource::<Events<AseFolderEvent>>().len();
+    assert_eq!(added, 2);
+
+    update_until_loaded(&mut app);
+    let file_map = app.world.resource::<AseFileMap>();
+    assert!(file_map.get(Path::new("sprites/hello.aseprite")).is_some());
+    assert!(file_map.get(Path::new("sprites/tiles.aseprite")).is_some());
+}
+
+#[test]
+fn batch_keeps_queue_order() {
+    use crate::process::ProcessSettings;
+    use crate::processing::ResourceDataByFile;
+    use asefile::AsepriteFile;
+
+    let paths: Vec<PathBuf> = ["tileset", "groups", "indexed", "grayscale", "reference"]
+        .into_iter()
+        .map(test_path)
+        .collect();
+    let files = paths
+        .iter()
+        .map(|path| {
+            let file = AsepriteFile::read_file(path).unwrap();
+            (path.clone(), file, Vec::new())
+        })
+        .collect();
+    let batch = ResourceDataByFile::new(files, &ProcessSettings::default());
+    let order: Vec<PathBuf> = batch.into_files().map(|(path, _)| path).collect();
+    assert_eq!(order, paths);
+}
+
+#[test]
+fn atlas_contains_every_frame() {
+    use crate::process::{process_file, ProcessSettings};
+
+    let path = test_path("tileset");
+    let ase = test_support::load_file(&path).unwrap();
+    let processed = process_file(&ase, &ProcessSettings::default());
+    let atlas = processed.atlas.unwrap();
+    assert_eq!(atlas.frames.len(), processed.frames.len());
+    let expected_len = atlas.image.width as usize * atlas.image.height as usize * 4;
+    assert_eq!(atlas.image.data.len(), expected_len);
+    for (frame, rect) in processed.frames.iter().zip(&atlas.frames) {
+        let image = frame.image.as_ref().unwrap();
+        assert_eq!((rect.width, rect.height), (image.width, image.height));
+        assert!(rect.x + rect.width <= atlas.image.width);
+        assert!(rect.y + rect.height <= atlas.image.height);
+        // The first row of each frame is copied into the atlas.
+        let row_len = image.width as usize * 4;
+        let start = (rect.y as usize * atlas.image.width as usize + rect.x as usize) * 4;
+        assert_eq!(
+            &atlas.image.data[start..start + row_len],
+            &image.data[..row_len]
+        );
+    }
+}
+
+#[test]
+fn raw_image_conversion_keeps_buffer() {
+    use crate::process::RawImage;
+    use bevy::prelude::Image;
+
+    let raw = RawImage {
+        width: 2,
+        height: 2,
+        data: vec![255; 16],
+    };
+    let buffer = raw.data.as_ptr();
+    let image: Image = raw.into();
+    assert_eq!(image.data.as_ptr(), buffer);
+}
+
+#[test]
+fn import_limits_reject_large_files() {
+    use crate::process::{check_limits, ImportLimits, LimitExceeded};
+
+    let file = test_support::load_file(test_path("tileset")).unwrap();
+    assert_eq!(check_limits(&file, &ImportLimits::default()), Ok(()));
+    let limits = ImportLimits {
+        max_canvas_width: Some(1),
+        ..Default::default()
+    };
+    assert!(matches!(
+        check_limits(&file, &limits),
+        Err(LimitExceeded::CanvasSize { .. })
+    ));
+    let limits = ImportLimits {
+        max_atlas_bytes: Some(0),
+        ..Default::default()
+    };
+    assert!(matches!(
+        check_limits(&file, &limits),
+        Err(LimitExceeded::AtlasBytes(_))
+    ));
+}
+
+#[test]
+fn file_map_lookup_by_handle() {
+    use crate::asset::{AseAsset, AseFileMap};
+    use crate::handle_id;
+    use bevy::asset::Handle;
+    use std::path::Path;
+
+    let path = Path::new("sprites/hello.aseprite");
+    let mut file_map = AseFileMap::default();
+    file_map.get_mut(path);
+    let handle = Handle::<AseAsset>::weak("sprites/hello.aseprite".into());
+    assert!(file_map.get_by_handle(&handle).is_some());
+    assert!(file_map.labels(path).is_empty());
+    assert_eq!(
+        handle_id::atlas(path),
+        handle_id::atlas("sprites/hello.aseprite")
+    );
+}
+
+#[test]
+fn player_holds_frames() {
+    use crate::asset::{Animation, Frame, Sprite};
+    use crate::player::{AnimationPlayer, HOLD_FRAME_DURATION_MS};
+    use bevy::{asset::Handle, utils::Duration};
+
+    let frame = |atlas_index, duration_ms| Frame {
+        sprite: Sprite { atlas_index },
+        duration_ms,
+        opaque_bounds: None,
+        changed_bounds: None,
+    };
+    let frames = vec![
+        frame(0, 100),
+        frame(1, HOLD_FRAME_DURATION_MS),
+        frame(2, 100),
+    ];
+    let animation = Animation::new(frames, Handle::default());
+    let ms = Duration::from_millis;
+
+    let mut player = AnimationPlayer::once(Handle::default()).with_hold_last_frame(true);
+    player.tick(&animation, ms(150));
+    assert!(player.is_held());
+    assert_eq!(player.frame(), 1);
+    player.resume();
+    assert!(!player.tick(&animation, ms(50)));
+    assert_eq!(player.frame(), 2);
+    assert!(player.tick(&animation, ms(100)));
+    assert!(player.is_finished());
+    assert_eq!(player.frame(), 2);
+
+    player.hold_at(0);
+    player.tick(&animation, ms(1000));
+    assert_eq!(player.frame(), 0);
+}
+
+#[test]
+fn animation_frame_at_normalized_time() {
+    use crate::asset::{Animation, Frame, Sprite};
+    use bevy::asset::Handle;
+
+    let frame = |atlas_index, duration_ms| Frame {
+        sprite: Sprite { atlas_index },
+        duration_ms,
+        opaque_bounds: None,
+        changed_bounds: None,
+    };
+    let animation = Animation::new(vec![frame(0, 100), frame(1, 300)], Handle::default());
+    assert_eq!(animation.duration_ms(), 400);
+    assert_eq!(animation.frame_at(0.0), Some(0));
+    assert_eq!(animation.frame_at(0.2), Some(0));
+    assert_eq!(animation.frame_at(0.25), Some(1));
+    assert_eq!(animation.frame_at(1.0), Some(1));
+    assert_eq!(animation.frame_at(-1.0), Some(0));
+    assert_eq!(animation.frame_at(2.0), Some(1));
+
+    let empty = Animation::new(Vec::new(), Handle::default());
+    assert_eq!(empty.frame_at(0.5), None);
+}
+
+#[test]
+fn player_auto_mode_uses_tag_repeat() {
+    use crate::asset::{Animation, Frame, Sprite};
+    use crate::player::AnimationPlayer;
+    use bevy::{asset::Handle, utils::Duration};
+
+    let frames = || {
+        vec![Frame {
+            sprite: Sprite { atlas_index: 0 },
+            duration_ms: 100,
+            opaque_bounds: None,
+            changed_bounds: None,
+        }]
+    };
+    let one_shot = Animation::new(frames(), Handle::default()).with_repeat(Some(1));
+    let looping = Animation::new(frames(), Handle::default()).with_repeat(Some(0));
+
+    let mut player = AnimationPlayer::new(Handle::default());
+    assert!(player.tick(&one_shot, Duration::from_millis(100)));
+    let mut player = AnimationPlayer::new(Handle::default());
+    assert!(!player.tick(&looping, Duration::from_millis(100)));
+}
+
+#[test]
+fn tag_repeat_is_read_from_file() {
+    use crate::process::{process_file_with_header, FileHeader, ProcessSettings};
+
+    // A copy of hello.aseprite whose "Blue" tag plays once and "Green" tag three times.
+    let path = test_path("repeat");
+    let ase = test_support::load_file(&path).unwrap();
+    let header = FileHeader::read(&std::fs::read(&path).unwrap());
+    let processed = process_file_with_header(&ase, &header, &ProcessSettings::default());
+    let repeat = |name: &str| {
+        processed
+            .animations
+            .iter()
+            .find(|anim| anim.tag_name.as_deref() == Some(name))
+            .and_then(|anim| anim.repeat)
+    };
+    assert_eq!(repeat("Blue"), Some(1));
+    assert_eq!(repeat("Green"), Some(3));
+}
+
+#[test]
+fn player_transitions_at_end_of_loop() {
+    use crate::asset::{Animation, Frame, Sprite};
+    use crate::player::{AnimationPlayer, PlayMode, TransitionPoint};
+    use bevy::{
+        asset::{Handle, HandleId},
+        utils::Duration,
+    };
+
+    let frames = (0..2)
+        .map(|atlas_index| Frame {
+            sprite: Sprite { atlas_index },
+            duration_ms: 100,
+            opaque_bounds: None,
+            changed_bounds: None,
+        })
+        .collect();
+    let walk = Animation::new(frames, Handle::default());
+    let run = Handle::weak(HandleId::random::<Animation>());
+
+    let mut player = AnimationPlayer::new(Handle::default()).with_mode(PlayMode::Loop);
+    player.transition_to(run.clone(), TransitionPoint::EndOfLoop);
+    player.tick(&walk, Duration::from_millis(150));
+    assert_eq!(player.frame(), 1);
+    assert_ne!(player.animation(), &run);
+    player.tick(&walk, Duration::from_millis(100));
+    assert_eq!(player.animation(), &run);
+    assert_eq!(player.frame(), 0);
+    assert!(player.pending_transition().is_none());
+}
+
+#[test]
+fn state_machine_follows_transitions() {
+    use crate::asset::Animation;
+    use crate::state_machine::{AseStateMachine, Condition};
+    use bevy::asset::{Handle, HandleId};
+
+    let idle = Handle::<Animation>::weak(HandleId::random::<Animation>());
+    let walk = Handle::<Animation>::weak(HandleId::random::<Animation>());
+    let mut machine = AseStateMachine::new("idle", idle)
+        .with_state("walk", walk.clone())
+        .with_transition("idle", "walk", Condition::Flag("moving".into(), true))
+        .with_transition("walk", "idle", Condition::Event("stop".into()));
+    let player = machine.player();
+
+    assert!(!machine.update(&player));
+    machine.set_flag("moving", true);
+    assert!(machine.update(&player));
+    assert_eq!(machine.current_state(), "walk");
+    assert_eq!(machine.player().animation(), &walk);
+    machine.trigger("stop");
+    assert!(machine.update(&player));
+    assert_eq!(machine.current_state(), "idle");
+}
+
+#[test]
+fn player_catch_up_policy() {
+    use crate::asset::{Animation, Frame, Sprite};
+    use crate::player::{AnimationPlayer, CatchUpPolicy, PlayMode, PlayerClock};
+    use bevy::{asset::Handle, utils::Duration};
+
+    let frames = (0..4)
+        .map(|atlas_index| Frame {
+            sprite: Sprite { atlas_index },
+            duration_ms: 100,
+            opaque_bounds: None,
+            changed_bounds: None,
+        })
+        .collect();
+    let animation = Animation::new(frames, Handle::default());
+    let hitch = Duration::from_millis(10_250);
+
+    let mut player = AnimationPlayer::new(Handle::default()).with_mode(PlayMode::Loop);
+    let mut entered = Vec::new();
+    let clock = PlayerClock::Nanos;
+    player.tick_with(
+        &animation,
+        hitch,
+        &CatchUpPolicy::default(),
+        clock,
+        &mut entered,
+    );
+    assert_eq!(player.frame(), 2);
+    // The first frame, then every frame passed.
+    assert_eq!(entered.len(), 103);
+
+    let policy = CatchUpPolicy {
+        skip_whole_loops: true,
+        ..Default::default()
+    };
+    let mut player = AnimationPlayer::new(Handle::default()).with_mode(PlayMode::Loop);
+    entered.clear();
+    player.tick_with(&animation, hitch, &policy, clock, &mut entered);
+    assert_eq!(player.frame(), 2);
+    assert_eq!(entered, vec![0, 1, 2]);
+
+    let policy = CatchUpPolicy {
+        max_delta: Some(Duration::from_millis(150)),
+        ..Default::default()
+    };
+    let mut player = AnimationPlayer::new(Handle::default()).with_mode(PlayMode::Loop);
+    player.tick_with(&animation, hitch, &policy, clock, &mut Vec::new());
+    assert_eq!(player.frame(), 1);
+}
+
+#[test]
+fn micros_clock_does_not_drift() {
+    use crate::asset::{Animation, Frame, Sprite};
+    use crate::player::{micros_delta, AnimationPlayer, CatchUpPolicy, PlayMode, PlayerClock};
+    use bevy::{asset::Handle, utils::Duration};
+
+    // An hour of 60 FPS updates, whose delta times aren't whole microseconds.
+    let delta = Duration::from_nanos(16_666_667);
+    let updates = 60 * 60 * 60;
+    let mut carried_nanos = 0;
+    let mut total = Duration::ZERO;
+    for _ in 0..updates {
+        total += micros_delta(delta, &mut carried_nanos);
+    }
+    assert_eq!(total + Duration::from_nanos(carried_nanos), delta * updates);
+
+    let frames = (0..7)
+        .map(|atlas_index| Frame {
+            sprite: Sprite { atlas_index },
+            duration_ms: 30,
+            opaque_bounds: None,
+            changed_bounds: None,
+        })
+        .collect();
+    let animation = Animation::new(frames, Handle::default());
+    let mut player = AnimationPlayer::new(Handle::default()).with_mode(PlayMode::Loop);
+    let policy = CatchUpPolicy::default();
+    let mut carried_nanos = 0;
+    for _ in 0..updates {
+        let delta = micros_delta(delta, &mut carried_nanos);
+        player.tick_with(
+            &animation,
+            delta,
+            &policy,
+            PlayerClock::Micros,
+            &mut Vec::new(),
+        );
+    }
+    // 3600.000072 seconds in 30 ms frames.
+    let frames_passed = (delta * updates).as_millis() / 30;
+    assert_eq!(player.frame(), (frames_passed % 7) as usize);
+}
+
+#[test]
+fn player_applies_speed_multipliers() {
+    use crate::asset::{Animation, Frame, Sprite};
+    use crate::player::{AnimationPlayer, PlayMode};
+    use bevy::{asset::Handle, utils::Duration};
+
+    let frames = (0..3)
+        .map(|atlas_index| Frame {
+            sprite: Sprite { atlas_index },
+            duration_ms: 100,
+            opaque_bounds: None,
+            changed_bounds: None,
+        })
+        .collect();
+    let mut animation = Animation::new(frames, Handle::default()).with_speed(2.0);
+    animation.set_frame_speeds(vec![1.0, 0.5]);
+    assert_eq!(animation.frame_speed(0), 2.0);
+    assert_eq!(animation.frame_speed(1), 1.0);
+    assert_eq!(animation.frame_speed(2), 2.0);
+
+    let mut player = AnimationPlayer::new(Handle::default()).with_mode(PlayMode::Loop);
+    player.tick(&animation, Duration::from_millis(60));
+    assert_eq!(player.frame(), 1);
+    player.tick(&animation, Duration::from_millis(80));
+    assert_eq!(player.frame(), 1);
+    player.tick(&animation, Duration::from_millis(10));
+    assert_eq!(player.frame(), 2);
+
+    animation.set_speed(f32::MIN_POSITIVE);
+    player.tick(&animation, Duration::from_secs(1));
+    assert_eq!(player.frame(), 2);
+}
+
+#[test]
+fn animation_events_by_frame() {
+    use crate::asset::{AnimationEvent, AnimationEvents};
+
+    let event = |frame, name: &str| AnimationEvent {
+        frame,
+        name: name.to_string(),
+        payload: None,
+    };
+    let events = AnimationEvents::new(vec![
+        event(3, "footstep"),
+        event(0, "start"),
+        event(1, "footstep"),
+        event(3, "sound"),
+    ]);
+    assert_eq!(events.events()[0].name, "start");
+    assert_eq!(events.at_frame(3).len(), 2);
+    assert!(events.at_frame(2).is_empty());
+    assert_eq!(events.named("footstep").count(), 2);
+}
+
+#[test]
+fn registry_maps_fired_events() {
+    use crate::asset::AnimationEvent;
+    use crate::event_registry::AnimationEventRegistryPlugin;
+    use crate::player::AnimationEventFired;
+    use bevy::prelude::*;
+
+    struct HitFrame(u32);
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_event::<AnimationEventFired>()
+        .add_plugin(AnimationEventRegistryPlugin::new().with("hit", |fired| {
+            Some(HitFrame(fired.event.payload.as_deref()?.parse().ok()?))
+        }));
+    for (name, payload) in [("hit", Some("12")), ("step", None), ("hit", Some("x"))] {
+        app.world.send_event(AnimationEventFired {
+            entity: Entity::from_raw(0),
+            animation: Handle::default(),
+            event: AnimationEvent {
+                frame: 0,
+                name: name.to_string(),
+                payload: payload.map(str::to_string),
+            },
+        });
+    }
+    app.update();
+
+    let hits = app.world.resource::<Events<HitFrame>>();
+    let damage: Vec<u32> = hits.get_reader().iter(hits).map(|hit| hit.0).collect();
+    assert_eq!(damage, [12]);
+}
+
+#[test]
+fn tint_applies_tag_color() {
+    use crate::asset::Animation;
+    use crate::player::AnimationPlayer;
+    use crate::tint::{apply_tints, AseTint};
+    use bevy::prelude::*;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_asset::<Animation>()
+        .add_system(apply_tints);
+    let red = Color::rgba_u8(255, 0, 0, 255);
+    let animation = Animation::new(Vec::new(), Handle::default()).with_color(Some(red));
+    let handle = app.world.resource_mut::<Assets<Animation>>().add(animation);
+    let entity = app
+        .world
+        .spawn((
+            TextureAtlasSprite::default(),
+            AnimationPlayer::new(handle),
+            AseTint::Tag,
+        ))
+        .id();
+
+    app.update();
+    let entity = app.world.entity(entity);
+    assert_eq!(entity.get::<TextureAtlasSprite>().unwrap().color, red);
+    assert!(!entity.contains::<AseTint>());
+}
+
+#[test]
+fn player_applies_mirrored_animation_flip() {
+    use crate::asset::{Animation, Frame, Sprite};
+    use crate::player::{
+        animate_sprites, AnimationEventFired, AnimationFinished, AnimationPlayer, FrameChanged,
+    };
+    use bevy::prelude::*;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_asset::<Animation>()
+        .add_event::<AnimationFinished>()
+        .add_event::<FrameChanged>()
+        .add_event::<AnimationEventFired>()
+        .add_system(animate_sprites);
+    let frames = || {
+        vec![Frame {
+            sprite: Sprite { atlas_index: 0 },
+            duration_ms: 100,
+            opaque_bounds: None,
+            changed_bounds: None,
+        }]
+    };
+    let mut animations = app.world.resource_mut::<Assets<Animation>>();
+    let walk_right = animations.add(Animation::new(frames(), Handle::default()));
+    let walk_left =
+        animations.add(Animation::new(frames(), Handle::default()).with_flip(true, false));
+    let sprite = TextureAtlasSprite {
+        flip_y: true,
+        ..default()
+    };
+    let entity = app
+        .world
+        .spawn((
+            sprite,
+            Handle::<TextureAtlas>::default(),
+            AnimationPlayer::new(walk_left),
+        ))
+        .id();
+
+    app.update();
+    let sprite = app.world.get::<TextureAtlasSprite>(entity).unwrap();
+    assert!(sprite.flip_x);
+    assert!(sprite.flip_y);
+
+    app.world
+        .get_mut::<AnimationPlayer>(entity)
+        .unwrap()
+        .play(walk_right);
+    app.update();
+    let sprite = app.world.get::<TextureAtlasSprite>(entity).unwrap();
+    assert!(!sprite.flip_x);
+    assert!(sprite.flip_y);
+}
+
+#[test]
+fn nine_slice_mesh_keeps_corners() {
+    use crate::asset::slice::{Slice9, SliceKey};
+    use crate::nine_slice::nine_slice_mesh;
+    use bevy::render::mesh::VertexAttributeValues;
+    use bevy::{prelude::*, sprite::TextureAtlas};
+
+    let mut atlas = TextureAtlas::new_empty(Handle::default(), Vec2::new(32.0, 16.0));
+    atlas.add_texture(Rect::new(16.0, 0.0, 32.0, 16.0));
+    let key = SliceKey {
+        from_frame: 0,
+        origin: (2, 2),
+        size: (12, 12),
+        slice9: Some(Slice9 {
+            center_x: 4,
+            center_y: 4,
+            center_width: 4,
+            center_height: 4,
+        }),
+        pivot: None,
+    };
+    assert!(nine_slice_mesh(&key, &atlas, 1, Vec2::ONE).is_none());
+
+    let mesh = nine_slice_mesh(&key, &atlas, 0, Vec2::new(48.0, 12.0)).unwrap();
+    let Some(VertexAttributeValues::Float32x3(positions)) =
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+    else {
+        panic!("Expected positions");
+    };
+    let xs: Vec<f32> = positions[..4].iter().map(|p| p[0]).collect();
+    assert_eq!(xs, [-24.0, -20.0, 20.0, 24.0]);
+    let uvs = mesh.attribute(Mesh::ATTRIBUTE_UV_0);
+    let Some(VertexAttributeValues::Float32x2(uvs)) = uvs else {
+        panic!("Expected UVs");
+    };
+    assert_eq!(uvs[0], [18.0 / 32.0, 2.0 / 16.0]);
+    assert_eq!(uvs[15], [30.0 / 32.0, 14.0 / 16.0]);
+}
+
+#[test]
+fn slice_rects_flip_y() {
+    use crate::asset::slice::{self, SliceKey};
+    use bevy::math::{Rect, Vec2};
+
+    let key = SliceKey {
+        from_frame: 0,
+        origin: (4, 2),
+        size: (8, 6),
+        slice9: None,
+        pivot: None,
+    };
+    assert_eq!(slice::rect(&key), Rect::new(4.0, 2.0, 12.0, 8.0));
+    let canvas_size = Vec2::new(16.0, 16.0);
+    assert_eq!(
+        slice::sprite_rect(&key, canvas_size),
+        Rect::new(-4.0, 0.0, 4.0, 6.0)
+    );
+    assert!(slice::center_rect(&key).is_none());
+}
+
+#[test]
+fn text_slices_become_placeholders() {
+    use crate::asset::slice::{Slice, SliceKey};
+    use crate::asset::UserData;
+    use crate::process::text_placeholder;
+    use bevy::math::Rect;
+
+    let slice = |text: &str| Slice {
+        name: "title".to_string(),
+        keys: vec![SliceKey {
+            from_frame: 0,
+            origin: (4, 2),
+            size: (8, 6),
+            slice9: None,
+            pivot: None,
+        }],
+        user_data: Some(UserData {
+            text: Some(text.to_string()),
+            color: None,
+        }),
+    };
+
+    let placeholder = text_placeholder(&slice("text:menu.title z:1")).unwrap();
+    assert_eq!(placeholder.name, "title");
+    assert_eq!(placeholder.key, "menu.title");
+    assert_eq!(placeholder.rect, Rect::new(4.0, 2.0, 12.0, 8.0));
+    assert!(text_placeholder(&slice("z:1 text:menu.title")).is_none());
+    assert!(text_placeholder(&slice("text:")).is_none());
+}
+
+#[test]
+fn pixel_to_local_uses_anchor() {
+    use crate::asset::AseAssetMap;
+    use bevy::{math::Vec2, sprite::Anchor};
+
+    let mut asset_map = AseAssetMap::default();
+    asset_map.set_canvas_size(Vec2::new(32.0, 16.0));
+    let pixel = Vec2::new(24.0, 4.0);
+    assert_eq!(
+        asset_map.pixel_to_local(pixel, &Anchor::Center),
+        Vec2::new(8.0, 4.0)
+    );
+    assert_eq!(
+        asset_map.pixel_to_local(pixel, &Anchor::BottomLeft),
+        Vec2::new(24.0, 12.0)
+    );
+}
+
+#[test]
+fn pixels_per_unit_sets_custom_size() {
+    use crate::asset::AseAssetMap;
+    use bevy::math::Vec2;
+
+    let mut asset_map = AseAssetMap::default();
+    asset_map.set_canvas_size(Vec2::new(32.0, 64.0));
+    assert_eq!(asset_map.world_size(), Vec2::new(32.0, 64.0));
+    assert_eq!(asset_map.custom_size(), None);
+
+    asset_map.set_pixels_per_unit(Some(32.0));
+    assert_eq!(asset_map.world_size(), Vec2::new(1.0, 2.0));
+    let bundle = asset_map.sprite_sheet_bundle();
+    assert_eq!(bundle.sprite.custom_size, Some(Vec2::new(1.0, 2.0)));
+}
+
+#[test]
+fn pixel_scale_from_ratio() {
+    use crate::asset::AseAssetMap;
+    use bevy::math::Vec2;
+
+    let mut asset_map = AseAssetMap::default();
+    assert_eq!(asset_map.pixel_scale(), Vec2::ONE);
+    asset_map.set_pixel_ratio((2, 1));
+    assert_eq!(asset_map.pixel_scale(), Vec2::new(2.0, 1.0));
+    asset_map.set_pixel_ratio((1, 1));
+    assert_eq!(asset_map.pixel_scale(), Vec2::ONE);
+}
+
+#[test]
+fn pixel_ratio_is_read_from_file() {
+    use crate::process::{process_file_with_header, FileHeader, ProcessSettings};
+
+    // A copy of hello.aseprite with 2:1 pixels.
+    let path = test_path("pixel_ratio");
+    let ase = test_support::load_file(&path).unwrap();
+    let header = FileHeader::read(&std::fs::read(&path).unwrap());
+    assert_eq!(header.pixel_ratio, (2, 1));
+    let processed = process_file_with_header(&ase, &header, &ProcessSettings::default());
+    assert_eq!(processed.pixel_ratio, (2, 1));
+    let processed = process_file_with_header(&ase, &FileHeader::default(), &Default::default());
+    assert_eq!(processed.pixel_ratio, (1, 1));
+}
+
+#[test]
+fn color_profile_warnings() {
+    use crate::process::{ColorProfile, ColorProfileHandling};
+
+    assert!(ColorProfileHandling::Warn.warns(ColorProfile::Icc));
+    assert!(!ColorProfileHandling::Warn.warns(ColorProfile::Srgb));
+    assert!(!ColorProfileHandling::Ignore.warns(ColorProfile::Icc));
+}
+
+#[test]
+fn icc_profile_is_reported() {
+    use crate::process::{
+        process_file_with_header, ColorProfile, ColorProfileHandling, FileHeader, ProcessSettings,
+    };
+
+    // A file with an embedded ICC profile.
+    let path = test_path("icc");
+    let ase = test_support::load_file(&path).unwrap();
+    let header = FileHeader::read(&std::fs::read(&path).unwrap());
+    assert_eq!(header.color_profile, ColorProfile::Icc);
+    let processed = process_file_with_header(&ase, &header, &ProcessSettings::default());
+    assert!(ColorProfileHandling::default().warns(processed.color_profile));
+
+    let header = FileHeader::read(&std::fs::read(test_path("tileset")).unwrap());
+    assert_eq!(header.color_profile, ColorProfile::Srgb);
+}
+
+// Checks a 4x4 test file with an opaque pixel at (0, 0), a transparent pixel at (1, 0),
+// a tileset of two 2x2 tiles and a slice.
+fn check_color_mode_file(name: &str, opaque: [u8; 4], tile: [u8; 4]) {
+    use crate::process::{process_file, PixelRect, ProcessSettings};
+    use asefile::AsepriteFile;
+    use bevy::{prelude::Image, render::render_resource::TextureFormat};
+
+    let file = AsepriteFile::read_file(&test_path(name)).unwrap();
+    let processed = process_file(&file, &ProcessSettings::default());
+    assert_eq!(processed.frames.len(), 2);
+    let frame = processed.frames[0].image.clone().unwrap();
+    assert_eq!(frame.data[..4], opaque);
+    assert_eq!(frame.data[7], 0);
+    let atlas = processed.atlas.unwrap();
+    assert_eq!(atlas.frames.len(), 2);
+    let bounds = PixelRect {
+        x: 1,
+        y: 1,
+        width: 2,
+        height: 2,
+    };
+    assert_eq!(processed.frames[1].opaque_bounds, Some(bounds));
+
+    let tileset = &processed.tilesets[0];
+    assert_eq!(tileset.tile_count, 2);
+    let tiles = tileset.image.as_ref().unwrap();
+    assert_eq!(tiles.data[3], 0);
+    assert_eq!(tiles.data[16..20], tile);
+
+    assert_eq!(processed.slices[0].name, "hitbox");
+    assert!(!processed.is_partial());
+    assert_eq!(
+        Image::from(frame).texture_descriptor.format,
+        TextureFormat::Rgba8UnormSrgb
+    );
+}
+
+#[test]
+fn indexed_color_mode() {
+    check_color_mode_file("indexed", [255, 0, 0, 255], [0, 0, 255, 255]);
+}
+
+#[test]
+fn grayscale_color_mode() {
+    check_color_mode_file("grayscale", [255, 255, 255, 255], [128, 128, 128, 255]);
+}
+
+#[test]
+fn raw_image_into_format() {
+    use crate::process::{RawImage, UnsupportedFormat};
+    use bevy::render::render_resource::TextureFormat;
+
+    let raw = RawImage {
+        width: 2,
+        height: 1,
+        data: vec![10, 20, 30, 255, 40, 50, 60, 255],
+    };
+    let mask = raw.clone().into_image(TextureFormat::R8Unorm).unwrap();
+    assert_eq!(mask.data, [10, 40]);
+    assert_eq!(mask.texture_descriptor.format, TextureFormat::R8Unorm);
+    let linear = raw.clone().into_image(TextureFormat::Rgba8Unorm).unwrap();
+    assert_eq!(linear.data, raw.data);
+    assert_eq!(
+        raw.into_image(TextureFormat::Rgba16Float).unwrap_err(),
+        UnsupportedFormat(TextureFormat::Rgba16Float)
+    );
+}
+
+#[test]
+fn collision_hull_from_alpha() {
+    use crate::asset::FrameCollision;
+    use crate::process::{convex_hull, RawImage};
+    use bevy::math::Vec2;
+
+    // A 3x2 image:
+    // X . .
+    // X X o
+    // where X is opaque and o is below the alpha threshold.
+    let alpha = [255, 0, 0, 255, 255, 100];
+    let image = RawImage {
+        width: 3,
+        height: 2,
+        data: alpha.iter().flat_map(|&a| [0, 0, 0, a]).collect(),
+    };
+    let hull = convex_hull(&image, 128);
+    assert_eq!(hull, [(0, 2), (2, 2), (2, 1), (1, 0), (0, 0)]);
+
+    let hull = hull
+        .iter()
+        .map(|&(x, y)| Vec2::new(x as f32, -(y as f32)))
+        .collect();
+    let collision = FrameCollision::new(hull);
+    assert!(collision.contains(Vec2::new(0.5, -1.5)));
+    assert!(!collision.contains(Vec2::new(2.5, -0.5)));
+}
+
+#[test]
+fn animation_hit_test_uses_alpha() {
+    use crate::asset::{Animation, Frame, Sprite};
+    use crate::process::RawImage;
+    use bevy::prelude::*;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_asset::<Image>()
+        .add_asset::<TextureAtlas>();
+    // A 2x2 atlas holding two 1x2 frames. Only the top pixel of frame 1 is opaque.
+    let alpha = [0, 255, 0, 0];
+    let image = RawImage {
+        width: 2,
+        height: 2,
+        data: alpha.iter().flat_map(|&a| [0, 0, 0, a]).collect(),
+    };
+    let image = app.world.resource_mut::<Assets<Image>>().add(image.into());
+    let mut atlas = TextureAtlas::new_empty(image, Vec2::new(2.0, 2.0));
+    atlas.add_texture(Rect::new(0.0, 0.0, 1.0, 2.0));
+    atlas.add_texture(Rect::new(1.0, 0.0, 2.0, 2.0));
+    let atlas = app.world.resource_mut::<Assets<TextureAtlas>>().add(atlas);
+    let frames = (0..2)
+        .map(|atlas_index| Frame {
+            sprite: Sprite { atlas_index },
+            duration_ms: 100,
+            opaque_bounds: None,
+            changed_bounds: None,
+        })
+        .collect();
+    let animation = Animation::new(frames, atlas);
+
+    let atlases = app.world.resource::<Assets<TextureAtlas>>();
+    let images = app.world.resource::<Assets<Image>>();
+    let top = Vec2::new(0.0, 0.5);
+    let bottom = Vec2::new(0.0, -0.5);
+    assert!(!animation.hit_test(0, top, atlases, images));
+    assert!(animation.hit_test(1, top, atlases, images));
+    assert!(!animation.hit_test(1, bottom, atlases, images));
+    assert!(!animation.hit_test(1, Vec2::new(1.0, 0.5), atlases, images));
+}
+
+#[test]
+fn player_clamps_frame_after_reload() {
+    use crate::asset::{Animation, Frame, Sprite};
+    use crate::player::{AnimationPlayer, PlayMode};
+    use bevy::{asset::Handle, utils::Duration};
+
+    let animation = |frame_count| {
+        let frames = (0..frame_count)
+            .map(|atlas_index| Frame {
+                sprite: Sprite { atlas_index },
+                duration_ms: 100,
+                opaque_bounds: None,
+                changed_bounds: None,
+            })
+            .collect();
+        Animation::new(frames, Handle::default())
+    };
+    let mut player = AnimationPlayer::new(Handle::default())
+        .with_mode(PlayMode::Once)
+        .with_hold_last_frame(true);
+    assert!(player.tick(&animation(4), Duration::from_millis(500)));
+    assert_eq!(player.frame(), 3);
+
+    // The finished player stays on its last frame, which no longer exists.
+    player.tick(&animation(2), Duration::from_millis(100));
+    assert_eq!(player.frame(), 1);
+}
+
+// Imports "sprites/hello.aseprite" into an app with only the resources added by `configure`.
+fn import_with(configure: impl FnOnce(&mut bevy::app::App)) -> bevy::app::App {
+    use crate::asset::AseAsset;
+    use crate::loader::{ase_importer, AseAssetLoader, Loader};
+    use bevy::prelude::*;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_asset::<AseAsset>()
+        .init_resource::<Loader>()
+        .init_asset_loader::<AseAssetLoader>()
+        .add_system(ase_importer);
+    configure(&mut app);
+    let handle: Handle<AseAsset> = app
+        .world
+        .resource::<AssetServer>()
+        .load("sprites/hello.aseprite");
+    app.world.resource_mut::<Loader>().add(handle);
+    update_until_loaded(&mut app);
+    assert!(app.world.resource::<Loader>().is_loaded());
+    app
+}
+
+#[test]
+fn import_skips_missing_resources() {
+    use crate::asset::{Animation, AseFileMap};
+    use crate::handle_id;
+    use bevy::prelude::*;
+
+    let path = "sprites/hello.aseprite";
+
+    // Nothing to import into.
+    import_with(|_| {});
+
+    // Only the file map: the file is indexed without assets.
+    let app = import_with(|app| {
+        app.init_resource::<AseFileMap>();
+    });
+    let file_map = app.world.resource::<AseFileMap>();
+    assert!(file_map.get(path).is_some());
+    assert!(file_map.labels(path).is_empty());
+
+    // Images and atlases without a file map: assets exist under their handle ids.
+    let app = import_with(|app| {
+        app.add_asset::<Image>().add_asset::<TextureAtlas>();
+    });
+    let atlases = app.world.resource::<Assets<TextureAtlas>>();
+    assert!(atlases.get(handle_id::atlas(path)).is_some());
+
+    // Animations without images.
+    let app = import_with(|app| {
+        app.add_asset::<Animation>().init_resource::<AseFileMap>();
+    });
+    let file_map = app.world.resource::<AseFileMap>();
+    let labels = file_map.labels(path);
+    assert!(labels.iter().all(|label| label.starts_with("Animation/")));
+}
+
+#[test]
+fn pre_update_import_is_visible_in_update() {
+    use crate::asset::AseAsset;
+    use crate::loader::{AseFileMapChanged, AseLoaderPlugin, Loader};
+    use bevy::prelude::*;
+
+    #[derive(Resource, Default)]
+    struct Seen(bool);
+
+    fn see_changes(mut events: EventReader<AseFileMapChanged>, mut seen: ResMut<Seen>) {
+        seen.0 |= events.iter().count() > 0;
+    }
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(AseLoaderPlugin {
+            base_set: CoreSet::PreUpdate,
+            ..Default::default()
+        })
+        .init_resource::<Seen>()
+        .add_system(see_changes);
+    let handle: Handle<AseAsset> = app
+        .world
+        .resource::<AssetServer>()
+        .load("sprites/hello.aseprite");
+    app.world.resource_mut::<Loader>().add(handle);
+
+    update_until_loaded(&mut app);
+    // The change was seen on the frame the file was imported.
+    assert!(app.world.resource::<Loader>().is_loaded());
+    assert!(app.world.resource::<Seen>().0);
+}
+
+#[test]
+fn scoped_loader_tracks_its_files() {
+    use crate::asset::AseAsset;
+    use crate::loader::{AseLoaderDefaultPlugin, Loader};
+    use bevy::prelude::*;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(AseLoaderDefaultPlugin);
+    let asset_server = app.world.resource::<AssetServer>().clone();
+    let hello: Handle<AseAsset> = asset_server.load("sprites/hello.aseprite");
+    let tiles: Handle<AseAsset> = asset_server.load("sprites/tiles.aseprite");
+    let mut loader = app.world.resource_mut::<Loader>();
+    loader.scoped("ui").add(hello);
+    loader.add(tiles);
+    assert_eq!(loader.scoped("ui").pending_count(), 1);
+    assert!(!loader.is_scope_loaded("ui"));
+    assert!(loader.is_scope_loaded("level"));
+
+    update_until_loaded(&mut app);
+    let loader = app.world.resource::<Loader>();
+    assert!(loader.is_loaded());
+    assert!(loader.is_scope_loaded("ui"));
+}
+
+#[test]
+fn load_group_unloads_its_assets() {
+    use crate::asset::{AseAsset, AseFileMap};
+    use crate::handle_id;
+    use crate::loader::{AseLoaderDefaultPlugin, Loader};
+    use bevy::prelude::*;
+
+    let path = "sprites/hello.aseprite";
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(AseLoaderDefaultPlugin);
+    let handle: Handle<AseAsset> = app.world.resource::<AssetServer>().load(path);
+    let group = app.world.resource_mut::<Loader>().add_group([handle]);
+    assert!(!app.world.resource::<Loader>().is_group_loaded(group));
+
+    update_until(&mut app, |app| {
+        app.world.resource::<Loader>().is_group_loaded(group)
+    });
+    assert!(app.world.resource::<Loader>().is_group_loaded(group));
+    assert!(app.world.resource::<AseFileMap>().get(path).is_some());
+    let atlases = app.world.resource::<Assets<TextureAtlas>>();
+    assert!(atlases.get(handle_id::atlas(path)).is_some());
+
+    app.world.resource_mut::<Loader>().unload_group(group);
+    app.update();
+    assert!(!app.world.resource::<Loader>().is_group_loaded(group));
+    assert!(app.world.resource::<AseFileMap>().get(path).is_none());
+    let atlases = app.world.resource::<Assets<TextureAtlas>>();
+    assert!(atlases.get(handle_id::atlas(path)).is_none());
+}
+
+#[test]
+fn unload_removes_file_sub_assets() {
+    use crate::asset::{Animation, AseAsset, AseFileMap};
+    use crate::handle_id;
+    use crate::loader::{AseLoaderDefaultPlugin, Loader};
+    use bevy::prelude::*;
+
+    let path = "sprites/hello.aseprite";
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(AseLoaderDefaultPlugin);
+    let handle: Handle<AseAsset> = app.world.resource::<AssetServer>().load(path);
+    app.world.resource_mut::<Loader>().add(handle);
+    update_until_loaded(&mut app);
+    let asset_map = app.world.resource::<AseFileMap>().get(path).unwrap();
+    let animations: Vec<_> = asset_map.animations.values().cloned().collect();
+    let textures: Vec<_> = asset_map.textures.values().cloned().collect();
+    assert!(!animations.is_empty());
+    assert!(!textures.is_empty());
+
+    app.world.resource_mut::<Loader>().unload(path);
+    app.update();
+    assert!(app.world.resource::<AseFileMap>().get(path).is_none());
+    let atlases = app.world.resource::<Assets<TextureAtlas>>();
+    assert!(atlases.get(handle_id::atlas(path)).is_none());
+    let animation_assets = app.world.resource::<Assets<Animation>>();
+    assert!(animations.iter().all(|h| animation_assets.get(h).is_none()));
+    let images = app.world.resource::<Assets<Image>>();
+    assert!(textures.iter().all(|h| images.get(h).is_none()));
+}
+
+#[test]
+fn unload_drops_queued_file() {
+    use crate::asset::{AseAsset, AseFileMap};
+    use crate::handle_id;
+    use crate::loader::{AseLoaderDefaultPlugin, Loader};
+    use bevy::asset::LoadState;
+    use bevy::prelude::*;
+
+    let path = "sprites/hello.aseprite";
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(AseLoaderDefaultPlugin);
+    let handle: Handle<AseAsset> = app.world.resource::<AssetServer>().load(path);
+    let mut loader = app.world.resource_mut::<Loader>();
+    loader.add(handle.clone());
+    loader.unload(path);
+    assert!(loader.is_loaded());
+
+    update_until(&mut app, |app| {
+        app.world.resource::<AssetServer>().get_load_state(&handle) == LoadState::Loaded
+    });
+    for _ in 0..10 {
+        app.update();
+    }
+    assert!(app.world.resource::<Loader>().is_loaded());
+    assert!(app.world.resource::<AseFileMap>().get(path).is_none());
+    let atlases = app.world.resource::<Assets<TextureAtlas>>();
+    assert!(atlases.get(handle_id::atlas(path)).is_none());
+}
+
+#[test]
+fn load_ticket_resolves_with_file_handles() {
+    use crate::asset::AseAsset;
+    use crate::handle_id;
+    use crate::loader::{AseLoaderDefaultPlugin, Loader};
+    use bevy::prelude::*;
+
+    let path = "sprites/hello.aseprite";
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(AseLoaderDefaultPlugin);
+    let handle: Handle<AseAsset> = app.world.resource::<AssetServer>().load(path);
+    let mut ticket = app.world.resource_mut::<Loader>().add_awaitable(handle);
+    assert!(ticket.try_recv().is_none());
+
+    let mut result = None;
+    update_until(&mut app, |_| {
+        result = ticket.try_recv();
+        result.is_some()
+    });
+    let file = result.unwrap().unwrap();
+    assert_eq!(file.path, std::path::Path::new(path));
+    let atlas: Handle<TextureAtlas> = file.get("Atlas").unwrap();
+    assert_eq!(atlas.id(), handle_id::atlas(path));
+    assert!(file.labels().any(|label| label == "FrameImage0"));
+}
+
+#[test]
+fn blocking_load_imports_immediately() {
+    use crate::asset::AseFileMap;
+    use crate::handle_id;
+    use crate::load_file_blocking;
+    use crate::loader::AseLoaderDefaultPlugin;
+    use bevy::prelude::*;
+
+    let path = test_path("groups");
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(AseLoaderDefaultPlugin);
+    load_file_blocking(&path, &mut app.world).unwrap();
+    assert!(app.world.resource::<AseFileMap>().get(&path).is_some());
+    let atlases = app.world.resource::<Assets<TextureAtlas>>();
+    assert!(atlases.get(handle_id::atlas(&path)).is_some());
+
+    assert!(load_file_blocking(test_path("missing"), &mut app.world).is_err());
+}
+
+#[test]
+fn palette_variants_recolor_the_atlas() {
+    use crate::asset::AseFileMap;
+    use crate::load_file_blocking;
+    use crate::loader::AseLoaderDefaultPlugin;
+    use bevy::prelude::*;
+
+    // indexed.palette1.hex swaps the file's red for green.
+    let path = test_path("indexed");
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(AseLoaderDefaultPlugin);
+    load_file_blocking(&path, &mut app.world).unwrap();
+    let file_map = app.world.resource::<AseFileMap>();
+    let asset_map = file_map.get(&path).unwrap();
+    assert_eq!(asset_map.palette_count(), 1);
+    assert_eq!(asset_map.atlas_for_palette(0), Some(asset_map.atlas()));
+    assert!(asset_map.atlas_for_palette(2).is_none());
+    let atlases = app.world.resource::<Assets<TextureAtlas>>();
+    let images = app.world.resource::<Assets<Image>>();
+    let first_pixel = |atlas: &Handle<TextureAtlas>| {
+        let atlas = atlases.get(atlas).unwrap();
+        images.get(&atlas.texture).unwrap().data[..4].to_vec()
+    };
+    assert_eq!(first_pixel(asset_map.atlas()), [255, 0, 0, 255]);
+    let variant = asset_map.atlas_for_palette(1).unwrap();
+    assert_eq!(first_pixel(variant), [0, 255, 0, 255]);
+    assert_eq!(
+        atlases.get(variant).unwrap().textures,
+        atlases.get(asset_map.atlas()).unwrap().textures
+    );
+}
+
+#[test]
+fn reimport_changes_generation() {
+    use crate::asset::AseFileMap;
+    use crate::load_file_blocking;
+    use crate::loader::AseLoaderDefaultPlugin;
+    use bevy::prelude::*;
+
+    let path = test_path("groups");
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(AseLoaderDefaultPlugin);
+    let generation = |app: &App| {
+        app.world
+            .resource::<AseFileMap>()
+            .get(&path)
+            .unwrap()
+            .generation()
+    };
+    load_file_blocking(&path, &mut app.world).unwrap();
+    let first = generation(&app);
+    assert!(first > 0);
+    load_file_blocking(&path, &mut app.world).unwrap();
+    assert!(generation(&app) > first);
+}
+
+#[test]
+fn file_load_state_waits_for_import() {
+    use crate::asset::{AseAsset, AseFileMap};
+    use crate::loader::{AseLoaderDefaultPlugin, Loader};
+    use bevy::{asset::LoadState, prelude::*};
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(AseLoaderDefaultPlugin);
+    let handle: Handle<AseAsset> = app
+        .world
+        .resource::<AssetServer>()
+        .load("sprites/hello.aseprite");
+    app.world.resource_mut::<Loader>().add(handle.clone());
+    let state = |app: &App| {
+        let asset_server = app.world.resource::<AssetServer>();
+        let file_map = app.world.resource::<AseFileMap>();
+        let loader = app.world.resource::<Loader>();
+        loader.file_load_state(&handle, asset_server, file_map)
+    };
+    assert_eq!(state(&app), LoadState::Loading);
+    update_until_loaded(&mut app);
+    assert_eq!(state(&app), LoadState::Loaded);
+}
+
+#[test]
+fn failed_load_is_reported_after_retries() {
+    use crate::asset::{AseAsset, AseFileMap};
+    use crate::loader::{AseImportError, AseLoaderDefaultPlugin, ImportError, LoadPolicy, Loader};
+    use bevy::prelude::*;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(AseLoaderDefaultPlugin);
+    let asset_server = app.world.resource::<AssetServer>().clone();
+    let missing: Handle<AseAsset> = asset_server.load("sprites/missing.aseprite");
+    let hello: Handle<AseAsset> = asset_server.load("sprites/hello.aseprite");
+    let mut loader = app.world.resource_mut::<Loader>();
+    loader.set_load_policy(LoadPolicy {
+        retries: 1,
+        timeout: None,
+    });
+    loader.add(missing);
+    loader.add(hello);
+
+    let mut errors = Vec::new();
+    update_until(&mut app, |app| {
+        let events = app.world.resource::<Events<AseImportError>>();
+        errors.extend(events.iter_current_update_events().cloned());
+        app.world.resource::<Loader>().is_loaded()
+    });
+    // The missing file doesn't keep the other file from being imported.
+    assert!(app.world.resource::<Loader>().is_loaded());
+    assert!(app
+        .world
+        .resource::<AseFileMap>()
+        .get("sprites/hello.aseprite")
+        .is_some());
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].path.to_str(), Some("sprites/missing.aseprite"));
+    assert_eq!(errors[0].error, ImportError::LoadFailed);
+}
+
+#[test]
+fn removed_asset_is_reported_instead_of_panicking() {
+    use crate::asset::AseAsset;
+    use crate::loader::{AseImportError, AseLoaderDefaultPlugin, ImportError, Loader};
+    use bevy::prelude::*;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(AseLoaderDefaultPlugin);
+    let asset_server = app.world.resource::<AssetServer>().clone();
+    let hello: Handle<AseAsset> = asset_server.load("sprites/hello.aseprite");
+    update_until(&mut app, |app| {
+        app.world.resource::<Assets<AseAsset>>().contains(&hello)
+    });
+    // Removed after loading, but before the importer processed it.
+    app.world.resource_mut::<Assets<AseAsset>>().remove(&hello);
+    app.world.resource_mut::<Loader>().add(hello);
+
+    app.update();
+    let events = app.world.resource::<Events<AseImportError>>();
+    let errors: Vec<_> = events.iter_current_update_events().cloned().collect();
+    assert!(app.world.resource::<Loader>().is_loaded());
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].path.to_str(), Some("sprites/hello.aseprite"));
+    assert_eq!(errors[0].error, ImportError::AssetRemoved);
+}
+
+#[test]
+fn layer_and_channel_labels_round_trip() {
+    use crate::handle_id::{self, label};
+
+    let layer = label::layer_image(3, "Body/Arm");
+    assert_eq!(layer, "FrameImage3/Layer/Body/Arm");
+    let Some(label::Parsed::LayerImage(frame, layer_name)) = label::parse(&layer) else {
+        panic!("Expected a layer image label");
+    };
+    assert_eq!((frame, layer_name), (3, "Body/Arm"));
+
+    let channel = label::channel_image(0, "Normal");
+    assert_eq!(channel, "FrameImage0/Channel/Normal");
+    let Some(label::Parsed::ChannelImage(frame, channel_name)) = label::parse(&channel) else {
+        panic!("Expected a channel image label");
+    };
+    assert_eq!((frame, channel_name), (0, "Normal"));
+
+    assert!(matches!(
+        label::parse("FrameImage3"),
+        Some(label::Parsed::FrameImage(3))
+    ));
+    assert!(label::parse("FrameImage3/Other/x").is_none());
+    assert_eq!(
+        handle_id::layer_image("a.aseprite", 3, "Body/Arm"),
+        "a.aseprite#FrameImage3/Layer/Body/Arm".into()
+    );
+}
+
+#[test]
+fn file_path_forms_agree() {
+    use crate::asset::AseFileMap;
+    use crate::handle_id;
+    use bevy::asset::AssetPath;
+    use std::path::{Path, PathBuf};
+
+    let path = Path::new("sprites/hero.aseprite");
+    let expected = handle_id::frame_image(path, 1);
+    assert_eq!(handle_id::frame_image("sprites/hero.aseprite", 1), expected);
+    assert_eq!(
+        handle_id::frame_image("sprites/hero.aseprite#Atlas", 1),
+        expected
+    );
+    assert_eq!(handle_id::frame_image(PathBuf::from(path), 1), expected);
+    assert_eq!(handle_id::frame_image(AssetPath::from(path), 1), expected);
+
+    let mut file_map = AseFileMap::default();
+    file_map.get_mut(AssetPath::from("sprites/hero.aseprite#Atlas"));
+    assert!(file_map.get(path).is_some());
+    assert!(file_map.get(&path.to_path_buf()).is_some());
+}
+
+#[test]
+fn label_kinds_match_labels() {
+    use crate::handle_id::{label, LabelKind};
+
+    let labels = [
+        (label::animation("walk"), LabelKind::Animation),
+        (label::atlas(), LabelKind::Atlas),
+        (label::lod_atlas(1), LabelKind::LodAtlas),
+        (label::palette_atlas(1), LabelKind::PaletteAtlas),
+        (label::outline_atlas(), LabelKind::OutlineAtlas),
+        (label::tag_atlas("walk"), LabelKind::TagAtlas),
+        (label::shadow_atlas(), LabelKind::ShadowAtlas),
+        (label::thumbnail(), LabelKind::Thumbnail),
+        (label::frame_image(2), LabelKind::FrameImage),
+        (label::frame_collision(2), LabelKind::FrameCollision),
+        (label::slice("hitbox"), LabelKind::Slice),
+        (label::text_placeholder("title"), LabelKind::TextPlaceholder),
+        (label::animation_events("walk"), LabelKind::AnimationEvents),
+        (label::tileset(1), LabelKind::Tileset),
+        (label::tileset_image(1), LabelKind::TilesetImage),
+        (label::layer_image(2, "Body"), LabelKind::LayerImage),
+        (label::channel_image(2, "Normal"), LabelKind::ChannelImage),
+    ];
+    for (label, kind) in labels {
+        assert_eq!(LabelKind::parse(&label), Some(kind), "{}", label);
+    }
+    for kind in LabelKind::ALL {
+        assert_eq!(LabelKind::parse(kind.as_str()), Some(kind));
+    }
+    assert_eq!(LabelKind::parse("Tileset"), Some(LabelKind::Tileset));
+    assert_eq!(LabelKind::parse("Sprite3"), None);
+}
+
+#[test]
+fn capabilities_match_processing() {
+    use crate::capabilities;
+    use crate::process::{process_file, ProcessSettings};
+
+    let capabilities = capabilities();
+    let file = test_support::load_file(test_path("tileset")).unwrap();
+    let processed = process_file(&file, &ProcessSettings::default());
+    assert!(capabilities.tilesets);
+    assert!(!processed.tilesets.is_empty());
+    assert!(capabilities.pixel_ratio);
+    assert!(capabilities.tag_repeat);
+    assert!(!capabilities.tile_user_data);
+}
+
+#[test]
+fn reference_layers_are_excluded() {
+    use crate::process::{process_file, ProcessSettings};
+
+    let file = test_support::load_file(test_path("reference")).unwrap();
+    let processed = process_file(&file, &ProcessSettings::default());
+    let image = processed.frames[0].image.as_ref().unwrap();
+    assert_eq!(image.data[..4], [255, 0, 0, 255]);
+    // The gray reference layer is left out.
+    assert_eq!(image.data[4..8], [0, 0, 0, 0]);
+
+    let settings = ProcessSettings {
+        reference_layers: true,
+        ..Default::default()
+    };
+    let processed = process_file(&file, &settings);
+    let image = processed.frames[0].image.as_ref().unwrap();
+    assert_eq!(image.data[4..8], [128, 128, 128, 255]);
+}
+
+#[test]
+fn composited_layers_match_frame_images() {
+    use crate::process::{composite_layers, FileHeader};
+    use asefile::AsepriteFile;
+
+    // "opacity" has a translucent layer and translucent cels, "blend" has a layer
+    // with each blend mode over a base layer.
+    for name in ["opacity", "blend", "groups", "indexed", "grayscale"] {
+        let bytes = std::fs::read(test_path(name)).unwrap();
+        let file = AsepriteFile::read(bytes.as_slice()).unwrap();
+        let header = FileHeader::read(&bytes);
+        for index in 0..file.num_frames() {
+            let image = composite_layers(&file, &header, index, |_| true);
+            assert_eq!(image.data, file.frame(index).image().into_raw(), "{name}");
+        }
+    }
+    let header = FileHeader::read(&std::fs::read(test_path("opacity")).unwrap());
+    assert_eq!(header.cel_opacities.len(), 2);
+    assert_eq!(header.cel_opacities.get(&(0, 1)), Some(&100));
+    assert_eq!(header.cel_opacities.get(&(1, 0)), Some(&200));
+}
+
+#[test]
+fn top_level_groups_are_flattened_separately() {
+    use crate::process::{process_file, LayerFlattening, ProcessSettings, RawImage};
+
+    let file = test_support::load_file(test_path("groups")).unwrap();
+    let settings = ProcessSettings {
+        flattening: LayerFlattening::TopLevelGroups,
+        ..Default::default()
+    };
+    let processed = process_file(&file, &settings);
+    let frame = &processed.frames[0];
+    let names: Vec<&str> = frame
+        .layer_images
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect();
+    assert_eq!(names, ["character", "fx", "shadow"]);
+    let pixel = |image: &RawImage, x: usize| image.data[x * 4..x * 4 + 4].to_vec();
+    let (_, character) = &frame.layer_images[0];
+    assert_eq!(pixel(character, 0), [255, 0, 0, 255]);
+    assert_eq!(pixel(character, 1), [0, 0, 0, 0]);
+    let (_, fx) = &frame.layer_images[1];
+    assert_eq!(pixel(fx, 1), [0, 0, 255, 255]);
+    assert_eq!(pixel(fx, 0), [0, 0, 0, 0]);
+    // The whole frame is still composited as well.
+    let image = frame.image.as_ref().unwrap();
+    assert_eq!(pixel(image, 2), [128, 128, 128, 255]);
+
+    let processed = process_file(&file, &ProcessSettings::default());
+    assert!(processed.frames[0].layer_images.is_empty());
+}
+
+#[test]
+fn shadow_layer_is_split_off() {
+    use crate::process::{process_file, ProcessSettings, RawImage};
+
+    let file = test_support::load_file(test_path("groups")).unwrap();
+    let settings = ProcessSettings {
+        shadow_layer: Some("shadow".into()),
+        ..Default::default()
+    };
+    let processed = process_file(&file, &settings);
+    let pixel = |image: &RawImage, x: usize| image.data[x * 4..x * 4 + 4].to_vec();
+    let image = processed.frames[0].image.as_ref().unwrap();
+    assert_eq!(pixel(image, 0), [255, 0, 0, 255]);
+    assert_eq!(pixel(image, 2), [0, 0, 0, 0]);
+    let shadow = processed.shadow.unwrap();
+    assert_eq!(shadow.frames.len(), 1);
+    assert_eq!(pixel(&shadow.image, 2), [128, 128, 128, 255]);
+    assert_eq!(pixel(&shadow.image, 0), [0, 0, 0, 0]);
+
+    let processed = process_file(&file, &ProcessSettings::default());
+    assert!(processed.shadow.is_none());
+}
+
+#[test]
+fn shadow_layer_keeps_cel_opacity() {
+    use crate::process::{process_file_with_header, FileHeader, ProcessSettings, RawImage};
+    use std::collections::BTreeSet;
+
+    // "Layer 2" has 160 opacity, with a cel of 100 opacity in frame 0,
+    // and "Layer 1" has a cel of 200 opacity in frame 1.
+    let bytes = std::fs::read(test_path("opacity")).unwrap();
+    let file = asefile::AsepriteFile::read(bytes.as_slice()).unwrap();
+    let settings = ProcessSettings {
+        shadow_layer: Some("Layer 1".into()),
+        ..Default::default()
+    };
+    let processed = process_file_with_header(&file, &FileHeader::read(&bytes), &settings);
+    let alphas = |image: &RawImage| image.data.iter().skip(3).step_by(4).copied();
+    let frame_alphas = |index: usize| -> BTreeSet<u8> {
+        alphas(processed.frames[index].image.as_ref().unwrap()).collect()
+    };
+    assert_eq!(frame_alphas(0), BTreeSet::from([0, 63]));
+    assert_eq!(frame_alphas(1), BTreeSet::from([0, 160]));
+
+    let shadow = processed.shadow.unwrap();
+    let shadow_alphas = |index: usize| -> BTreeSet<u8> {
+        let rect = &shadow.frames[index];
+        let stride = shadow.image.width as usize * 4;
+        (rect.y..rect.y + rect.height)
+            .flat_map(|y| {
+                let start = y as usize * stride + rect.x as usize * 4;
+                shadow.image.data[start..start + rect.width as usize * 4]
+                    .iter()
+                    .skip(3)
+                    .step_by(4)
+                    .copied()
+            })
+            .collect()
+    };
+    assert_eq!(shadow_alphas(0), BTreeSet::from([0, 255]));
+    assert_eq!(shadow_alphas(1), BTreeSet::from([0, 200]));
+}
+
+#[test]
+fn thumbnail_is_downscaled_with_nearest() {
+    use crate::process::{process_file, ProcessSettings, ThumbnailSettings};
+
+    let file = test_support::load_file(test_path("groups")).unwrap();
+    let settings = ProcessSettings {
+        thumbnail: Some(ThumbnailSettings {
+            max_size: 2,
+            tag: None,
+        }),
+        ..Default::default()
+    };
+    let thumbnail = process_file(&file, &settings).thumbnail.unwrap();
+    assert_eq!((thumbnail.width, thumbnail.height), (2, 2));
+    assert_eq!(thumbnail.data[0..4], [255, 0, 0, 255]);
+    assert_eq!(thumbnail.data[4..8], [128, 128, 128, 255]);
+
+    let processed = process_file(&file, &ProcessSettings::default());
+    assert!(processed.thumbnail.is_none());
+}
+
+#[test]
+fn lod_atlases_halve_each_level() {
+    use crate::process::{process_file, ProcessSettings};
+
+    let file = test_support::load_file(test_path("groups")).unwrap();
+    let settings = ProcessSettings {
+        lod_levels: 2,
+        ..Default::default()
+    };
+    let processed = process_file(&file, &settings);
+    let atlas = processed.atlas.unwrap();
+    assert_eq!(processed.lod_atlases.len(), 2);
+    let mut size = (atlas.frames[0].width, atlas.frames[0].height);
+    for lod_atlas in &processed.lod_atlases {
+        size = ((size.0 + 1) / 2, (size.1 + 1) / 2);
+        assert_eq!(lod_atlas.frames.len(), atlas.frames.len());
+        for frame in &lod_atlas.frames {
+            assert_eq!((frame.width, frame.height), size);
+        }
+    }
+
+    let processed = process_file(&file, &ProcessSettings::default());
+    assert!(processed.lod_atlases.is_empty());
+}
+
+#[test]
+fn outline_atlas_matches_atlas_layout() {
+    use crate::process::{process_file, OutlineSettings, OutlineStyle, ProcessSettings};
+
+    // The second frame of the indexed file has a 2x2 square at (1, 1).
+    let file = test_support::load_file(test_path("indexed")).unwrap();
+    let pixel_at = |style: OutlineStyle, x: u32, y: u32| {
+        let settings = ProcessSettings {
+            outline: Some(OutlineSettings {
+                style,
+                color: [0, 255, 0, 255],
+            }),
+            ..Default::default()
+        };
+        let processed = process_file(&file, &settings);
+        let outline = processed.outline.unwrap();
+        assert_eq!(outline.frames, processed.atlas.unwrap().frames);
+        let rect = outline.frames[1];
+        let start = (((rect.y + y) * outline.image.width + rect.x + x) * 4) as usize;
+        outline.image.data[start..start + 4].to_vec()
+    };
+    assert_eq!(pixel_at(OutlineStyle::Outline, 0, 1), [0, 255, 0, 255]);
+    assert_eq!(pixel_at(OutlineStyle::Outline, 1, 1), [0, 0, 0, 0]);
+    assert_eq!(pixel_at(OutlineStyle::Outline, 0, 0), [0, 0, 0, 0]);
+    assert_eq!(pixel_at(OutlineStyle::Silhouette, 1, 1), [0, 255, 0, 255]);
+    assert_eq!(pixel_at(OutlineStyle::Silhouette, 0, 1)[3], 0);
+
+    let processed = process_file(&file, &ProcessSettings::default());
+    assert!(processed.outline.is_none());
+}
+
+#[test]
+fn layer_z_orders_are_read_from_user_data() {
+    use crate::process::{process_file, ProcessSettings};
+
+    let file = test_support::load_file(test_path("groups")).unwrap();
+    let processed = process_file(&file, &ProcessSettings::default());
+    let z_orders: Vec<(&str, Option<f32>)> = processed
+        .layers
+        .iter()
+        .map(|layer| (layer.name.as_str(), layer.z_order))
+        .collect();
+    assert_eq!(
+        z_orders,
+        [
+            ("character", None),
+            ("body", None),
+            ("fx", Some(5.0)),
+            ("spark", None),
+            ("shadow", Some(-1.5)),
+        ]
+    );
+}
+
+#[test]
+fn atlas_usage_measures_visible_pixels() {
+    let resources = test_support::load_resources(test_path("indexed")).unwrap();
+    let usage = resources.atlas_usage().unwrap();
+    assert_eq!(usage.frame_occupancy.len(), resources.frame_count());
+    // The second frame only shows a 2x2 square on its 4x4 canvas.
+    assert_eq!(usage.frame_occupancy[1], 0.25);
+    let area = usage.size.0 as f32 * usage.size.1 as f32;
+    assert_eq!(usage.fill_ratio(), usage.used_pixels as f32 / area);
+    assert_eq!(usage.fill_ratio() + usage.wasted_ratio(), 1.0);
+}
+
+#[test]
+fn atlas_per_tag_replaces_file_atlas() {
+    use crate::process::{pack_atlas, process_file, ProcessSettings, ProcessedTagAtlas};
+
+    let file = test_support::load_file(test_path("groups")).unwrap();
+    let settings = ProcessSettings {
+        atlas_per_tag: true,
+        keep_frame_images: false,
+        ..Default::default()
+    };
+    let processed = process_file(&file, &settings);
+    assert!(processed.atlas.is_none());
+    assert_eq!(processed.tag_atlases.len(), file.num_tags() as usize);
+    // Without a file atlas, frame images are kept.
+    assert!(processed.frames.iter().all(|frame| frame.image.is_some()));
+
+    let image = processed.frames[0].image.clone().unwrap();
+    let tag_atlas = ProcessedTagAtlas {
+        tag_name: "walk".to_string(),
+        first_frame: 2,
+        atlas: pack_atlas(&[&image, &image]).unwrap(),
+    };
+    assert!(!tag_atlas.contains(1));
+    assert!(tag_atlas.contains(2));
+    assert!(tag_atlas.contains(3));
+    assert!(!tag_atlas.contains(4));
+}
+
+#[test]
+fn external_aliases_resolve_across_files() {
+    use crate::asset::{Animation, AseFileMap};
+    use bevy::asset::{Handle, HandleId};
+    use std::path::Path;
+
+    let mut file_map = AseFileMap::default();
+    let handle: Handle<Animation> = Handle::weak(HandleId::random::<Animation>());
+    let hero = file_map.get_mut("sprites/hero.aseprite");
+    let frames = (0..4).map(|frame| (frame, frame)).collect();
+    hero.insert_animation("walk".to_string(), handle.clone(), frames);
+    let villager = file_map.get_mut("sprites/villager.aseprite");
+    villager.add_external_alias("stroll", "sprites/hero.aseprite", "walk");
+    villager.add_external_alias("loop", "sprites/villager.aseprite", "loop");
+
+    let stroll = file_map
+        .animation("sprites/villager.aseprite", "stroll")
+        .unwrap();
+    assert_eq!(stroll.handle(), &handle);
+    assert_eq!(stroll.path(), Path::new("sprites/hero.aseprite"));
+    assert_eq!(stroll.frame_count(), 4);
+    assert_eq!(
+        file_map.labeled("sprites/villager.aseprite#Animation/stroll"),
+        Some(handle.clone_untyped())
+    );
+    // Aliases pointing at themselves give up instead of looping.
+    assert!(file_map
+        .animation("sprites/villager.aseprite", "loop")
+        .is_none());
+}
+
+#[test]
+fn dedicated_pool_processes_files() {
+    use crate::asset::{AseAsset, AseFileMap};
+    use crate::loader::{AseLoaderPlugin, Loader, ProcessingPool};
+    use bevy::prelude::*;
+    use std::path::Path;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(AseLoaderPlugin {
+            processing_pool: ProcessingPool::Dedicated { threads: 1 },
+            ..Default::default()
+        });
+    let loader = app.world.resource::<Loader>();
+    assert_eq!(
+        loader.processing_pool(),
+        ProcessingPool::Dedicated { threads: 1 }
+    );
+    let handle: Handle<AseAsset> = app
+        .world
+        .resource::<AssetServer>()
+        .load("sprites/hello.aseprite");
+    app.world.resource_mut::<Loader>().add(handle);
+
+    update_until_loaded(&mut app);
+    let file_map = app.world.resource::<AseFileMap>();
+    assert!(file_map.get(Path::new("sprites/hello.aseprite")).is_some());
+}
+
+#[test]
+fn processing_limits_hold_back_queued_files() {
+    use crate::asset::{AseAsset, AseFileMap};
+    use crate::loader::{AseLoaderDefaultPlugin, Loader, ProcessingLimits};
+    use bevy::prelude::*;
+    use std::path::Path;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(AseLoaderDefaultPlugin);
+    let mut loader = app.world.resource_mut::<Loader>();
+    loader.set_processing_limits(ProcessingLimits {
+        max_files: Some(1),
+        max_bytes: None,
+    });
+    for path in ["sprites/hello.aseprite", "sprites/tiles.aseprite"] {
+        let handle: Handle<AseAsset> = app.world.resource::<AssetServer>().load(path);
+        app.world.resource_mut::<Loader>().add(handle);
+    }
+
+    update_until(&mut app, |app| {
+        let loader = app.world.resource::<Loader>();
+        assert!(loader.pending_count() <= 1);
+        loader.is_loaded()
+    });
+    let file_map = app.world.resource::<AseFileMap>();
+    assert!(file_map.get(Path::new("sprites/hello.aseprite")).is_some());
+    assert!(file_map.get(Path::new("sprites/tiles.aseprite")).is_some());
+}
+
+#[test]
+fn hint_moves_file_to_front_of_queue() {
+    use crate::asset::AseAsset;
+    use crate::loader::{AseLoaderDefaultPlugin, Loader};
+    use bevy::prelude::*;
+    use std::path::PathBuf;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(AseLoaderDefaultPlugin);
+    for path in ["sprites/hello.aseprite", "sprites/tiles.aseprite"] {
+        let handle: Handle<AseAsset> = app.world.resource::<AssetServer>().load(path);
+        app.world.resource_mut::<Loader>().add(handle);
+    }
+
+    let mut loader = app.world.resource_mut::<Loader>();
+    assert!(loader.hint_needed_soon("sprites/tiles.aseprite"));
+    assert!(!loader.hint_needed_soon("sprites/missing.aseprite"));
+    let queued = app
+        .world
+        .resource::<Loader>()
+        .queued_paths(app.world.resource::<AssetServer>());
+    assert_eq!(
+        queued,
+        [
+            PathBuf::from("sprites/tiles.aseprite"),
+            PathBuf::from("sprites/hello.aseprite")
+        ]
+    );
+}
+
+#[test]
+fn sub_asset_events_follow_import() {
+    use crate::asset::AseAsset;
+    use crate::handle_id::{self, LabelKind};
+    use crate::loader::{AseLoaderDefaultPlugin, AseSubAssetCreated, Loader};
+    use bevy::prelude::*;
+    use std::path::Path;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(AseLoaderDefaultPlugin);
+    let handle: Handle<AseAsset> = app
+        .world
+        .resource::<AssetServer>()
+        .load("sprites/hello.aseprite");
+    app.world.resource_mut::<Loader>().add(handle);
+    update_until_loaded(&mut app);
+
+    let events = app.world.resource::<Events<AseSubAssetCreated>>();
+    let created: Vec<_> = events.get_reader().iter(events).cloned().collect();
+    let atlas = created
+        .iter()
+        .find(|event| event.kind == LabelKind::Atlas)
+        .unwrap();
+    assert_eq!(atlas.path, Path::new("sprites/hello.aseprite"));
+    assert_eq!(atlas.label, "Atlas");
+    assert_eq!(
+        atlas.handle.id(),
+        handle_id::atlas("sprites/hello.aseprite")
+    );
+    assert!(created
+        .iter()
+        .any(|event| event.kind == LabelKind::FrameImage));
+}
+
+#[test]
+fn debug_labels_name_textures() {
+    use crate::process::{FileHeader, ProcessSettings};
+    use crate::processing::ResourceData;
+
+    let path = test_path("indexed");
+    let file = test_support::load_file(&path).unwrap();
+    let settings = ProcessSettings {
+        debug_labels: true,
+        ..Default::default()
+    };
+    let data = ResourceData::new(&path, &file, &FileHeader::default(), &[], &settings).unwrap();
+    let atlas = data.atlas.unwrap();
+    let expected = format!("{}#Atlas", path.display());
+    assert_eq!(
+        atlas.image.texture_descriptor.label,
+        Some(expected.as_str())
+    );
+    // The file has no tags, so frames are named after their labels.
+    let frame = data.sprites[1].texture.as_ref().unwrap();
+    let expected = format!("{}#FrameImage1", path.display());
+    assert_eq!(frame.texture_descriptor.label, Some(expected.as_str()));
+
+    let data = test_support::load_resources(&path).unwrap();
+    assert_eq!(data.frame_image(0).unwrap().texture_descriptor.label, None);
+}
+
+#[test]
+fn memory_report_counts_file_images() {
+    use crate::asset::{Animation, AseFileMap};
+    use crate::handle_id;
+    use bevy::prelude::*;
+
+    let path = "sprites/hello.aseprite";
+    let app = import_with(|app| {
+        app.add_asset::<Image>()
+            .add_asset::<TextureAtlas>()
+            .add_asset::<Animation>()
+            .init_resource::<AseFileMap>();
+    });
+    let report = crate::memory_report(&app.world);
+    assert_eq!(report.files.len(), 1);
+    let file = &report.files[0];
+    assert_eq!(file.path, std::path::Path::new(path));
+
+    let atlases = app.world.resource::<Assets<TextureAtlas>>();
+    let images = app.world.resource::<Assets<Image>>();
+    let atlas = atlases.get(handle_id::atlas(path)).unwrap();
+    let atlas_bytes = images.get(&atlas.texture).unwrap().data.len();
+    assert_eq!(file.atlas_bytes, atlas_bytes);
+    assert_eq!(report.total_bytes(), file.total_bytes());
+
+    let animations = app.world.resource::<Assets<Animation>>();
+    for (_, animation) in animations.iter() {
+        assert!(animation.approx_size_bytes(atlases, images) > atlas_bytes);
+    }
+
+    assert!(crate::memory_report(&World::new()).files.is_empty());
+}
+
+#[test]
+fn tiles_can_be_edited_at_runtime() {
+    use crate::asset::{TileSize, TileWriteError, Tileset};
+    use crate::process::{FileHeader, ProcessSettings, RawImage};
+    use crate::processing::ResourceData;
+    use bevy::prelude::*;
+    use bevy::render::render_resource::TextureUsages;
+
+    let path = test_path("tileset");
+    let file = test_support::load_file(&path).unwrap();
+    let settings = ProcessSettings {
+        editable_tilesets: true,
+        ..Default::default()
+    };
+    let data = ResourceData::new(&path, &file, &FileHeader::default(), &[], &settings).unwrap();
+    let texture = data.tilesets[0].texture.as_ref().unwrap();
+    let usage = texture.texture_descriptor.usage;
+    assert!(usage.contains(TextureUsages::COPY_SRC | TextureUsages::COPY_DST));
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_asset::<Image>();
+    // Two 1x2 tiles, stacked vertically.
+    let image = RawImage {
+        width: 1,
+        height: 4,
+        data: vec![0; 16],
+    };
+    let mut images = app.world.resource_mut::<Assets<Image>>();
+    let tileset = Tileset {
+        id: 0,
+        tile_count: 2,
+        tile_size: TileSize {
+            width: 1,
+            height: 2,
+        },
+        name: "tiles".to_string(),
+        texture: images.add(image.into()),
+    };
+    let pixels = [255; 8];
+    tileset.write_tile(&mut images, 1, &pixels).unwrap();
+    let data = &images.get(&tileset.texture).unwrap().data;
+    assert_eq!(data[..8], [0; 8]);
+    assert_eq!(data[8..], pixels);
+
+    let error = tileset.write_tile(&mut images, 2, &pixels).unwrap_err();
+    assert!(matches!(error, TileWriteError::IndexOutOfRange { .. }));
+    let error = tileset
+        .write_tile(&mut images, 0, &pixels[..4])
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        TileWriteError::WrongLength {
+            expected: 8,
+            actual: 4
+        }
+    ));
+}
+
+#[test]
+fn composite_tileset_offsets_sources() {
+    use crate::asset::{CompositeTileset, CompositeTilesetError, TileSize, Tileset};
+    use crate::process::RawImage;
+    use bevy::prelude::*;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_asset::<Image>()
+        .add_asset::<Tileset>();
+    // A tileset of 1x1 tiles, each filled with its own value.
+    let mut add_tileset = |values: &[u8], height: u16| {
+        let image = RawImage {
+            width: 1,
+            height: values.len() as u32 * height as u32,
+            data: values
+                .iter()
+                .flat_map(|&value| [value; 4].repeat(height as usize))
+                .collect(),
+        };
+        let texture = app.world.resource_mut::<Assets<Image>>().add(image.into());
+        let tileset = Tileset {
+            id: 0,
+            tile_count: values.len() as u32,
+            tile_size: TileSize { width: 1, height },
+            name: "tiles".to_string(),
+            texture,
+        };
+        app.world.resource_mut::<Assets<Tileset>>().add(tileset)
+    };
+    let first = add_tileset(&[1, 2], 1);
+    let second = add_tileset(&[3, 4, 5], 1);
+    let tall = add_tileset(&[6], 2);
+
+    let world = &mut app.world;
+    world.resource_scope(|world, mut images: Mut<Assets<Image>>| {
+        let tilesets = world.resource::<Assets<Tileset>>();
+        let sources = [first.clone(), second.clone()];
+        let composite = CompositeTileset::compose(&sources, tilesets, &mut images).unwrap();
+        assert_eq!(composite.tile_count, 5);
+        assert_eq!(composite.texture_size(), Vec2::new(1.0, 5.0));
+        assert_eq!(composite.offset(&second), Some(2));
+        assert_eq!(composite.tile_index(&second, 1), Some(3));
+        assert_eq!(composite.tile_index(&second, 3), None);
+        assert_eq!(composite.tile_index(&tall, 0), None);
+        assert_eq!(composite.source_tile(3), Some((&second, 1)));
+        let texture = images.get(&composite.texture).unwrap();
+        assert_eq!(texture.texture_descriptor.size.height, 5);
+        // The tile at composite index 3 holds the second tileset's tile 1.
+        assert_eq!(texture.data[3 * 4], 4);
+
+        let sources = [first.clone(), tall.clone()];
+        let error = CompositeTileset::compose(&sources, tilesets, &mut images).unwrap_err();
+        assert_eq!(error, CompositeTilesetError::TileSizeMismatch(1));
+        let error = CompositeTileset::compose(&[], tilesets, &mut images).unwrap_err();
+        assert_eq!(error, CompositeTilesetError::NoSources);
+    });
+}
+
+#[test]
+fn tile_rects_follow_strip_layout() {
+    use crate::asset::{TileSize, Tileset};
+    use bevy::prelude::*;
+
+    let tileset = Tileset {
+        id: 0,
+        tile_count: 4,
+        tile_size: TileSize {
+            width: 8,
+            height: 16,
+        },
+        name: "tiles".to_string(),
+        texture: Handle::default(),
+    };
+    let rect = tileset.tile_rect(1).unwrap();
+    assert_eq!(rect.pixels, Rect::new(0.0, 16.0, 8.0, 32.0));
+    assert_eq!(rect.uv, Rect::new(0.0, 0.25, 1.0, 0.5));
+    assert!(rect.contains_uv(Vec2::new(0.5, 0.25)));
+    assert!(!rect.contains_uv(Vec2::new(0.5, 0.5)));
+    assert!(tileset.tile_rect(4).is_none());
+}
+
+#[test]
+fn tile_indices_survive_appended_tiles() {
+    use crate::asset::tileset::tile_index_changes;
+
+    // Tiles of 2 bytes: an empty tile followed by three distinct tiles.
+    let old = [0, 0, 1, 1, 2, 2, 3, 3];
+    let no_changes = (Vec::new(), Vec::new());
+
+    // Appending tiles and editing tiles in place keep every index.
+    let appended = [0, 0, 1, 1, 2, 2, 3, 3, 4, 4];
+    assert_eq!(tile_index_changes(&old, &appended, 2), no_changes);
+    let edited = [0, 0, 1, 1, 5, 5, 3, 3];
+    assert_eq!(tile_index_changes(&old, &edited, 2), no_changes);
+
+    // Inserting a tile moves every tile after it.
+    let inserted = [0, 0, 4, 4, 1, 1, 2, 2, 3, 3];
+    let changes = tile_index_changes(&old, &inserted, 2);
+    assert_eq!(changes, (vec![(1, 2), (2, 3), (3, 4)], Vec::new()));
+
+    // Removing the last tile.
+    let removed = [0, 0, 1, 1, 2, 2];
+    assert_eq!(tile_index_changes(&old, &removed, 2), (Vec::new(), vec![3]));
+
+    // Drawing on an empty tile doesn't count as moving it to the empty tile 0.
+    let old = [0, 0, 0, 0];
+    let drawn = [0, 0, 1, 1];
+    assert_eq!(tile_index_changes(&old, &drawn, 2), no_changes);
+}
+
+#[test]
+fn nine_slice_tiles_repeat_edges_and_center() {
+    use crate::asset::slice::{Slice9, SliceKey};
+    use crate::nine_slice::nine_slice_tiles;
+    use bevy::prelude::*;
+
+    // A 3x3 tile slice with one tile per corner, edge and center.
+    let key = SliceKey {
+        from_frame: 0,
+        origin: (0, 0),
+        size: (24, 24),
+        slice9: Some(Slice9 {
+            center_x: 8,
+            center_y: 8,
+            center_width: 8,
+            center_height: 8,
+        }),
+        pivot: None,
+    };
+    let tile_size = UVec2::splat(8);
+    let tiles = nine_slice_tiles(&key, tile_size, UVec2::new(4, 3)).unwrap();
+    assert_eq!(tiles.get(0, 0), Some(UVec2::new(0, 0)));
+    assert_eq!(tiles.get(1, 0), Some(UVec2::new(1, 0)));
+    assert_eq!(tiles.get(2, 1), Some(UVec2::new(1, 1)));
+    assert_eq!(tiles.get(3, 2), Some(UVec2::new(2, 2)));
+    assert_eq!(tiles.get(4, 0), None);
+    assert_eq!(
+        tiles.indices(3, 10),
+        vec![10, 11, 11, 12, 13, 14, 14, 15, 16, 17, 17, 18]
+    );
+
+    // Too small for the corners, or not aligned to tiles.
+    assert!(nine_slice_tiles(&key, tile_size, UVec2::new(1, 3)).is_none());
+    assert!(nine_slice_tiles(&key, UVec2::splat(5), UVec2::new(4, 3)).is_none());
+}
+
+#[test]
+fn frames_record_changed_bounds() {
+    use crate::process::{changed_bounds, process_file, PixelRect, ProcessSettings};
+
+    let file = test_support::load_file(test_path("indexed")).unwrap();
+    let processed = process_file(&file, &ProcessSettings::default());
+    let frames = &processed.frames;
+    for (index, frame) in frames.iter().enumerate() {
+        let previous = &frames[(index + frames.len() - 1) % frames.len()];
+        let expected = changed_bounds(
+            previous.image.as_ref().unwrap(),
+            frame.image.as_ref().unwrap(),
+        );
+        assert_eq!(frame.changed_bounds, expected);
+    }
+
+    // Without frame images, every frame may have changed.
+    let settings = ProcessSettings {
+        frame_images: false,
+        ..Default::default()
+    };
+    let processed = process_file(&file, &settings);
+    let canvas = PixelRect {
+        x: 0,
+        y: 0,
+        width: processed.width,
+        height: processed.height,
+    };
+    assert!(processed
+        .frames
+        .iter()
+        .all(|frame| frame.changed_bounds == Some(canvas)));
+}
+
+#[test]
+fn player_plays_frame_range() {
+    use crate::asset::{Animation, Frame, Sprite};
+    use crate::player::{AnimationPlayer, PlayMode};
+    use bevy::{asset::Handle, utils::Duration};
+
+    let frames = (0..10)
+        .map(|atlas_index| Frame {
+            sprite: Sprite { atlas_index },
+            duration_ms: 100,
+            opaque_bounds: None,
+            changed_bounds: None,
+        })
+        .collect();
+    let file = Animation::new(frames, Handle::default());
+
+    let mut player = AnimationPlayer::new(Handle::default()).with_mode(PlayMode::Loop);
+    player.play_range(5..=7);
+    player.tick(&file, Duration::ZERO);
+    assert_eq!(player.frame(), 5);
+    player.tick(&file, Duration::from_millis(250));
+    assert_eq!(player.frame(), 7);
+    // Loops back to the range's first frame.
+    player.tick(&file, Duration::from_millis(100));
+    assert_eq!(player.frame(), 5);
+
+    // Played once, the range finishes on its last frame.
+    let mut player = AnimationPlayer::once(Handle::default()).with_hold_last_frame(true);
+    player.play_range(8..=20);
+    assert!(player.tick(&file, Duration::from_millis(250)));
+    assert_eq!(player.frame(), 9);
+
+    // Ranges past the last frame play the whole animation.
+    player.play_range(12..=14);
+    player.tick(&file, Duration::ZERO);
+    assert_eq!(player.frame(), 0);
+    player.play(Handle::default());
+    assert!(player.range().is_none());
+}
+
+#[test]
+fn frame_table_numbers_animation_frames() {
+    use crate::asset::{Animation, AseFileMap};
+    use bevy::asset::{Handle, HandleId};
+
+    let path = "sprites/hero.aseprite";
+    let mut file_map = AseFileMap::default();
+    let asset_map = file_map.get_mut(path);
+    let handle = || Handle::<Animation>::weak(HandleId::random::<Animation>());
+    // "walk" is packed into its own tag atlas, starting at file frame 2.
+    asset_map.insert_animation("walk".to_string(), handle(), vec![(2, 0), (3, 1)]);
+    asset_map.insert_animation("idle".to_string(), handle(), vec![(0, 0), (1, 1)]);
+
+    let table = file_map.frame_table(path).unwrap();
+    assert_eq!(table.frames.len(), 4);
+    assert_eq!(table.id("idle", 1), Some(1));
+    assert_eq!(table.id("walk", 0), Some(2));
+    assert_eq!(table.id("walk", 2), None);
+    assert_eq!(table.id("run", 0), None);
+    let entry = table.entry(3).unwrap();
+    assert_eq!((entry.tag_name.as_str(), entry.offset), ("walk", 1));
+    assert_eq!((entry.frame, entry.atlas_index), (3, 1));
+
+    // Ids and fingerprints don't depend on insertion order.
+    let mut other = AseFileMap::default();
+    let asset_map = other.get_mut(path);
+    asset_map.insert_animation("idle".to_string(), handle(), vec![(0, 0), (1, 1)]);
+    asset_map.insert_animation("walk".to_string(), handle(), vec![(2, 0), (3, 1)]);
+    let other_table = other.frame_table(path).unwrap();
+    assert_eq!(other_table, table);
+    assert_eq!(other_table.fingerprint(), table.fingerprint());
+    assert!(file_map.frame_table("sprites/villager.aseprite").is_none());
+}
+
+#[test]
+fn auto_import_processes_loaded_files() {
+    use crate::asset::{AseAsset, AseFileMap};
+    use crate::loader::{AseLoaderPlugin, Loader};
+    use bevy::prelude::*;
+
+    let path = "sprites/hello.aseprite";
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(AseLoaderPlugin {
+            auto_import: true,
+            ..Default::default()
+        });
+    assert!(app.world.resource::<Loader>().auto_import());
+    let _handle: Handle<AseAsset> = app.world.resource::<AssetServer>().load(path);
+    update_until(&mut app, |app| {
+        app.world.resource::<AseFileMap>().get(path).is_some()
+    });
+    let labels = app.world.resource::<AseFileMap>().labels(path);
+    assert!(labels.contains(&"Atlas".to_string()));
+
+    // Taking the file's data out sends one Modified event, which must not queue it again.
+    let mut modified = 0;
+    for frame in 0..30 {
+        app.update();
+        let events = app.world.resource::<Events<AssetEvent<AseAsset>>>();
+        if frame >= 5 {
+            modified += events
+                .iter_current_update_events()
+                .filter(|event| matches!(event, AssetEvent::Modified { .. }))
+                .count();
+        }
+        assert_eq!(app.world.resource::<Loader>().pending_count(), 0);
+    }
+    assert_eq!(modified, 0);
+}
+
+#[test]
+fn file_imported_event_holds_main_assets() {
+    use crate::asset::{AseAsset, AseFileMap};
+    use crate::handle_id;
+    use crate::loader::{AseFileImported, AseLoaderDefaultPlugin, Loader};
+    use bevy::prelude::*;
+    use std::path::Path;
+
+    let path = "sprites/hello.aseprite";
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin::default())
+        .add_plugin(AseLoaderDefaultPlugin);
+    let handle: Handle<AseAsset> = app.world.resource::<AssetServer>().load(path);
+    app.world.resource_mut::<Loader>().add(handle);
+    update_until_loaded(&mut app);
+
+    let events = app.world.resource::<Events<AseFileImported>>();
+    let imported: Vec<_> = events.get_reader().iter(events).cloned().collect();
+    assert_eq!(imported.len(), 1);
+    let event = &imported[0];
+    assert_eq!(event.path, Path::new(path));
+    let atlas = event.atlas.as_ref().unwrap();
+    assert_eq!(atlas.id(), handle_id::atlas(path));
+    let asset_map = app.world.resource::<AseFileMap>().get(path).unwrap();
+    assert_eq!(event.animations.len(), asset_map.animations.len());
+    assert!(event
+        .animations
+        .windows(2)
+        .all(|pair| pair[0].0 <= pair[1].0));
+}
+
+#[test]
+fn player_state_restores_playback() {
+    use crate::asset::{Animation, Frame, Sprite};
+    use crate::player::{AnimationPlayer, PlayMode, PlayerState};
+    use bevy::{asset::Handle, utils::Duration};
+
+    let frames = (0..4)
+        .map(|atlas_index| Frame {
+            sprite: Sprite { atlas_index },
+            duration_ms: 100,
+            opaque_bounds: None,
+            changed_bounds: None,
+        })
+        .collect();
+    let animation = Animation::new(frames, Handle::default());
+
+    let mut player = AnimationPlayer::new(Handle::default()).with_mode(PlayMode::Once);
+    player.tick(&animation, Duration::from_nanos(150_000_001));
+    let state = player.save();
+    assert_eq!(state.frame, 1);
+    assert_eq!(state.elapsed_nanos, 50_000_001);
+
+    let step = Duration::from_millis(70);
+    let played: Vec<_> = (0..5)
+        .map(|_| {
+            player.tick(&animation, step);
+            player.frame()
+        })
+        .collect();
+    player.restore(state);
+    assert_eq!(player.save(), state);
+    let replayed: Vec<_> = (0..5)
+        .map(|_| {
+            player.tick(&animation, step);
+            player.frame()
+        })
+        .collect();
+    assert_eq!(played, replayed);
+
+    player.restore(PlayerState::default());
+    assert_eq!(player.frame(), 0);
+    assert!(!player.is_finished());
 }
@@ -0,0 +1,104 @@
+use crate::asset::{Animation, AseFileMap};
+use crate::player::AnimationPlayer;
+use bevy::prelude::*;
+use std::path::PathBuf;
+
+/// Component which tints an entity's [TextureAtlasSprite] with a color set in Aseprite.
+///
+/// The color is applied once, as soon as its source has been imported,
+/// and the component is then removed. This allows palette variants such as
+/// enemy recolors to be authored as tag or layer colors, without extra art.
+/// The [apply_tints] system applies the tint; it is added by
+/// [AnimationPlayerPlugin](crate::player::AnimationPlayerPlugin).
+///
+/// Sources without a color leave the sprite's color unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::asset::Animation;
+/// use bevy_ase::player::AnimationPlayer;
+/// use bevy_ase::tint::AseTint;
+///
+/// fn spawn_enemies(mut commands: Commands, walk: Handle<Animation>) {
+///     // Tinted with the color of the "walk" tag.
+///     commands.spawn((
+///         SpriteSheetBundle::default(),
+///         AnimationPlayer::new(walk.clone()),
+///         AseTint::Tag,
+///     ));
+///     // Tinted with the color of the "Elite" layer.
+///     commands.spawn((
+///         SpriteSheetBundle::default(),
+///         AnimationPlayer::new(walk),
+///         AseTint::layer("sprites/enemy.aseprite", "Elite"),
+///     ));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Component)]
+pub enum AseTint {
+    /// Use the tag color of the animation played by the entity's [AnimationPlayer].
+    Tag,
+    /// Use the color of a layer in an Aseprite file.
+    Layer {
+        /// Path of the Aseprite file.
+        path: PathBuf,
+        /// Name of the layer.
+        layer: String,
+    },
+}
+
+impl AseTint {
+    /// Creates an [AseTint::Layer] tint.
+    pub fn layer(path: impl Into<PathBuf>, layer: impl Into<String>) -> Self {
+        Self::Layer {
+            path: path.into(),
+            layer: layer.into(),
+        }
+    }
+
+    // Returns Some once the tint's source is available, with its color if it has one.
+    fn resolve(
+        &self,
+        player: Option<&AnimationPlayer>,
+        animations: &Assets<Animation>,
+        file_map: Option<&AseFileMap>,
+    ) -> Option<Option<Color>> {
+        match self {
+            AseTint::Tag => {
+                let animation = animations.get(player?.animation())?;
+                Some(animation.color())
+            }
+            AseTint::Layer { path, layer } => {
+                let asset_map = file_map?.get(path.as_path())?;
+                Some(asset_map.layer_color(layer))
+            }
+        }
+    }
+}
+
+/// System function which applies each [AseTint] to its entity's [TextureAtlasSprite].
+///
+/// This system is already added by [AnimationPlayerPlugin](crate::player::AnimationPlayerPlugin).
+pub fn apply_tints(
+    mut commands: Commands,
+    animations: Res<Assets<Animation>>,
+    file_map: Option<Res<AseFileMap>>,
+    mut sprites: Query<(
+        Entity,
+        &AseTint,
+        Option<&AnimationPlayer>,
+        &mut TextureAtlasSprite,
+    )>,
+) {
+    for (entity, tint, player, mut sprite) in &mut sprites {
+        let Some(color) = tint.resolve(player, &animations, file_map.as_deref()) else {
+            continue;
+        };
+        if let Some(color) = color {
+            sprite.color = color;
+        }
+        commands.entity(entity).remove::<AseTint>();
+    }
+}
@@ -0,0 +1,183 @@
+use crate::asset::AseAsset;
+use crate::loader::{Loader, DEFAULT_EXTENSIONS};
+use bevy::{asset::HandleId, prelude::*, utils::HashMap};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Plugin which imports every Aseprite file in an asset folder, including files added later.
+///
+/// The folder and its subfolders are scanned when the app starts, and then every
+/// `interval`. Each new file is loaded and queued on the [Loader]. Changed files are
+/// queued again when the [AssetServer] reloads them, which requires its
+/// `watch_for_changes` setting. Each queued file is announced with an [AseFolderEvent];
+/// import results are reported by the loader's usual events, such as
+/// [AseFileMapChanged](crate::loader::AseFileMapChanged).
+///
+/// This suits rapid prototyping: art dropped into the folder appears in the game.
+/// Requires the [Loader] resource, e.g. from
+/// [AseLoaderDefaultPlugin](crate::loader::AseLoaderDefaultPlugin).
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::loader::AseLoaderDefaultPlugin;
+/// use bevy_ase::watch::AseFolderWatchPlugin;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins(DefaultPlugins.set(AssetPlugin {
+///             watch_for_changes: true,
+///             ..default()
+///         }))
+///         .add_plugin(AseLoaderDefaultPlugin)
+///         .add_plugin(AseFolderWatchPlugin::new("sprites"));
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AseFolderWatchPlugin {
+    /// The watched folder, relative to the asset folder.
+    pub folder: PathBuf,
+    /// How often the folder is scanned for new files. Defaults to one second.
+    pub interval: Duration,
+    /// Extensions of the imported files. Defaults to `["aseprite", "ase"]`.
+    pub extensions: &'static [&'static str],
+}
+
+impl AseFolderWatchPlugin {
+    /// Creates a plugin watching the given folder, relative to the asset folder.
+    pub fn new(folder: impl Into<PathBuf>) -> Self {
+        Self {
+            folder: folder.into(),
+            interval: Duration::from_secs(1),
+            extensions: DEFAULT_EXTENSIONS,
+        }
+    }
+}
+
+impl Plugin for AseFolderWatchPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(AseFolderWatch {
+            folder: self.folder.clone(),
+            extensions: self.extensions,
+            timer: Timer::new(self.interval, TimerMode::Repeating),
+            scanned: false,
+            handles: HashMap::default(),
+        })
+        .add_event::<AseFolderEvent>()
+        .add_system(watch_ase_folder);
+    }
+}
+
+/// Event sent by [watch_ase_folder] when a file in the watched folder is queued on the [Loader].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AseFolderEvent {
+    /// A file was found for the first time.
+    Added {
+        /// Path of the Aseprite file.
+        path: PathBuf,
+    },
+    /// A file was reloaded after it changed.
+    Changed {
+        /// Path of the Aseprite file.
+        path: PathBuf,
+    },
+}
+
+/// Resource holding the state of [AseFolderWatchPlugin].
+///
+/// Keeps the handles of all files found in the watched folder, so they stay loaded.
+#[derive(Debug, Resource)]
+pub struct AseFolderWatch {
+    folder: PathBuf,
+    extensions: &'static [&'static str],
+    timer: Timer,
+    scanned: bool,
+    handles: HashMap<HandleId, Handle<AseAsset>>,
+}
+
+impl AseFolderWatch {
+    /// Returns the watched folder, relative to the asset folder.
+    pub fn folder(&self) -> &Path {
+        &self.folder
+    }
+    /// Returns the handles of all files found in the watched folder.
+    pub fn handles(&self) -> impl Iterator<Item = &Handle<AseAsset>> {
+        self.handles.values()
+    }
+
+    // Returns the paths of all files with a watched extension, including subfolders.
+    fn scan(&self, asset_server: &AssetServer) -> Vec<PathBuf> {
+        let asset_io = asset_server.asset_io();
+        let mut folders = vec![self.folder.clone()];
+        let mut files = Vec::new();
+        while let Some(folder) = folders.pop() {
+            let Ok(entries) = asset_io.read_directory(&folder) else {
+                continue;
+            };
+            for path in entries {
+                if asset_io.is_dir(&path) {
+                    folders.push(path);
+                } else if self.is_watched(&path) {
+                    files.push(path);
+                }
+            }
+        }
+        files.sort();
+        files
+    }
+
+    fn is_watched(&self, path: &Path) -> bool {
+        let Some(extension) = path.extension().and_then(|extension| extension.to_str()) else {
+            return false;
+        };
+        self.extensions
+            .iter()
+            .any(|watched| watched.eq_ignore_ascii_case(extension))
+    }
+}
+
+/// System function which queues new and changed files of the watched folder on the [Loader].
+///
+/// This system is already added by [AseFolderWatchPlugin].
+pub fn watch_ase_folder(
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mut watch: ResMut<AseFolderWatch>,
+    mut loader: ResMut<Loader>,
+    mut asset_events: EventReader<AssetEvent<AseAsset>>,
+    mut folder_events: EventWriter<AseFolderEvent>,
+) {
+    for event in asset_events.iter() {
+        let AssetEvent::Modified { handle } = event else {
+            continue;
+        };
+        let Some(handle) = watch.handles.get(&handle.id()) else {
+            continue;
+        };
+        let Some(path) = asset_server.get_handle_path(handle) else {
+            continue;
+        };
+        loader.add(handle.clone());
+        folder_events.send(AseFolderEvent::Changed {
+            path: path.path().to_path_buf(),
+        });
+    }
+
+    let finished = watch.timer.tick(time.delta()).just_finished();
+    if watch.scanned && !finished {
+        return;
+    }
+    watch.scanned = true;
+    for path in watch.scan(&asset_server) {
+        let handle: Handle<AseAsset> = asset_server.load(path.as_path());
+        if watch.handles.contains_key(&handle.id()) {
+            continue;
+        }
+        watch.handles.insert(handle.id(), handle.clone());
+        loader.add(handle);
+        folder_events.send(AseFolderEvent::Added { path });
+    }
+}
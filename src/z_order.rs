@@ -0,0 +1,70 @@
+use crate::asset::AseFileMap;
+use bevy::prelude::*;
+use std::path::PathBuf;
+
+/// Component which sets an entity's z translation from a layer's z-order set in Aseprite.
+///
+/// Artists set a layer's z-order with a `z:{value}` entry in its user data text, e.g. `z:5`.
+/// The z-order is applied once, as soon as the file has been imported, and the component
+/// is then removed. This lets artists control the draw order of decor sprites without
+/// code changes. The [apply_z_orders] system applies the z-order; it is added by
+/// [AnimationPlayerPlugin](crate::player::AnimationPlayerPlugin).
+///
+/// Layers without a z-order leave the translation unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_ase::z_order::AseZOrder;
+///
+/// fn spawn_decor(mut commands: Commands, tree: Handle<TextureAtlas>) {
+///     // Drawn at the z-order of the "Trees" layer.
+///     commands.spawn((
+///         SpriteSheetBundle {
+///             texture_atlas: tree,
+///             ..default()
+///         },
+///         AseZOrder::new("sprites/forest.aseprite", "Trees"),
+///     ));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Component)]
+pub struct AseZOrder {
+    /// Path of the Aseprite file.
+    pub path: PathBuf,
+    /// Name of the layer.
+    pub layer: String,
+}
+
+impl AseZOrder {
+    /// Creates an [AseZOrder] for a layer of an Aseprite file.
+    pub fn new(path: impl Into<PathBuf>, layer: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            layer: layer.into(),
+        }
+    }
+}
+
+/// System function which applies each [AseZOrder] to its entity's [Transform].
+///
+/// This system is already added by [AnimationPlayerPlugin](crate::player::AnimationPlayerPlugin).
+pub fn apply_z_orders(
+    mut commands: Commands,
+    file_map: Option<Res<AseFileMap>>,
+    mut transforms: Query<(Entity, &AseZOrder, &mut Transform)>,
+) {
+    let Some(file_map) = file_map else {
+        return;
+    };
+    for (entity, z_order, mut transform) in &mut transforms {
+        let Some(asset_map) = file_map.get(z_order.path.as_path()) else {
+            continue;
+        };
+        if let Some(z) = asset_map.layer_z_order(&z_order.layer) {
+            transform.translation.z = z;
+        }
+        commands.entity(entity).remove::<AseZOrder>();
+    }
+}